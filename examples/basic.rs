@@ -20,9 +20,7 @@ fn setup_camera(mut commands: Commands) {
 }
 
 fn draw_ui(terminal_res: Res<TerminalResource<MyTerminal>>) {
-    let mut terminal = terminal_res.0.lock().unwrap();
-
-    terminal
+    terminal_res
         .draw(|frame| {
             let area = frame.area();
             let block = Block::default()
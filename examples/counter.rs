@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 use bevy_terminal_emu::prelude::*;
 use ratatui::widgets::{Block, Borders, Paragraph};
@@ -7,12 +9,20 @@ struct MyTerminal;
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
-        .add_plugins(TerminalEmuPlugin::<MyTerminal>::default())
+        // Nothing animates, so 10 updates a second is plenty
+        .add_plugins(
+            TerminalEmuPlugin::<MyTerminal>::new().update_interval(Duration::from_millis(100)),
+        )
         .insert_resource(Counter(0))
         .add_systems(Startup, setup_camera)
         .add_systems(
             Update,
-            (handle_input, draw_ui).chain().in_set(TerminalSet::AppTick),
+            (
+                handle_input,
+                draw_ui.run_if(terminal_update_due::<MyTerminal>),
+            )
+                .chain()
+                .in_set(TerminalSet::AppTick),
         )
         .run();
 }
@@ -38,9 +48,7 @@ fn handle_input(mut queue: ResMut<TerminalInputQueue<MyTerminal>>, mut counter:
 }
 
 fn draw_ui(terminal_res: Res<TerminalResource<MyTerminal>>, counter: Res<Counter>) {
-    let mut terminal = terminal_res.0.lock().unwrap();
-
-    terminal
+    terminal_res
         .draw(|frame| {
             let area = frame.area();
             let block = Block::default()
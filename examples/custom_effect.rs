@@ -10,7 +10,7 @@ fn main() {
         .add_plugins(TerminalEmuPlugin::<MyTerminal>::default())
         .add_systems(Startup, (setup_camera, spawn_spin_effect))
         .add_systems(Update, draw_ui.in_set(TerminalSet::AppTick))
-        .add_systems(Update, spin_system.in_set(TerminalSet::Effects))
+        .add_systems(Update, spin_system.in_set(TerminalSet::GeometryEffects))
         .run();
 }
 
@@ -39,11 +39,12 @@ fn spawn_spin_effect(mut commands: Commands) {
 
 // Step 3: Write a system that queries effects and cells
 fn spin_system(
-    time: Res<Time>,
-    effects: Query<(&SpinEffect, &EffectRegion), With<TargetTerminal<MyTerminal>>>,
+    clock: Res<EffectClock<MyTerminal>>,
+    effects: Query<(&SpinEffect, &EffectRegion), RunningEffect<MyTerminal>>,
     mut cells: Query<(&GridPosition, &mut Transform), With<TerminalCell<MyTerminal>>>,
 ) {
-    let t = time.elapsed_secs();
+    // EffectClock respects the terminal's effect time-scale and pause
+    let t = clock.elapsed_secs();
 
     for (spin, region) in effects.iter() {
         for (pos, mut transform) in cells.iter_mut() {
@@ -61,9 +62,7 @@ fn spin_system(
 }
 
 fn draw_ui(terminal_res: Res<TerminalResource<MyTerminal>>) {
-    let mut terminal = terminal_res.0.lock().unwrap();
-
-    terminal
+    terminal_res
         .draw(|frame| {
             let area = frame.area();
             let block = Block::default()
@@ -74,7 +73,7 @@ fn draw_ui(terminal_res: Res<TerminalResource<MyTerminal>>) {
                  This demonstrates the 3-step custom effect pattern:\n\
                  1. Define a Component with effect params\n\
                  2. Write a system querying effects + cells\n\
-                 3. Register in TerminalSet::Effects\n\n\
+                 3. Register in TerminalSet::GeometryEffects\n\n\
                  No traits or registration boilerplate needed.",
             )
             .block(block);
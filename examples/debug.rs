@@ -10,7 +10,7 @@ fn main() {
         .add_plugins(TerminalEmuPlugin::<MyTerminal>::default())
         .add_systems(Startup, setup_camera)
         .add_systems(Update, draw_ui.in_set(TerminalSet::AppTick))
-        .add_systems(Update, debug_system)
+        .add_systems(Update, (debug_system, toggle_atlas_overlay))
         .run();
 }
 
@@ -25,9 +25,7 @@ fn setup_camera(mut commands: Commands) {
 }
 
 fn draw_ui(terminal_res: Res<TerminalResource<MyTerminal>>) {
-    let mut terminal = terminal_res.0.lock().unwrap();
-
-    terminal
+    terminal_res
         .draw(|frame| {
             let area = frame.area();
             let block = Block::default()
@@ -39,6 +37,16 @@ fn draw_ui(terminal_res: Res<TerminalResource<MyTerminal>>) {
         .unwrap();
 }
 
+/// Press F3 to show the glyph atlas next to the terminal.
+fn toggle_atlas_overlay(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut overlay: ResMut<AtlasDebugOverlay<MyTerminal>>,
+) {
+    if keys.just_pressed(KeyCode::F3) {
+        overlay.enabled = !overlay.enabled;
+    }
+}
+
 fn debug_system(
     atlas: Res<FontAtlasResource<MyTerminal>>,
     images: Res<Assets<Image>>,
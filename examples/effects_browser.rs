@@ -13,13 +13,7 @@ fn main() {
     App::new()
         .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
         .insert_resource(ClearColor(bevy::color::Color::BLACK))
-        .add_plugins(TerminalEmuPlugin::<MyTerminal> {
-            config: TerminalConfig {
-                columns: 160,
-                rows: 48,
-                ..Default::default()
-            },
-        })
+        .add_plugins(TerminalEmuPlugin::<MyTerminal>::new().columns(160).rows(48))
         .insert_resource(BrowserState::new())
         .insert_resource(ActiveEffectEntities::default())
         .add_systems(Startup, setup_camera)
@@ -75,29 +69,14 @@ impl RegionPreset {
         }
     }
 
-    fn to_effect_region(self) -> EffectRegion {
+    fn to_effect_region(self, cols: u16, rows: u16) -> EffectRegion {
         match self {
             RegionPreset::Full => EffectRegion::all(),
-            RegionPreset::LeftHalf => EffectRegion {
-                include: vec![GridRect { col: 0, row: 0, width: 80, height: 48 }],
-                exclude: vec![],
-            },
-            RegionPreset::RightHalf => EffectRegion {
-                include: vec![GridRect { col: 80, row: 0, width: 80, height: 48 }],
-                exclude: vec![],
-            },
-            RegionPreset::TopHalf => EffectRegion {
-                include: vec![GridRect { col: 0, row: 0, width: 160, height: 24 }],
-                exclude: vec![],
-            },
-            RegionPreset::BottomHalf => EffectRegion {
-                include: vec![GridRect { col: 0, row: 24, width: 160, height: 24 }],
-                exclude: vec![],
-            },
-            RegionPreset::Center => EffectRegion {
-                include: vec![GridRect { col: 40, row: 12, width: 80, height: 24 }],
-                exclude: vec![],
-            },
+            RegionPreset::LeftHalf => EffectRegion::left_half(cols, rows),
+            RegionPreset::RightHalf => EffectRegion::right_half(cols, rows),
+            RegionPreset::TopHalf => EffectRegion::top_half(cols, rows),
+            RegionPreset::BottomHalf => EffectRegion::bottom_half(cols, rows),
+            RegionPreset::Center => EffectRegion::center(cols, rows, cols / 2, rows / 2),
         }
     }
 }
@@ -271,6 +250,7 @@ fn handle_input(
 fn sync_effects(
     mut commands: Commands,
     state: Res<BrowserState>,
+    config: Res<TerminalConfig<MyTerminal>>,
     mut active: ResMut<ActiveEffectEntities>,
     cells: Query<Entity, With<TerminalCell<MyTerminal>>>,
     mut collapses: Query<&mut Collapse>,
@@ -278,31 +258,34 @@ fn sync_effects(
     mut slashes: Query<&mut Slash>,
     mut explodes: Query<&mut Explode>,
 ) {
-    let region = state.current_region().to_effect_region();
-    let target = TargetTerminal::<MyTerminal>::default();
+    let region = state
+        .current_region()
+        .to_effect_region(config.columns, config.rows);
 
     for (idx, effect) in state.effects.iter().enumerate() {
         let is_spawned = active.map.contains_key(&idx);
 
         if effect.active && !is_spawned {
             // Spawn the effect entity with the current region
+            let region = region.clone();
             let entity = match idx {
-                0 => commands.spawn((Wave::default(), region.clone(), target.clone())).id(),
-                1 => commands.spawn((Ripple::default(), region.clone(), target.clone())).id(),
-                2 => commands.spawn((Collapse::default(), region.clone(), target.clone())).id(),
-                3 => commands.spawn((Gravity::default(), region.clone(), target.clone())).id(),
-                4 => commands.spawn((Glitch::default(), region.clone(), target.clone())).id(),
-                5 => commands.spawn((Scatter::default(), region.clone(), target.clone())).id(),
-                6 => commands.spawn((Breathe::default(), region.clone(), target.clone())).id(),
-                7 => commands.spawn((Jitter::default(), region.clone(), target.clone())).id(),
-                8 => commands.spawn((Slash::default(), region.clone(), target.clone())).id(),
-                9 => commands.spawn((Explode::default(), region.clone(), target.clone())).id(),
-                10 => commands.spawn((Rainbow::default(), region.clone(), target.clone())).id(),
-                11 => commands.spawn((Glow::default(), region.clone(), target.clone())).id(),
-                12 => commands.spawn((Shiny::default(), region.clone(), target.clone())).id(),
-                13 => commands.spawn((Bubbly::default(), region.clone(), target.clone())).id(),
+                0 => commands.spawn_effect::<MyTerminal>(Wave::default(), region),
+                1 => commands.spawn_effect::<MyTerminal>(Ripple::default(), region),
+                2 => commands.spawn_effect::<MyTerminal>(Collapse::default(), region),
+                3 => commands.spawn_effect::<MyTerminal>(Gravity::default(), region),
+                4 => commands.spawn_effect::<MyTerminal>(Glitch::default(), region),
+                5 => commands.spawn_effect::<MyTerminal>(Scatter::default(), region),
+                6 => commands.spawn_effect::<MyTerminal>(Breathe::default(), region),
+                7 => commands.spawn_effect::<MyTerminal>(Jitter::default(), region),
+                8 => commands.spawn_effect::<MyTerminal>(Slash::default(), region),
+                9 => commands.spawn_effect::<MyTerminal>(Explode::default(), region),
+                10 => commands.spawn_effect::<MyTerminal>(Rainbow::default(), region),
+                11 => commands.spawn_effect::<MyTerminal>(Glow::default(), region),
+                12 => commands.spawn_effect::<MyTerminal>(Shiny::default(), region),
+                13 => commands.spawn_effect::<MyTerminal>(Bubbly::default(), region),
                 _ => unreachable!(),
-            };
+            }
+            .id();
             active.map.insert(idx, entity);
 
             // Gravity needs CellVelocity on all cells
@@ -370,9 +353,7 @@ fn sync_effects(
 }
 
 fn draw_ui(terminal_res: Res<TerminalResource<MyTerminal>>, state: Res<BrowserState>, config: Res<TerminalConfig<MyTerminal>>) {
-    let mut terminal = terminal_res.0.lock().unwrap();
-
-    terminal
+    terminal_res
         .draw(|frame| {
             let area = frame.area();
             let chunks = Layout::default()
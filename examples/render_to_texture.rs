@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+use bevy_terminal_emu::prelude::*;
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+struct MyTerminal;
+
+/// Marker for the quad the terminal is projected onto.
+#[derive(Component)]
+struct Spinning;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
+        .add_plugins(
+            TerminalEmuPlugin::<MyTerminal>::new()
+                .columns(40)
+                .rows(12)
+                // Draw the terminal on layer 1 so only its offscreen camera sees it
+                .render_layer(1),
+        )
+        .add_systems(Update, draw_ui.in_set(TerminalSet::AppTick))
+        .add_systems(
+            Update,
+            setup_scene.run_if(resource_added::<TerminalRenderTarget<MyTerminal>>),
+        )
+        .add_systems(Update, spin)
+        .run();
+}
+
+fn setup_scene(
+    mut commands: Commands,
+    target: Res<TerminalRenderTarget<MyTerminal>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let aspect = target.size.x as f32 / target.size.y as f32;
+    let height = 3.0;
+
+    commands.spawn((
+        Mesh3d(meshes.add(Rectangle::new(height * aspect, height))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color_texture: Some(target.image.clone()),
+            unlit: true,
+            cull_mode: None,
+            ..default()
+        })),
+        Transform::default(),
+        Spinning,
+    ));
+
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 0.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+}
+
+fn spin(time: Res<Time>, mut query: Query<&mut Transform, With<Spinning>>) {
+    for mut transform in query.iter_mut() {
+        transform.rotate_y(0.6 * time.delta_secs());
+    }
+}
+
+fn draw_ui(terminal_res: Res<TerminalResource<MyTerminal>>) {
+    terminal_res
+        .draw(|frame| {
+            let area = frame.area();
+            let block = Block::default()
+                .title(" Render to Texture ")
+                .borders(Borders::ALL);
+            let paragraph = Paragraph::new(
+                "This terminal is rendered into an Image\n\
+                 and textured onto a spinning 3D quad.",
+            )
+            .block(block);
+            frame.render_widget(paragraph, area);
+        })
+        .unwrap();
+}
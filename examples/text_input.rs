@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+use bevy_terminal_emu::prelude::*;
+use ratatui::layout::Position;
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+struct MyTerminal;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
+        .add_plugins(
+            TerminalEmuPlugin::<MyTerminal>::new()
+                .columns(60)
+                .rows(5)
+                .cursor_blink_hz(1.0),
+        )
+        .insert_resource(TextField(String::new()))
+        .add_systems(Startup, setup_camera)
+        .add_systems(
+            Update,
+            (handle_input, draw_ui).chain().in_set(TerminalSet::AppTick),
+        )
+        .run();
+}
+
+#[derive(Resource)]
+struct TextField(String);
+
+fn setup_camera(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
+
+fn handle_input(mut queue: ResMut<TerminalInputQueue<MyTerminal>>, mut input: ResMut<TextField>) {
+    while let Some(event) = queue.events.pop_front() {
+        if let terminput::Event::Key(key_event) = event {
+            match key_event.code {
+                terminput::KeyCode::Char(ch) => input.0.push(ch),
+                terminput::KeyCode::Backspace => {
+                    input.0.pop();
+                }
+                terminput::KeyCode::Enter => input.0.clear(),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn draw_ui(terminal_res: Res<TerminalResource<MyTerminal>>, input: Res<TextField>) {
+    terminal_res
+        .draw(|frame| {
+            let area = frame.area();
+            let block = Block::default()
+                .title(" Type something (Enter clears) ")
+                .borders(Borders::ALL);
+            let inner = block.inner(area);
+            frame.render_widget(Paragraph::new(input.0.as_str()).block(block), area);
+
+            // Keep the cursor after the last character, inside the box
+            let col = (input.0.chars().count() as u16).min(inner.width.saturating_sub(1));
+            frame.set_cursor_position(Position::new(inner.x + col, inner.y));
+        })
+        .unwrap();
+}
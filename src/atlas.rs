@@ -1,14 +1,25 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::marker::PhantomData;
+use std::sync::Arc;
 
-use ab_glyph::{Font, FontRef, ScaleFont};
+use ab_glyph::{Font as _, FontRef, GlyphImageFormat, ScaleFont};
 use bevy::asset::RenderAssetUsages;
+use bevy::image::{CompressedImageFormats, ImageFormat, ImageSampler, ImageType};
 use bevy::prelude::*;
 use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::text::Font;
 
 use bevy::window::PrimaryWindow;
 
-use crate::grid::{BackgroundSprite, BaseTransform, CellEntityIndex, ForegroundSprite, GridPosition, TerminalCell};
+use crate::atlas_cache::CachedAtlas;
+use crate::bitmap_font::BitmapFont;
+use crate::glyph::{ascii_chars, cap_center_offset};
+use crate::grid::{BaseTransform, CellEntityIndex, ForegroundSprite, GridPosition, TerminalCell};
+use crate::FontSource;
+
+pub use crate::glyph::{
+    compute_cell_size, is_monospace, AtlasFilter, AtlasPadding, MonospacePolicy,
+};
 
 /// Holds the generated font atlas texture, layout, and glyph mapping.
 #[derive(Resource)]
@@ -16,150 +27,1077 @@ pub struct FontAtlasResource<T: 'static + Send + Sync> {
     pub image: Handle<Image>,
     pub layout: Handle<TextureAtlasLayout>,
     pub glyph_map: HashMap<char, usize>,
+    /// Glyphs in `glyph_map` rasterized in color, which keep their own
+    /// colors instead of taking the sprite tint (see `color_glyphs`).
+    pub color_glyphs: HashSet<char>,
     pub cell_size: UVec2,
+    /// Shift that centers capital letters in the cell, as a fraction of the
+    /// cell height (+ up), for `GlyphAlign::CapCenter`. Zero for bitmap fonts.
+    pub cap_center: f32,
     pub font_size: f32,
     /// The scale factor the atlas was rasterized at (for HiDPI).
     pub scale_factor: f32,
+    /// Sampler filter the atlas image was built with.
+    pub filter: AtlasFilter,
+    /// Glyph shaping options the atlas was rasterized with.
+    options: GlyphOptions,
     /// The font bytes used to build this atlas (kept for rebuilds).
     font_bytes: Vec<u8>,
+    /// Bitmap glyph sheet used instead of `font_bytes` when set.
+    bitmap: Option<Arc<BitmapFont>>,
     /// Characters discovered at runtime that aren't yet in the atlas.
     pub pending_glyphs: HashSet<char>,
     /// Number of glyphs currently in the atlas.
     pub glyph_count: usize,
+    /// Tiles per atlas row.
+    columns: u32,
+    /// Upper bound on atlas tiles. When full, new glyphs replace the
+    /// least-recently-used ones instead of growing the texture.
+    /// `None` means unbounded.
+    pub max_glyphs: Option<usize>,
+    /// Sync pass in which each glyph was last written to a cell.
+    pub glyph_last_used: HashMap<char, u64>,
+    /// Monotonic counter bumped once per sync pass, used for LRU ordering.
+    pub use_tick: u64,
+    /// Set when `font_bytes` changed and the atlas must be re-rasterized.
+    font_stale: bool,
+    /// `SharedAtlases` entry the texture belongs to, if shared.
+    shared: Option<AtlasKey>,
+    /// Generation of the shared entry this glyph placement was copied from.
+    shared_generation: u64,
     _marker: PhantomData<T>,
 }
 
-/// Number of columns in the atlas grid.
+impl<T: 'static + Send + Sync> FontAtlasResource<T> {
+    /// Key of the shared atlas this terminal draws from, if any.
+    pub fn shared_key(&self) -> Option<AtlasKey> {
+        self.shared
+    }
+
+    fn assets(&self) -> AtlasAssets {
+        AtlasAssets {
+            image: self.image.clone(),
+            layout: self.layout.clone(),
+            glyph_map: self.glyph_map.clone(),
+            color_glyphs: self.color_glyphs.clone(),
+            cell_size: self.cell_size,
+            glyph_count: self.glyph_count,
+            columns: self.columns,
+        }
+    }
+
+    fn set_assets(&mut self, assets: AtlasAssets) {
+        self.image = assets.image;
+        self.layout = assets.layout;
+        self.glyph_map = assets.glyph_map;
+        self.color_glyphs = assets.color_glyphs;
+        self.cell_size = assets.cell_size;
+        self.glyph_count = assets.glyph_count;
+        self.columns = assets.columns;
+    }
+
+    /// Take over glyph placement another terminal published to the shared
+    /// entry. Returns the chars whose cells need re-syncing: ones whose tile
+    /// moved or was evicted, and pending ones the other terminal added.
+    fn adopt(&mut self, assets: &AtlasAssets) -> HashSet<char> {
+        let mut changed: HashSet<char> = self
+            .glyph_map
+            .iter()
+            .filter(|(ch, index)| assets.glyph_map.get(*ch) != Some(*index))
+            .map(|(ch, _)| *ch)
+            .collect();
+        self.pending_glyphs.retain(|ch| {
+            let added = assets.glyph_map.contains_key(ch);
+            if added {
+                changed.insert(*ch);
+            }
+            !added
+        });
+        self.glyph_map.clone_from(&assets.glyph_map);
+        self.color_glyphs.clone_from(&assets.color_glyphs);
+        self.glyph_count = assets.glyph_count;
+        changed
+    }
+
+    /// Whether `ch` is drawn from a color tile and should skip the tint.
+    pub fn is_color_glyph(&self, ch: char) -> bool {
+        self.color_glyphs.contains(&ch)
+    }
+
+    /// Record that `ch` was displayed during the current sync pass.
+    pub fn touch_glyph(&mut self, ch: char) {
+        self.glyph_last_used.insert(ch, self.use_tick);
+    }
+
+    /// Pick the least-recently-used glyph to evict, never the space fallback
+    /// or any char in `protected`. Glyphs not currently on screen are
+    /// preferred so visible cells don't flicker.
+    fn lru_victim(&self, on_screen: &HashSet<char>, protected: &HashSet<char>) -> Option<char> {
+        let key = |ch: &&char| (self.glyph_last_used.get(*ch).copied().unwrap_or(0), **ch);
+        let candidates = self
+            .glyph_map
+            .keys()
+            .filter(|ch| **ch != ' ' && !protected.contains(*ch));
+        candidates
+            .clone()
+            .filter(|ch| !on_screen.contains(*ch))
+            .min_by_key(key)
+            .or_else(|| candidates.min_by_key(key))
+            .copied()
+    }
+}
+
+/// Handle of a font still loading through the `AssetServer`.
+/// While present, sync is paused and the grid stays empty.
+#[derive(Resource)]
+pub struct PendingFont<T: 'static + Send + Sync> {
+    pub handle: Handle<Font>,
+    _marker: PhantomData<T>,
+}
+
+/// Run condition: true once the terminal's font bytes are available.
+pub fn font_ready<T: 'static + Send + Sync>(pending: Option<Res<PendingFont<T>>>) -> bool {
+    pending.is_none()
+}
+
+/// Return `bytes` if they parse as a font, otherwise warn and fall back to
+/// the embedded default font.
+fn validated_font_bytes(bytes: &[u8]) -> Vec<u8> {
+    if FontRef::try_from_slice(bytes).is_ok() {
+        bytes.to_vec()
+    } else {
+        warn!("Failed to parse font data, falling back to the default font");
+        FontSource::Default.bytes().to_vec()
+    }
+}
+
+/// Preferred number of columns in the atlas grid.
 const ATLAS_COLS: u32 = 16;
 
+/// Atlas grid shape for `glyphs` tiles of `stride` texels that keeps the
+/// texture within `max_dimension` on both axes, as (columns, max rows).
+///
+/// Narrows the grid when 16 tiles don't fit across, and widens it when the
+/// glyphs wouldn't fit in `max_dimension` rows of 16.
+fn atlas_grid(glyphs: usize, stride: UVec2, max_dimension: u32) -> (u32, u32) {
+    let max_cols = (max_dimension / stride.x.max(1)).max(1);
+    let max_rows = (max_dimension / stride.y.max(1)).max(1);
+    let mut cols = ATLAS_COLS.min(max_cols);
+    let glyphs = glyphs.min(u32::MAX as usize) as u32;
+    if glyphs > cols.saturating_mul(max_rows) {
+        cols = glyphs.div_ceil(max_rows).min(max_cols);
+    }
+    (cols, max_rows)
+}
+
 /// Raw atlas data before it's stored as Bevy assets.
 struct AtlasData {
     image: Image,
     layout: TextureAtlasLayout,
     glyph_map: HashMap<char, usize>,
+    color_glyphs: HashSet<char>,
     cell_size: UVec2,
     glyph_count: usize,
+    columns: u32,
+}
+
+/// An atlas added to the asset stores, plus where each glyph sits in it.
+#[derive(Clone)]
+struct AtlasAssets {
+    image: Handle<Image>,
+    layout: Handle<TextureAtlasLayout>,
+    glyph_map: HashMap<char, usize>,
+    color_glyphs: HashSet<char>,
+    cell_size: UVec2,
+    glyph_count: usize,
+    columns: u32,
+}
+
+impl AtlasAssets {
+    fn add(
+        data: AtlasData,
+        images: &mut Assets<Image>,
+        layouts: &mut Assets<TextureAtlasLayout>,
+    ) -> Self {
+        Self {
+            image: images.add(data.image),
+            layout: layouts.add(data.layout),
+            glyph_map: data.glyph_map,
+            color_glyphs: data.color_glyphs,
+            cell_size: data.cell_size,
+            glyph_count: data.glyph_count,
+            columns: data.columns,
+        }
+    }
+}
+
+/// Identifies terminals that can draw from the same atlas: same font, font
+/// size, scale factor and rasterization settings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AtlasKey(u64);
+
+impl AtlasKey {
+    fn new<T: 'static + Send + Sync>(
+        config: &crate::TerminalConfig<T>,
+        font_bytes: &[u8],
+        bitmap: Option<&Arc<BitmapFont>>,
+        scale_factor: f32,
+    ) -> Self {
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        // Bitmap sheets are compared by identity, outline fonts by content
+        match bitmap {
+            Some(bitmap) => (Arc::as_ptr(bitmap) as usize).hash(&mut hasher),
+            None => font_bytes.hash(&mut hasher),
+        }
+        config.font_size.to_bits().hash(&mut hasher);
+        scale_factor.to_bits().hash(&mut hasher);
+        (config.atlas_filter as u8).hash(&mut hasher);
+        GlyphOptions::from_config(config).hash_into(&mut hasher);
+        config.max_atlas_dimension.hash(&mut hasher);
+        config.max_glyphs.hash(&mut hasher);
+        Self(hasher.finish())
+    }
 }
 
-/// Return the printable ASCII characters (0x20..=0x7E).
-fn ascii_chars() -> Vec<char> {
-    (0x20u8..=0x7E).map(|b| b as char).collect()
+struct SharedAtlas {
+    assets: AtlasAssets,
+    /// Bumped whenever a terminal adds or replaces glyphs.
+    generation: u64,
+    /// Terminals currently drawing from this atlas.
+    users: usize,
 }
 
-/// Compute the cell (width, height) in pixels for a given font and size.
+/// Atlases shared by terminals with `share_atlas` enabled, so terminals with
+/// the same font and size keep one texture between them.
 ///
-/// Uses exact font metrics (no rounding) so adjacent cells tile seamlessly.
-/// Height excludes line_gap so vertical borders connect without gaps.
-pub fn compute_cell_size(font_bytes: &[u8], font_size: f32) -> (f32, f32) {
-    let font = FontRef::try_from_slice(font_bytes).expect("Failed to parse font");
-    let scale = ab_glyph::PxScale::from(font_size);
-    let scaled_font = font.as_scaled(scale);
-    let glyph_id = font.glyph_id('M');
-    let cell_width = scaled_font.h_advance(glyph_id);
-    let cell_height = scaled_font.ascent() - scaled_font.descent();
-    (cell_width, cell_height)
-}
-
-/// Build the font atlas texture and layout for a given font size, font bytes, and character set.
-fn build_atlas_data_for_chars(font_bytes: &[u8], font_size: f32, chars: &[char]) -> AtlasData {
-    let font = FontRef::try_from_slice(font_bytes).expect("Failed to parse font");
-    let scale = ab_glyph::PxScale::from(font_size);
-    let scaled_font = font.as_scaled(scale);
-
-    let glyph_id = font.glyph_id('M');
-    let cell_w = scaled_font.h_advance(glyph_id).ceil() as u32;
-    let cell_h = (scaled_font.ascent() - scaled_font.descent()).ceil() as u32;
-    let cell_size = UVec2::new(cell_w, cell_h);
+/// Each terminal keeps its own copy of the glyph placement and picks up
+/// glyphs other terminals added (or evicted) the next frame. A terminal whose
+/// font, size or scale factor changes moves to the atlas for its new
+/// settings, building it if needed. An atlas is dropped with its last user.
+#[derive(Resource, Default)]
+pub struct SharedAtlases {
+    entries: HashMap<AtlasKey, SharedAtlas>,
+}
 
-    let glyph_count = chars.len();
-    let atlas_rows = ((glyph_count as u32) + ATLAS_COLS - 1) / ATLAS_COLS;
+impl SharedAtlases {
+    /// Number of distinct atlases in use.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of terminals drawing from the atlas under `key`.
+    pub fn users(&self, key: AtlasKey) -> usize {
+        self.entries.get(&key).map_or(0, |entry| entry.users)
+    }
+
+    /// Start using the atlas under `key`, if there is one, returning it and
+    /// its generation.
+    fn join(&mut self, key: AtlasKey) -> Option<(AtlasAssets, u64)> {
+        let entry = self.entries.get_mut(&key)?;
+        entry.users += 1;
+        Some((entry.assets.clone(), entry.generation))
+    }
+
+    /// Register a freshly built atlas with a single user.
+    fn insert(&mut self, key: AtlasKey, assets: AtlasAssets) {
+        let entry = SharedAtlas {
+            assets,
+            generation: 0,
+            users: 1,
+        };
+        self.entries.insert(key, entry);
+    }
+
+    /// Stop using the atlas under `key`, dropping it if that was the last user.
+    fn leave(&mut self, key: AtlasKey) {
+        if let Some(entry) = self.entries.get_mut(&key) {
+            entry.users = entry.users.saturating_sub(1);
+            if entry.users == 0 {
+                self.entries.remove(&key);
+            }
+        }
+    }
+
+    /// Replace the glyph placement under `key`, returning the new generation.
+    fn publish(&mut self, key: AtlasKey, assets: AtlasAssets) -> u64 {
+        let Some(entry) = self.entries.get_mut(&key) else {
+            return 0;
+        };
+        entry.assets = assets;
+        entry.generation += 1;
+        entry.generation
+    }
+}
+
+/// Chars to rasterize at startup beyond printable ASCII: the config's
+/// `initial_charset`, then its `preload_chars`.
+fn startup_chars<T: 'static + Send + Sync>(config: &crate::TerminalConfig<T>) -> Vec<char> {
+    let mut chars = config.initial_charset.extra_chars();
+    chars.extend_from_slice(&config.preload_chars);
+    chars
+}
+
+/// Append the chars in `preload` that aren't in `chars` yet and that `font`
+/// can render, in order.
+fn with_preloaded(
+    mut chars: Vec<char>,
+    preload: &[char],
+    font: GlyphFont,
+    font_size: f32,
+    options: GlyphOptions,
+) -> Vec<char> {
+    if preload.is_empty() {
+        return chars;
+    }
+    let rasterizer = GlyphRasterizer::new(font, font_size, font.cell_size(font_size), options);
+    let mut seen: HashSet<char> = chars.iter().copied().collect();
+    for &ch in preload {
+        if rasterizer.can_render(ch) && seen.insert(ch) {
+            chars.push(ch);
+        }
+    }
+    chars
+}
+
+/// Warn once per font load when a proportional font is used without
+/// `MonospacePolicy::FitGlyph`.
+fn warn_if_proportional(font_bytes: &[u8], policy: MonospacePolicy) {
+    if policy == MonospacePolicy::ForceCellWidth && !is_monospace(font_bytes) {
+        warn!(
+            "Terminal font is not monospace; glyphs will not line up with cells. \
+             Use a monospace font or set `monospace_policy: MonospacePolicy::FitGlyph`"
+        );
+    }
+}
+
+/// Config-driven adjustments applied while rasterizing glyphs.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct GlyphOptions {
+    /// Extend box-drawing strokes to the tile edges.
+    snap_box_drawing: bool,
+    /// Fill block elements geometrically instead of using the font.
+    solid_block_elements: bool,
+    /// Keep the colors of glyphs the font has color bitmaps for.
+    color_glyphs: bool,
+    /// Horizontal fitting of proportional glyphs.
+    monospace_policy: MonospacePolicy,
+    /// Space between atlas tiles.
+    padding: AtlasPadding,
+}
+
+impl GlyphOptions {
+    /// Feed every option that affects the rasterized pixels to `hasher`.
+    fn hash_into(&self, hasher: &mut impl std::hash::Hasher) {
+        use std::hash::Hash;
+
+        self.snap_box_drawing.hash(hasher);
+        self.solid_block_elements.hash(hasher);
+        self.color_glyphs.hash(hasher);
+        (self.monospace_policy as u8).hash(hasher);
+        match self.padding {
+            AtlasPadding::Auto => 0u32.hash(hasher),
+            AtlasPadding::Pixels(px) => (1u32, px).hash(hasher),
+            AtlasPadding::Fraction(f) => (2u32, f.to_bits()).hash(hasher),
+        }
+    }
+
+    fn from_config<T: 'static + Send + Sync>(config: &crate::TerminalConfig<T>) -> Self {
+        Self {
+            snap_box_drawing: config.snap_box_drawing,
+            solid_block_elements: config.solid_block_elements,
+            color_glyphs: config.color_glyphs,
+            monospace_policy: config.monospace_policy,
+            padding: config.atlas_padding,
+        }
+    }
+}
+
+/// Whether `ch` is a box-drawing or block-element character whose strokes
+/// are meant to join the neighboring cell. Shades are excluded: their
+/// dither pattern isn't a stroke.
+fn is_box_drawing(ch: char) -> bool {
+    matches!(ch, '\u{2500}'..='\u{2590}' | '\u{2594}'..='\u{259F}')
+}
+
+/// Extend strokes that stop within a few texels of a tile edge out to that
+/// edge. `origin` and `size` give the tile rect inside an RGBA buffer
+/// `atlas_width` texels wide.
+///
+/// For each row (and column), the strongest coverage in the band next to
+/// the edge is copied outward to the edge, so anti-aliased stroke profiles
+/// are preserved rather than thickened.
+fn snap_strokes_to_edges(pixel_data: &mut [u8], atlas_width: u32, origin: UVec2, size: UVec2) {
+    /// Coverage below this is treated as anti-aliasing fringe, not a stroke.
+    const STROKE_ALPHA: u8 = 64;
+
+    let alpha_at =
+        |x: u32, y: u32| ((origin.y + y) * atlas_width + origin.x + x) as usize * 4 + 3;
+    let (w, h) = (size.x, size.y);
+    if w == 0 || h == 0 {
+        return;
+    }
+    let band_x = (w / 6).max(1).min(w - 1);
+    let band_y = (h / 6).max(1).min(h - 1);
+
+    // Walk inward from the edge, then fill back to the edge with the peak
+    let extend = |pixel_data: &mut [u8], line: &dyn Fn(u32) -> usize, band: u32| {
+        let (mut peak, mut peak_at) = (0u8, 0);
+        for i in 0..=band {
+            let a = pixel_data[line(i)];
+            if a > peak {
+                (peak, peak_at) = (a, i);
+            }
+        }
+        if peak < STROKE_ALPHA {
+            return;
+        }
+        for i in 0..peak_at {
+            let idx = line(i);
+            if pixel_data[idx] < peak {
+                pixel_data[idx - 3..idx].fill(255);
+                pixel_data[idx] = peak;
+            }
+        }
+    };
+
+    for y in 0..h {
+        extend(pixel_data, &|i| alpha_at(i, y), band_x);
+        extend(pixel_data, &|i| alpha_at(w - 1 - i, y), band_x);
+    }
+    for x in 0..w {
+        extend(pixel_data, &|i| alpha_at(x, i), band_y);
+        extend(pixel_data, &|i| alpha_at(x, h - 1 - i), band_y);
+    }
+}
+
+/// Coverage alpha and unit-square rects (y down) filled by a block element,
+/// or `None` for any other char.
+fn block_element_shape(ch: char) -> Option<(u8, Vec<Rect>)> {
+    let eighths = |n: u32| n as f32 / 8.0;
+    let full = Rect::new(0.0, 0.0, 1.0, 1.0);
+    let quadrant = |right: bool, lower: bool| {
+        let (x, y) = (if right { 0.5 } else { 0.0 }, if lower { 0.5 } else { 0.0 });
+        Rect::new(x, y, x + 0.5, y + 0.5)
+    };
+    let (ul, ur, ll, lr) = (
+        quadrant(false, false),
+        quadrant(true, false),
+        quadrant(false, true),
+        quadrant(true, true),
+    );
+    let code = ch as u32;
+    let rects = match ch {
+        '\u{2580}' => vec![Rect::new(0.0, 0.0, 1.0, 0.5)],
+        // Lower one eighth through full block
+        '\u{2581}'..='\u{2588}' => vec![Rect::new(0.0, 1.0 - eighths(code - 0x2580), 1.0, 1.0)],
+        // Left seven eighths through left one eighth
+        '\u{2589}'..='\u{258F}' => vec![Rect::new(0.0, 0.0, eighths(0x2590 - code), 1.0)],
+        '\u{2590}' => vec![Rect::new(0.5, 0.0, 1.0, 1.0)],
+        // Light, medium and dark shade
+        '\u{2591}'..='\u{2593}' => {
+            let alpha = ((code - 0x2590) as f32 * 0.25 * 255.0).round() as u8;
+            return Some((alpha, vec![full]));
+        }
+        '\u{2594}' => vec![Rect::new(0.0, 0.0, 1.0, eighths(1))],
+        '\u{2595}' => vec![Rect::new(eighths(7), 0.0, 1.0, 1.0)],
+        '\u{2596}' => vec![ll],
+        '\u{2597}' => vec![lr],
+        '\u{2598}' => vec![ul],
+        '\u{2599}' => vec![ul, ll, lr],
+        '\u{259A}' => vec![ul, lr],
+        '\u{259B}' => vec![ul, ur, ll],
+        '\u{259C}' => vec![ul, ur, lr],
+        '\u{259D}' => vec![ur],
+        '\u{259E}' => vec![ur, ll],
+        '\u{259F}' => vec![ur, ll, lr],
+        _ => return None,
+    };
+    Some((255, rects))
+}
 
-    // Add padding between atlas cells so glyph overflow lands in empty space
-    // rather than bleeding into a neighbor's tile.
-    let pad: u32 = (cell_w / 2).max(4);
-    let stride_w = cell_w + pad;
-    let stride_h = cell_h + pad;
-    let atlas_width = stride_w * ATLAS_COLS;
-    let atlas_height = stride_h * atlas_rows;
+/// Fill the tile at `origin` with the exact area coverage of a block
+/// element. Returns false (drawing nothing) if `ch` isn't one.
+fn draw_block_element(
+    pixel_data: &mut [u8],
+    atlas_width: u32,
+    origin: UVec2,
+    size: UVec2,
+    ch: char,
+) -> bool {
+    let Some((alpha, rects)) = block_element_shape(ch) else {
+        return false;
+    };
+    let scale = size.as_vec2();
+    let rects: Vec<Rect> = rects
+        .into_iter()
+        .map(|r| Rect::from_corners(r.min * scale, r.max * scale))
+        .collect();
+    for y in 0..size.y {
+        for x in 0..size.x {
+            let texel = Rect::new(x as f32, y as f32, x as f32 + 1.0, y as f32 + 1.0);
+            // Quadrant rects never overlap, so areas simply add up
+            let coverage: f32 = rects
+                .iter()
+                .map(|r| r.intersect(texel).size().element_product())
+                .sum();
+            let idx = ((origin.y + y) * atlas_width + origin.x + x) as usize * 4;
+            let a = (coverage.min(1.0) * alpha as f32).round() as u8;
+            pixel_data[idx..idx + 4].copy_from_slice(&[255, 255, 255, a]);
+        }
+    }
+    true
+}
+
+/// Decode a font's color bitmap into straight (not premultiplied) RGBA, as
+/// (size, texels). `None` for monochrome formats and undecodable data.
+fn color_image_rgba(image: &ab_glyph::v2::GlyphImage) -> Option<(UVec2, Vec<u8>)> {
+    match image.format {
+        GlyphImageFormat::BitmapPremulBgra32 => {
+            let size = UVec2::new(image.width as u32, image.height as u32);
+            Some((size, unpremultiply_bgra(image.data)))
+        }
+        GlyphImageFormat::Png => {
+            let decoded = Image::from_buffer(
+                image.data,
+                ImageType::Format(ImageFormat::Png),
+                CompressedImageFormats::NONE,
+                true,
+                ImageSampler::Default,
+                RenderAssetUsages::default(),
+            )
+            .ok()?
+            .convert(TextureFormat::Rgba8UnormSrgb)?;
+            Some((decoded.size(), decoded.data?))
+        }
+        _ => None,
+    }
+}
+
+/// Convert premultiplied BGRA texels to straight RGBA.
+fn unpremultiply_bgra(data: &[u8]) -> Vec<u8> {
+    data.chunks_exact(4)
+        .flat_map(|texel| {
+            let [b, g, r, a] = [texel[0], texel[1], texel[2], texel[3]];
+            let straight = |c: u8| match a {
+                0 => 0,
+                a => ((c as u32 * 255 + a as u32 / 2) / a as u32).min(255) as u8,
+            };
+            [straight(r), straight(g), straight(b), a]
+        })
+        .collect()
+}
+
+/// Scale a straight RGBA image `size` texels big to fit inside a `cell`
+/// tile at `origin`, keeping its aspect ratio and centering it.
+///
+/// Each tile texel averages the source texels it covers, weighted by alpha
+/// so transparent edges don't darken the colors. Returns false if `rgba` is
+/// too short for `size`.
+fn blit_color_image(
+    pixel_data: &mut [u8],
+    atlas_width: u32,
+    origin: UVec2,
+    cell: UVec2,
+    size: UVec2,
+    rgba: &[u8],
+) -> bool {
+    if size.cmpeq(UVec2::ZERO).any() || rgba.len() < (size.x * size.y * 4) as usize {
+        return false;
+    }
+    let scale = (cell.as_vec2() / size.as_vec2()).min_element();
+    let fitted = (size.as_vec2() * scale)
+        .round()
+        .as_uvec2()
+        .clamp(UVec2::ONE, cell);
+    let offset = (cell - fitted) / 2;
+    // Source texels [start, end) that destination texel `i` of `dst` covers
+    let span = |i: u32, dst: u32, src: u32| {
+        let start = i * src / dst;
+        (start, ((i + 1) * src).div_ceil(dst).max(start + 1).min(src))
+    };
+
+    for y in 0..fitted.y {
+        let (y0, y1) = span(y, fitted.y, size.y);
+        for x in 0..fitted.x {
+            let (x0, x1) = span(x, fitted.x, size.x);
+            let mut sum = [0u32; 4];
+            for sy in y0..y1 {
+                for sx in x0..x1 {
+                    let i = (sy * size.x + sx) as usize * 4;
+                    let alpha = rgba[i + 3] as u32;
+                    for (total, &c) in sum.iter_mut().zip(&rgba[i..i + 3]) {
+                        *total += c as u32 * alpha;
+                    }
+                    sum[3] += alpha;
+                }
+            }
+            let texel = match sum[3] {
+                0 => [0; 4],
+                total => {
+                    let count = (y1 - y0) * (x1 - x0);
+                    let channel = |c: usize| (sum[c] / total) as u8;
+                    [channel(0), channel(1), channel(2), (total / count) as u8]
+                }
+            };
+            let at = origin + offset + UVec2::new(x, y);
+            let idx = (at.y * atlas_width + at.x) as usize * 4;
+            pixel_data[idx..idx + 4].copy_from_slice(&texel);
+        }
+    }
+    true
+}
+
+/// Font data the atlas rasterizes from.
+#[derive(Clone, Copy)]
+enum GlyphFont<'a> {
+    /// Outline font bytes, rendered with ab_glyph.
+    Outline(&'a [u8]),
+    /// Pre-rasterized glyph sheet.
+    Bitmap(&'a BitmapFont),
+}
+
+impl<'a> GlyphFont<'a> {
+    fn new(font_bytes: &'a [u8], bitmap: Option<&'a BitmapFont>) -> Self {
+        match bitmap {
+            Some(bitmap) => Self::Bitmap(bitmap),
+            None => Self::Outline(font_bytes),
+        }
+    }
+
+    /// Atlas tile size in texels when rasterized at `font_size`.
+    fn cell_size(self, font_size: f32) -> UVec2 {
+        match self {
+            Self::Outline(bytes) => {
+                let (w, h) = compute_cell_size(bytes, font_size);
+                UVec2::new(w.ceil() as u32, h.ceil() as u32)
+            }
+            Self::Bitmap(bitmap) => bitmap.cell_size(font_size),
+        }
+    }
+}
+
+/// How a `GlyphRasterizer` produces glyph coverage.
+enum GlyphSource<'a> {
+    Outline {
+        font: FontRef<'a>,
+        scale: ab_glyph::PxScale,
+        ascent: f32,
+    },
+    Bitmap {
+        font: &'a BitmapFont,
+        /// Whole-number upscale applied to each sheet pixel.
+        scale: u32,
+    },
+}
+
+/// Everything needed to rasterize glyphs into atlas tiles.
+struct GlyphRasterizer<'a> {
+    source: GlyphSource<'a>,
+    options: GlyphOptions,
+    /// Tile size without padding.
+    cell_size: UVec2,
+    /// Tile size plus padding.
+    stride: UVec2,
+    /// Tiles per atlas row.
+    columns: u32,
+}
+
+impl<'a> GlyphRasterizer<'a> {
+    fn new(font: GlyphFont<'a>, font_size: f32, cell_size: UVec2, options: GlyphOptions) -> Self {
+        let source = match font {
+            GlyphFont::Outline(bytes) => {
+                // Loaded fonts are validated first; fall back rather than panic
+                // on anything that slipped through
+                let font = FontRef::try_from_slice(bytes)
+                    .or_else(|_| FontRef::try_from_slice(crate::DEFAULT_FONT_BYTES))
+                    .expect("Failed to parse default font");
+                let scale = ab_glyph::PxScale::from(font_size);
+                let ascent = font.as_scaled(scale).ascent();
+                GlyphSource::Outline {
+                    font,
+                    scale,
+                    ascent,
+                }
+            }
+            GlyphFont::Bitmap(font) => GlyphSource::Bitmap {
+                font,
+                scale: font.scale_for(font_size),
+            },
+        };
+        let pad = options.padding.texels(cell_size.x);
+        Self {
+            source,
+            options,
+            cell_size,
+            stride: cell_size + UVec2::splat(pad),
+            columns: ATLAS_COLS,
+        }
+    }
+
+    /// Lay tiles out `columns` to a row instead of the default 16.
+    fn with_columns(mut self, columns: u32) -> Self {
+        self.columns = columns.max(1);
+        self
+    }
+
+    /// Top-left texel of tile `index`.
+    fn tile_origin(&self, index: usize) -> UVec2 {
+        let index = index as u32;
+        UVec2::new(index % self.columns, index / self.columns) * self.stride
+    }
+
+    /// Whether the font has a glyph for this character.
+    fn can_render(&self, ch: char) -> bool {
+        if self.options.solid_block_elements && block_element_shape(ch).is_some() {
+            return true;
+        }
+        match &self.source {
+            GlyphSource::Outline {
+                font,
+                scale,
+                ascent,
+            } => {
+                let id = font.glyph_id(ch);
+                let glyph = id.with_scale_and_position(*scale, ab_glyph::point(0.0, *ascent));
+                font.outline_glyph(glyph).is_some()
+                    || (self.options.color_glyphs
+                        && font.glyph_raster_image2(id, u16::MAX).is_some())
+            }
+            GlyphSource::Bitmap { font, .. } => font.glyph_index(ch).is_some(),
+        }
+    }
+
+    /// Zero out tile `index` (including its padding) so it can be reused.
+    fn clear(&self, pixel_data: &mut [u8], atlas_width: u32, index: usize) {
+        let UVec2 {
+            x: cell_origin_x,
+            y: cell_origin_y,
+        } = self.tile_origin(index);
+        for y in cell_origin_y..cell_origin_y + self.stride.y {
+            let start = (y * atlas_width + cell_origin_x) as usize * 4;
+            let end = start + self.stride.x as usize * 4;
+            pixel_data[start..end].fill(0);
+        }
+    }
+
+    /// Draw `ch` as a white, alpha-covered glyph into tile `index` of an RGBA
+    /// buffer, applying any enabled shaping options. With `color_glyphs`,
+    /// glyphs the font has a color bitmap for are drawn in color instead.
+    ///
+    /// Returns whether the tile holds a color glyph.
+    fn draw(&self, pixel_data: &mut [u8], atlas_width: u32, ch: char, index: usize) -> bool {
+        let origin = self.tile_origin(index);
+        if self.options.solid_block_elements
+            && draw_block_element(pixel_data, atlas_width, origin, self.cell_size, ch)
+        {
+            return false;
+        }
+        if self.options.color_glyphs && self.draw_color_glyph(pixel_data, atlas_width, ch, index) {
+            return true;
+        }
+        self.draw_glyph(pixel_data, atlas_width, ch, index);
+        if self.options.snap_box_drawing && is_box_drawing(ch) {
+            snap_strokes_to_edges(pixel_data, atlas_width, origin, self.cell_size);
+        }
+        false
+    }
+
+    /// Copy the font's color bitmap of `ch` (CBDT/CBLC or sbix) into tile
+    /// `index`, keeping its colors. Returns false when there is none.
+    fn draw_color_glyph(
+        &self,
+        pixel_data: &mut [u8],
+        atlas_width: u32,
+        ch: char,
+        index: usize,
+    ) -> bool {
+        let GlyphSource::Outline { font, scale, .. } = &self.source else {
+            return false;
+        };
+        // Ask for the strike nearest the tile height; it's scaled to fit anyway
+        let strike = scale.y.round().clamp(1.0, u16::MAX as f32) as u16;
+        let Some(image) = font.glyph_raster_image2(font.glyph_id(ch), strike) else {
+            return false;
+        };
+        let Some((size, rgba)) = color_image_rgba(&image) else {
+            return false;
+        };
+        let origin = self.tile_origin(index);
+        blit_color_image(pixel_data, atlas_width, origin, self.cell_size, size, &rgba)
+    }
+
+    /// Rasterize `ch` from the font into tile `index`.
+    fn draw_glyph(&self, pixel_data: &mut [u8], atlas_width: u32, ch: char, index: usize) {
+        let (font, scale, ascent) = match &self.source {
+            GlyphSource::Outline {
+                font,
+                scale,
+                ascent,
+            } => (font, *scale, *ascent),
+            GlyphSource::Bitmap { font, scale } => {
+                self.draw_bitmap(pixel_data, atlas_width, font, *scale, ch, index);
+                return;
+            }
+        };
+        let id = font.glyph_id(ch);
+        let mut scale = scale;
+        if self.options.monospace_policy == MonospacePolicy::FitGlyph {
+            let advance = font.as_scaled(scale).h_advance(id);
+            if advance > 0.0 {
+                scale.x *= self.cell_size.x as f32 / advance;
+            }
+        }
+        let glyph = id.with_scale_and_position(scale, ab_glyph::point(0.0, ascent));
+
+        let Some(outlined) = font.outline_glyph(glyph) else {
+            return;
+        };
+        let bounds = outlined.px_bounds();
+        let UVec2 {
+            x: cell_origin_x,
+            y: cell_origin_y,
+        } = self.tile_origin(index);
+
+        outlined.draw(|px, py, coverage| {
+            let x = cell_origin_x as i32 + bounds.min.x as i32 + px as i32;
+            let y = cell_origin_y as i32 + bounds.min.y as i32 + py as i32;
+
+            // Allow overflow into this cell's padding but not into the next tile
+            if x >= cell_origin_x as i32
+                && y >= cell_origin_y as i32
+                && (x as u32) < cell_origin_x + self.stride.x
+                && (y as u32) < cell_origin_y + self.stride.y
+            {
+                let idx = (y as u32 * atlas_width + x as u32) as usize * 4;
+                let alpha = (coverage * 255.0).round() as u8;
+                // White glyph, variable alpha
+                pixel_data[idx] = 255;
+                pixel_data[idx + 1] = 255;
+                pixel_data[idx + 2] = 255;
+                // Composite alpha (max with existing)
+                pixel_data[idx + 3] = pixel_data[idx + 3].max(alpha);
+            }
+        });
+    }
+
+    /// Copy a bitmap glyph into tile `index`, repeating each sheet pixel
+    /// `scale` x `scale` times.
+    fn draw_bitmap(
+        &self,
+        pixel_data: &mut [u8],
+        atlas_width: u32,
+        font: &BitmapFont,
+        scale: u32,
+        ch: char,
+        index: usize,
+    ) {
+        let Some(glyph) = font.glyph_index(ch) else {
+            return;
+        };
+        let UVec2 {
+            x: cell_origin_x,
+            y: cell_origin_y,
+        } = self.tile_origin(index);
+
+        for gy in 0..font.glyph_height {
+            for gx in 0..font.glyph_width {
+                let alpha = font.coverage(glyph, gx, gy);
+                if alpha == 0 {
+                    continue;
+                }
+                for sy in 0..scale {
+                    for sx in 0..scale {
+                        let x = cell_origin_x + gx * scale + sx;
+                        let y = cell_origin_y + gy * scale + sy;
+                        let idx = (y * atlas_width + x) as usize * 4;
+                        pixel_data[idx..idx + 4].copy_from_slice(&[255, 255, 255, alpha]);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Build the font atlas texture and layout for a given font size, font, and character set.
+///
+/// The texture stays within `max_dimension` texels on each side; chars that
+/// don't fit are left out (with an error) and fall back to LRU replacement
+/// when they're drawn.
+fn build_atlas_data_for_chars(
+    font: GlyphFont,
+    font_size: f32,
+    chars: &[char],
+    filter: AtlasFilter,
+    options: GlyphOptions,
+    max_dimension: u32,
+) -> AtlasData {
+    let cell_size = font.cell_size(font_size);
+    let cell_w = cell_size.x;
+
+    let rasterizer = GlyphRasterizer::new(font, font_size, cell_size, options);
+    let (columns, max_rows) = atlas_grid(chars.len(), rasterizer.stride, max_dimension);
+    let rasterizer = rasterizer.with_columns(columns);
+
+    let capacity = (columns * max_rows) as usize;
+    if chars.len() > capacity {
+        error!(
+            "Font atlas needs {} glyphs but only {capacity} fit in {max_dimension}x{max_dimension} \
+             texels; raise `max_atlas_dimension` or lower `font_size`",
+            chars.len()
+        );
+    }
+    let chars = &chars[..chars.len().min(capacity)];
+
+    let glyph_count = chars.len();
+    let atlas_rows = (glyph_count as u32).div_ceil(columns).max(1);
+    let pad = options.padding.texels(cell_w);
+    let atlas_width = rasterizer.stride.x * columns;
+    let atlas_height = rasterizer.stride.y * atlas_rows;
 
     let mut pixel_data = vec![0u8; (atlas_width * atlas_height * 4) as usize];
     let mut glyph_map = HashMap::new();
-
-    let ascent = scaled_font.ascent();
+    let mut color_glyphs = HashSet::new();
 
     for (i, &ch) in chars.iter().enumerate() {
         glyph_map.insert(ch, i);
-
-        let glyph_id = font.glyph_id(ch);
-        let glyph = glyph_id.with_scale_and_position(scale, ab_glyph::point(0.0, ascent));
-
-        if let Some(outlined) = font.outline_glyph(glyph) {
-            let bounds = outlined.px_bounds();
-            let grid_col = (i as u32) % ATLAS_COLS;
-            let grid_row = (i as u32) / ATLAS_COLS;
-            let cell_origin_x = grid_col * stride_w;
-            let cell_origin_y = grid_row * stride_h;
-
-            outlined.draw(|px, py, coverage| {
-                let x = cell_origin_x as i32 + bounds.min.x as i32 + px as i32;
-                let y = cell_origin_y as i32 + bounds.min.y as i32 + py as i32;
-
-                // Allow overflow into this cell's padding but not into the next tile
-                if x >= cell_origin_x as i32
-                    && y >= cell_origin_y as i32
-                    && (x as u32) < cell_origin_x + stride_w
-                    && (y as u32) < cell_origin_y + stride_h
-                {
-                    let idx = (y as u32 * atlas_width + x as u32) as usize * 4;
-                    let alpha = (coverage * 255.0).round() as u8;
-                    // White glyph, variable alpha
-                    pixel_data[idx] = 255;
-                    pixel_data[idx + 1] = 255;
-                    pixel_data[idx + 2] = 255;
-                    // Composite alpha (max with existing)
-                    pixel_data[idx + 3] = pixel_data[idx + 3].max(alpha);
-                }
-            });
+        if rasterizer.draw(&mut pixel_data, atlas_width, ch, i) {
+            color_glyphs.insert(ch);
         }
     }
 
-    let mut image = Image::new(
-        Extent3d {
-            width: atlas_width,
-            height: atlas_height,
-            depth_or_array_layers: 1,
-        },
-        TextureDimension::D2,
+    AtlasData::from_pixels(
         pixel_data,
-        TextureFormat::Rgba8UnormSrgb,
-        RenderAssetUsages::default(),
-    );
-    // Use linear filtering so anti-aliased glyphs stay smooth even when the
-    // app default sampler is set to nearest (common for pixel-art games).
-    image.sampler = bevy::image::ImageSampler::linear();
-
-    let layout = TextureAtlasLayout::from_grid(
+        UVec2::new(atlas_width, atlas_height),
         cell_size,
-        ATLAS_COLS,
-        atlas_rows,
-        Some(UVec2::new(pad, pad)),
-        None,
-    );
-
-    AtlasData {
-        image,
-        layout,
+        columns,
+        pad,
+        filter,
         glyph_map,
-        cell_size,
-        glyph_count,
+        color_glyphs,
+    )
+}
+
+impl AtlasData {
+    /// Wrap rasterized RGBA pixels `size` texels big in an image and a tile
+    /// layout of `columns` tiles per row, `pad` texels apart.
+    fn from_pixels(
+        pixel_data: Vec<u8>,
+        size: UVec2,
+        cell_size: UVec2,
+        columns: u32,
+        pad: u32,
+        filter: AtlasFilter,
+        glyph_map: HashMap<char, usize>,
+        color_glyphs: HashSet<char>,
+    ) -> Self {
+        let mut image = Image::new(
+            Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            pixel_data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        );
+        // Set the sampler explicitly so glyphs don't inherit the app default
+        // (often nearest in pixel-art games) when linear was asked for.
+        image.sampler = filter.sampler();
+
+        let rows = size.y / (cell_size.y + pad).max(1);
+        let layout = TextureAtlasLayout::from_grid(
+            cell_size,
+            columns,
+            rows,
+            Some(UVec2::new(pad, pad)),
+            None,
+        );
+
+        Self {
+            image,
+            layout,
+            glyph_count: glyph_map.len(),
+            glyph_map,
+            color_glyphs,
+            cell_size,
+            columns,
+        }
+    }
+
+    /// The atlas as a disk cache entry.
+    fn to_cached(&self) -> Option<CachedAtlas> {
+        Some(CachedAtlas {
+            size: self.image.size(),
+            cell_size: self.cell_size,
+            columns: self.columns,
+            glyph_map: self.glyph_map.clone(),
+            color_glyphs: self.color_glyphs.clone(),
+            pixels: self.image.data.clone()?,
+        })
     }
+
+    /// Rebuild an atlas from a disk cache entry.
+    fn from_cached(cached: CachedAtlas, options: GlyphOptions, filter: AtlasFilter) -> Self {
+        let pad = options.padding.texels(cached.cell_size.x);
+        Self::from_pixels(
+            cached.pixels,
+            cached.size,
+            cached.cell_size,
+            cached.columns,
+            pad,
+            filter,
+            cached.glyph_map,
+            cached.color_glyphs,
+        )
+    }
+}
+
+/// Disk cache key for an atlas of `chars` rasterized from outline font
+/// `font_bytes`. Changes whenever anything that affects the pixels does.
+fn atlas_cache_key(
+    font_bytes: &[u8],
+    font_size: f32,
+    chars: &[char],
+    options: GlyphOptions,
+    max_dimension: u32,
+) -> u64 {
+    use std::hash::{Hash, Hasher};
+
+    // Not `DefaultHasher`, whose output may change between Rust releases
+    let mut hasher = crate::atlas_cache::Fnv1a::default();
+    font_bytes.hash(&mut hasher);
+    font_size.to_bits().hash(&mut hasher);
+    chars.hash(&mut hasher);
+    options.hash_into(&mut hasher);
+    max_dimension.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Round a requested logical cell size to the nearest whole multiple (at least
+/// 1x) of the atlas tile on each axis, so every texel covers an integer number
+/// of physical pixels.
+pub(crate) fn snap_to_integer_scale(
+    requested: Vec2,
+    atlas_cell_size: UVec2,
+    scale_factor: f32,
+) -> Vec2 {
+    let tile = atlas_cell_size.as_vec2().max(Vec2::ONE);
+    let multiple = (requested * scale_factor / tile).round().max(Vec2::ONE);
+    tile * multiple / scale_factor
 }
 
 /// Correct layout cell dimensions so that `cell_width * scale_factor` exactly
 /// equals the atlas cell texel count.  This guarantees 1:1 texel-to-physical-pixel
 /// mapping on the sprite quad (no stretching / blurriness).
 /// The origin is also snapped to physical pixel boundaries.
-fn align_layout_to_atlas<T: 'static + Send + Sync>(
+pub(crate) fn align_layout_to_atlas<T: 'static + Send + Sync>(
     layout: &mut crate::TerminalLayout<T>,
     config: &crate::TerminalConfig<T>,
     atlas_cell_size: UVec2,
@@ -167,9 +1105,17 @@ fn align_layout_to_atlas<T: 'static + Send + Sync>(
 ) {
     // When cell_size_override is set, honour it — the caller has explicitly
     // decoupled grid spacing from glyph rasterisation (e.g. portrait overlay).
+    // With nearest filtering, snap it to whole multiples of the atlas tile so
+    // pixel glyphs scale up crisply.
     if let Some(cell_override) = config.cell_size_override {
-        layout.cell_width = cell_override.x;
-        layout.cell_height = cell_override.y;
+        let cell = match config.atlas_filter {
+            AtlasFilter::Nearest => {
+                snap_to_integer_scale(cell_override, atlas_cell_size, scale_factor)
+            }
+            AtlasFilter::Linear => cell_override,
+        };
+        layout.cell_width = cell.x;
+        layout.cell_height = cell.y;
     } else {
         layout.cell_width = atlas_cell_size.x as f32 / scale_factor;
         layout.cell_height = atlas_cell_size.y as f32 / scale_factor;
@@ -182,13 +1128,23 @@ fn align_layout_to_atlas<T: 'static + Send + Sync>(
     });
     // Round in physical-pixel space so the grid's top-left lands on a pixel boundary.
     layout.origin = (raw_origin * scale_factor).round() / scale_factor;
+    layout.columns = config.columns;
+    layout.rows = config.rows;
 }
 
 /// Generate the font atlas as a startup system.
+///
+/// For `FontSource::Asset`, this starts the load and builds a placeholder atlas
+/// from the default font; `finish_font_loading` swaps in the real font later.
+///
+/// With `share_atlas`, a terminal whose settings match an existing shared
+/// atlas uses that one instead of building its own.
 pub fn generate_font_atlas<T: 'static + Send + Sync>(
     mut commands: Commands,
+    asset_server: Res<AssetServer>,
     mut images: ResMut<Assets<Image>>,
     mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut shared: ResMut<SharedAtlases>,
     config: Res<crate::TerminalConfig<T>>,
     mut layout: ResMut<crate::TerminalLayout<T>>,
     window_query: Query<&Window, With<PrimaryWindow>>,
@@ -198,150 +1154,538 @@ pub fn generate_font_atlas<T: 'static + Send + Sync>(
         .map(|w| w.scale_factor())
         .unwrap_or(1.0);
 
-    let font_bytes = config.font.bytes().to_vec();
-    let chars = ascii_chars();
+    let mut bitmap = None;
+    let font_bytes = match &config.font {
+        FontSource::Bitmap(font) => {
+            bitmap = Some(font.clone());
+            FontSource::Default.bytes().to_vec()
+        }
+        FontSource::Asset(path) => {
+            commands.insert_resource(PendingFont::<T> {
+                handle: asset_server.load(path.clone()),
+                _marker: PhantomData,
+            });
+            FontSource::Default.bytes().to_vec()
+        }
+        source => validated_font_bytes(source.bytes()),
+    };
+    if bitmap.is_none() && matches!(config.font, FontSource::Custom(_)) {
+        warn_if_proportional(&font_bytes, config.monospace_policy);
+    }
     let raster_size = config.font_size * scale_factor;
-    let data = build_atlas_data_for_chars(&font_bytes, raster_size, &chars);
-    let image_handle = images.add(data.image);
-    let layout_handle = layouts.add(data.layout);
+    let font = GlyphFont::new(&font_bytes, bitmap.as_deref());
+    let options = GlyphOptions::from_config(&config);
+    let preload = startup_chars(&config);
+    let mut chars = with_preloaded(ascii_chars(), &preload, font, raster_size, options);
+    if let Some(max) = config.max_glyphs {
+        chars.truncate(max.max(1));
+    }
+    let key = config
+        .share_atlas
+        .then(|| AtlasKey::new(&config, &font_bytes, bitmap.as_ref(), scale_factor));
+    let mut pending_glyphs = HashSet::new();
+    let (assets, shared_generation) = match key.and_then(|key| shared.join(key)) {
+        Some((assets, generation)) => {
+            // Preloads the sharing terminals didn't ask for are added lazily
+            let missing = chars
+                .iter()
+                .filter(|ch| !assets.glyph_map.contains_key(*ch));
+            pending_glyphs.extend(missing);
+            (assets, generation)
+        }
+        None => {
+            let data = load_or_build_atlas(&config, &font_bytes, font, raster_size, &chars);
+            let assets = AtlasAssets::add(data, &mut images, &mut layouts);
+            if let Some(key) = key {
+                shared.insert(key, assets.clone());
+            }
+            (assets, 0)
+        }
+    };
 
     // Align layout cell dimensions to the atlas so sprites render 1:1.
-    align_layout_to_atlas(&mut layout, &config, data.cell_size, scale_factor);
+    align_layout_to_atlas(&mut layout, &config, assets.cell_size, scale_factor);
 
+    let cap_center = match bitmap {
+        Some(_) => 0.0,
+        None => cap_center_offset(&font_bytes),
+    };
     commands.insert_resource(FontAtlasResource::<T> {
-        image: image_handle,
-        layout: layout_handle,
-        glyph_map: data.glyph_map,
-        cell_size: data.cell_size,
+        image: assets.image,
+        layout: assets.layout,
+        glyph_map: assets.glyph_map,
+        color_glyphs: assets.color_glyphs,
+        cell_size: assets.cell_size,
+        cap_center,
         font_size: config.font_size,
         scale_factor,
+        filter: config.atlas_filter,
+        options,
         font_bytes,
-        pending_glyphs: HashSet::new(),
-        glyph_count: data.glyph_count,
+        bitmap,
+        pending_glyphs,
+        glyph_count: assets.glyph_count,
+        columns: assets.columns,
+        max_glyphs: config.max_glyphs,
+        glyph_last_used: HashMap::new(),
+        use_tick: 0,
+        font_stale: false,
+        shared: key,
+        shared_generation,
         _marker: PhantomData,
     });
 }
 
+/// Rasterize `chars`, or read them from the disk cache when
+/// `atlas_cache_dir` is set and has a matching entry.
+fn load_or_build_atlas<T: 'static + Send + Sync>(
+    config: &crate::TerminalConfig<T>,
+    font_bytes: &[u8],
+    font: GlyphFont,
+    raster_size: f32,
+    chars: &[char],
+) -> AtlasData {
+    let options = GlyphOptions::from_config(config);
+    // Only outline fonts are worth caching; bitmap sheets are just copied
+    let cache = config
+        .atlas_cache_dir
+        .as_deref()
+        .filter(|_| matches!(font, GlyphFont::Outline(_)))
+        .map(|dir| {
+            let max_dimension = config.max_atlas_dimension;
+            let key = atlas_cache_key(font_bytes, raster_size, chars, options, max_dimension);
+            (dir, key)
+        });
+    if let Some(cached) = cache.and_then(|(dir, key)| crate::atlas_cache::load(dir, key)) {
+        return AtlasData::from_cached(cached, options, config.atlas_filter);
+    }
+    let data = build_atlas_data_for_chars(
+        font,
+        raster_size,
+        chars,
+        config.atlas_filter,
+        options,
+        config.max_atlas_dimension,
+    );
+    if let (Some((dir, key)), Some(cached)) = (cache, data.to_cached()) {
+        crate::atlas_cache::save(dir, key, &cached);
+    }
+    data
+}
+
+/// Polls a `PendingFont<T>` and, once the asset is loaded (or has failed),
+/// hands its bytes to the atlas and flags it for a rebuild. A failed load
+/// logs a warning and keeps the default font.
+pub fn finish_font_loading<T: 'static + Send + Sync>(
+    mut commands: Commands,
+    config: Res<crate::TerminalConfig<T>>,
+    pending: Option<Res<PendingFont<T>>>,
+    fonts: Res<Assets<Font>>,
+    asset_server: Res<AssetServer>,
+    mut atlas: ResMut<FontAtlasResource<T>>,
+) {
+    let Some(pending) = pending else {
+        return;
+    };
+
+    let font_bytes = if let Some(font) = fonts.get(&pending.handle) {
+        validated_font_bytes(&font.data)
+    } else if asset_server.load_state(&pending.handle).is_failed() {
+        warn!("Failed to load terminal font asset, falling back to the default font");
+        FontSource::Default.bytes().to_vec()
+    } else {
+        return;
+    };
+
+    warn_if_proportional(&font_bytes, config.monospace_policy);
+    atlas.cap_center = cap_center_offset(&font_bytes);
+    atlas.font_bytes = font_bytes;
+    atlas.font_stale = true;
+    commands.remove_resource::<PendingFont<T>>();
+}
+
+/// Opt-in record of the characters terminal `T`'s font can't render, which
+/// otherwise show up as unexplained blanks. Set `enabled`, e.g. while
+/// checking a translation, then read `chars` or watch the log.
+///
+/// Each char is warned about once. Chars found within `log_interval` of the
+/// last warning wait and go out together in the next one, so a stream of
+/// unsupported text doesn't flood the log.
+#[derive(Resource, Clone, Debug)]
+pub struct MissingGlyphLog<T: 'static + Send + Sync> {
+    /// Collect and log missing chars (default: false).
+    pub enabled: bool,
+    /// Every char found missing while enabled, in code point order.
+    pub chars: BTreeSet<char>,
+    /// Minimum seconds between warnings (default: 5.0).
+    pub log_interval: f32,
+    /// Found since the last warning.
+    unlogged: Vec<char>,
+    /// `Time::elapsed_secs` at the last warning.
+    logged_at: Option<f32>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: 'static + Send + Sync> Default for MissingGlyphLog<T> {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            chars: BTreeSet::new(),
+            log_interval: 5.0,
+            unlogged: Vec::new(),
+            logged_at: None,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: 'static + Send + Sync> MissingGlyphLog<T> {
+    /// Note that the font can't render `ch`.
+    pub fn record(&mut self, ch: char) {
+        if self.chars.insert(ch) {
+            self.unlogged.push(ch);
+        }
+    }
+
+    /// Chars to warn about at time `now` (in seconds), if any are waiting
+    /// and the last warning was at least `log_interval` ago.
+    pub fn take_due(&mut self, now: f32) -> Option<Vec<char>> {
+        if self.unlogged.is_empty() {
+            return None;
+        }
+        if self
+            .logged_at
+            .is_some_and(|logged_at| now - logged_at < self.log_interval)
+        {
+            return None;
+        }
+        self.logged_at = Some(now);
+        let mut chars = std::mem::take(&mut self.unlogged);
+        chars.sort();
+        Some(chars)
+    }
+}
+
 /// Expands the font atlas when new (previously unseen) characters are pending.
 /// Runs before `rebuild_font_atlas` so that new glyphs are available for the
 /// current frame's sync pass.
+///
+/// New glyphs are appended into unused tiles of the existing texture; the
+/// texture only grows (by whole rows) when it runs out of tiles. Existing
+/// glyph indices never move, so only cells showing the new characters need
+/// to be re-synced.
+///
+/// Once `max_glyphs` is reached, or the texture would exceed
+/// `max_atlas_dimension`, each new glyph instead takes over the tile of the
+/// least-recently-used one. Cells still showing an evicted glyph are marked
+/// dirty so sync re-queues it as pending.
+///
+/// A shared atlas is first brought up to date with glyphs other terminals
+/// placed, and this terminal's additions are published back to it. LRU
+/// eviction only sees this terminal's usage, so terminals sharing an atlas
+/// should leave `max_glyphs` room for each other's glyphs.
+///
+/// Chars the font can't render are dropped and keep showing as blanks; with
+/// `MissingGlyphLog<T>` enabled they're recorded there and logged.
 pub fn expand_font_atlas<T: 'static + Send + Sync>(
+    config: Res<crate::TerminalConfig<T>>,
     mut atlas: ResMut<FontAtlasResource<T>>,
+    mut shared: ResMut<SharedAtlases>,
     terminal_res: Res<crate::TerminalResource<T>>,
-    layout: Res<crate::TerminalLayout<T>>,
     mut images: ResMut<Assets<Image>>,
     mut layouts: ResMut<Assets<TextureAtlasLayout>>,
-    cell_index: Res<CellEntityIndex<T>>,
-    mut fg_query: Query<&mut Sprite, (With<ForegroundSprite<T>>, Without<BackgroundSprite<T>>)>,
-) {
+    time: Res<Time>,
+    mut missing: ResMut<MissingGlyphLog<T>>,
+) -> Result {
+    if let Some(chars) = missing.take_due(time.elapsed_secs()) {
+        let list: String = chars.iter().collect();
+        warn!(
+            "Font can't render {} char(s), shown as blanks: {list:?}",
+            chars.len()
+        );
+    }
+
+    let behind_shared = atlas.shared.is_some_and(|key| {
+        shared
+            .entries
+            .get(&key)
+            .is_some_and(|entry| entry.generation != atlas.shared_generation)
+    });
+    if !behind_shared && atlas.pending_glyphs.is_empty() {
+        return Ok(());
+    }
+    // Leave the work for a later frame while another thread holds the terminal
+    let Some(mut terminal) = terminal_res.try_lock()? else {
+        return Ok(());
+    };
+
+    if let Some(key) = atlas.shared {
+        let newer = shared
+            .entries
+            .get(&key)
+            .filter(|entry| entry.generation != atlas.shared_generation);
+        if let Some(entry) = newer {
+            let changed = atlas.adopt(&entry.assets);
+            atlas.shared_generation = entry.generation;
+            if !changed.is_empty() {
+                terminal.backend_mut().mark_dirty_where(|cell| {
+                    cell.symbol()
+                        .chars()
+                        .next()
+                        .is_some_and(|ch| changed.contains(&ch))
+                });
+            }
+        }
+    }
+
     if atlas.pending_glyphs.is_empty() {
-        return;
+        return Ok(());
     }
 
-    // Drain pending first to release the mutable borrow before accessing font_bytes
+    // Reborrow so the rasterizer can hold `font_bytes` while other fields are updated
+    let atlas = &mut *atlas;
     let pending: Vec<char> = atlas.pending_glyphs.drain().collect();
 
-    // Filter pending chars to only those the font can actually render
-    let font = FontRef::try_from_slice(&atlas.font_bytes).expect("Failed to parse font");
-    let scale = ab_glyph::PxScale::from(atlas.font_size);
-    let ascent = font.as_scaled(scale).ascent();
+    let raster_size = atlas.font_size * atlas.scale_factor;
+    let font = GlyphFont::new(&atlas.font_bytes, atlas.bitmap.as_deref());
+    let rasterizer = GlyphRasterizer::new(font, raster_size, atlas.cell_size, atlas.options)
+        .with_columns(atlas.columns);
 
-    let new_chars: Vec<char> = pending
+    // Filter pending chars to only those the font can actually render,
+    // sorted so that placement order is deterministic.
+    let mut new_chars: Vec<char> = pending
         .into_iter()
+        .filter(|ch| !atlas.glyph_map.contains_key(ch))
         .filter(|&ch| {
-            let glyph_id = font.glyph_id(ch);
-            let glyph = glyph_id.with_scale_and_position(scale, ab_glyph::point(0.0, ascent));
-            font.outline_glyph(glyph).is_some()
+            let renders = rasterizer.can_render(ch);
+            if !renders && missing.enabled {
+                missing.record(ch);
+            }
+            renders
         })
         .collect();
+    new_chars.sort();
 
     if new_chars.is_empty() {
-        return;
+        return Ok(());
     }
 
-    // Merge existing glyph_map keys with new chars, sorted for deterministic ordering
-    let mut all_chars: Vec<char> = atlas.glyph_map.keys().copied().collect();
-    all_chars.extend(new_chars);
-    all_chars.sort();
-    all_chars.dedup();
+    // Chars beyond the glyph cap or the texture size limit have to replace
+    // an existing glyph
+    let max_rows = (config.max_atlas_dimension / rasterizer.stride.y).max(1);
+    let capacity = (atlas.columns * max_rows) as usize;
+    let cap = atlas.max_glyphs.map_or(capacity, |max| max.min(capacity));
+    if capacity < atlas.max_glyphs.unwrap_or(usize::MAX)
+        && atlas.glyph_count + new_chars.len() > capacity
+    {
+        error_once!(
+            "Font atlas is full at {capacity} glyphs ({} texels max); replacing \
+             least-recently-used glyphs. Raise `max_atlas_dimension` to fit more",
+            config.max_atlas_dimension
+        );
+    }
+    let free = cap.saturating_sub(atlas.glyph_count);
+    let replacements = new_chars.split_off(new_chars.len().min(free));
 
-    let raster_size = atlas.font_size * atlas.scale_factor;
-    let data = build_atlas_data_for_chars(&atlas.font_bytes, raster_size, &all_chars);
-    let image_handle = images.add(data.image);
-    let layout_handle = layouts.add(data.layout);
-    atlas.image = image_handle.clone();
-    atlas.layout = layout_handle.clone();
-    atlas.glyph_map = data.glyph_map;
-    atlas.cell_size = data.cell_size;
-    atlas.glyph_count = data.glyph_count;
-
-    // Update all foreground sprite handles to point to the new atlas
-    let fg_custom_size = Some(Vec2::new(layout.cell_width, layout.cell_height));
-    for &fg_entity in &cell_index.fg_entities {
-        if let Ok(mut fg_sprite) = fg_query.get_mut(fg_entity) {
-            fg_sprite.image = image_handle.clone();
-            fg_sprite.custom_size = fg_custom_size;
-            if let Some(ref mut tex_atlas) = fg_sprite.texture_atlas {
-                tex_atlas.layout = layout_handle.clone();
+    let (Some(image), Some(tex_layout)) = (images.get_mut(&atlas.image), layouts.get_mut(&atlas.layout))
+    else {
+        return Ok(());
+    };
+
+    // Grow the texture by whole rows when there aren't enough free tiles.
+    // The width never changes, so existing pixel rows stay where they are.
+    let needed = atlas.glyph_count + new_chars.len();
+    if needed > tex_layout.textures.len() {
+        let rows = needed.div_ceil(atlas.columns as usize) as u32;
+        // `resize` keeps the image's sampler, so the filter carries over
+        image.resize(Extent3d {
+            width: rasterizer.stride.x * atlas.columns,
+            height: rasterizer.stride.y * rows,
+            depth_or_array_layers: 1,
+        });
+        let pad = atlas.options.padding.texels(atlas.cell_size.x);
+        *tex_layout = TextureAtlasLayout::from_grid(
+            atlas.cell_size,
+            atlas.columns,
+            rows,
+            Some(UVec2::new(pad, pad)),
+            None,
+        );
+    }
+
+    let atlas_width = image.width();
+    let Some(pixel_data) = image.data.as_mut() else {
+        return Ok(());
+    };
+
+    let first_index = atlas.glyph_count;
+    for (offset, &ch) in new_chars.iter().enumerate() {
+        let index = first_index + offset;
+        if rasterizer.draw(pixel_data, atlas_width, ch, index) {
+            atlas.color_glyphs.insert(ch);
+        }
+        atlas.glyph_map.insert(ch, index);
+    }
+    atlas.glyph_count = needed;
+
+    // Chars whose cells need re-syncing: newly placed glyphs and evicted ones
+    let mut changed: HashSet<char> = new_chars.into_iter().collect();
+
+    if !replacements.is_empty() {
+        let on_screen: HashSet<char> = terminal
+            .backend()
+            .buffer()
+            .iter()
+            .filter_map(|cell| cell.symbol().chars().next())
+            .collect();
+
+        for ch in replacements {
+            // Nothing left to evict; the char is re-queued if it's drawn again
+            let Some(victim) = atlas.lru_victim(&on_screen, &changed) else {
+                break;
+            };
+            let Some(index) = atlas.glyph_map.remove(&victim) else {
+                break;
+            };
+            atlas.glyph_last_used.remove(&victim);
+            atlas.color_glyphs.remove(&victim);
+
+            rasterizer.clear(pixel_data, atlas_width, index);
+            if rasterizer.draw(pixel_data, atlas_width, ch, index) {
+                atlas.color_glyphs.insert(ch);
             }
+            atlas.glyph_map.insert(ch, index);
+
+            changed.insert(victim);
+            changed.insert(ch);
         }
     }
 
-    // Mark all cells dirty so sync re-processes glyph indices with the expanded atlas
-    terminal_res.0.lock().unwrap().backend_mut().mark_all_dirty();
+    terminal.backend_mut().mark_dirty_where(|cell| {
+        cell.symbol()
+            .chars()
+            .next()
+            .is_some_and(|ch| changed.contains(&ch))
+    });
+
+    if let Some(key) = atlas.shared {
+        atlas.shared_generation = shared.publish(key, atlas.assets());
+    }
+    Ok(())
 }
 
-/// Detects when `TerminalConfig.font_size` has changed and rebuilds the atlas,
+/// Detects when `TerminalConfig.font_size`, `atlas_filter` or a glyph shaping
+/// option has changed (or a new font finished loading) and rebuilds the atlas,
 /// cell positions, and sprite sizes to match.
+///
+/// A newly loaded font also gets the `initial_charset` and `preload_chars`
+/// the placeholder font couldn't render.
+///
+/// A terminal on a shared atlas leaves it for the one matching its new
+/// settings, so the other terminals keep theirs unchanged.
 pub fn rebuild_font_atlas<T: 'static + Send + Sync>(
     config: Res<crate::TerminalConfig<T>>,
     mut layout: ResMut<crate::TerminalLayout<T>>,
     mut atlas: ResMut<FontAtlasResource<T>>,
+    mut shared: ResMut<SharedAtlases>,
+    terminal_res: Res<crate::TerminalResource<T>>,
     mut images: ResMut<Assets<Image>>,
     mut layouts: ResMut<Assets<TextureAtlasLayout>>,
     cell_index: Res<CellEntityIndex<T>>,
     window_query: Query<&Window, With<PrimaryWindow>>,
     mut parent_query: Query<(&GridPosition, &mut BaseTransform, &mut Transform, &mut Sprite), With<TerminalCell<T>>>,
     mut fg_query: Query<&mut Sprite, (With<ForegroundSprite<T>>, Without<TerminalCell<T>>)>,
-) {
+) -> Result {
     let scale_factor = window_query
         .single()
         .map(|w| w.scale_factor())
         .unwrap_or(1.0);
 
-    if config.font_size == atlas.font_size && scale_factor == atlas.scale_factor {
-        return;
+    if !atlas.font_stale
+        && config.font_size == atlas.font_size
+        && scale_factor == atlas.scale_factor
+        && config.atlas_filter == atlas.filter
+        && GlyphOptions::from_config(&config) == atlas.options
+    {
+        return Ok(());
     }
+    // Retried next frame while a `TerminalDriver` holds the terminal
+    let Some(mut terminal) = terminal_res.try_lock()? else {
+        return Ok(());
+    };
+    let new_font = std::mem::take(&mut atlas.font_stale);
 
-    // Rebuild the atlas at the new font size with all currently known chars
+    // Rebuild the atlas with all currently known chars, keeping their indices
     let mut all_chars: Vec<char> = atlas.glyph_map.keys().copied().collect();
-    all_chars.sort();
+    all_chars.sort_by_key(|ch| atlas.glyph_map[ch]);
 
     let raster_size = config.font_size * scale_factor;
-    let data = build_atlas_data_for_chars(&atlas.font_bytes, raster_size, &all_chars);
+    let options = GlyphOptions::from_config(&config);
+    let font = GlyphFont::new(&atlas.font_bytes, atlas.bitmap.as_deref());
+    if new_font {
+        let preload = startup_chars(&config);
+        all_chars = with_preloaded(all_chars, &preload, font, raster_size, options);
+        if let Some(max) = atlas.max_glyphs {
+            all_chars.truncate(max.max(atlas.glyph_count));
+        }
+    }
+
+    let key = config.share_atlas.then(|| {
+        AtlasKey::new(
+            &config,
+            &atlas.font_bytes,
+            atlas.bitmap.as_ref(),
+            scale_factor,
+        )
+    });
+    if let Some(old_key) = atlas.shared {
+        shared.leave(old_key);
+    }
+    let (assets, shared_generation) = match key.and_then(|key| shared.join(key)) {
+        Some((assets, generation)) => {
+            // Glyphs sit in different tiles in the joined atlas; re-sync
+            // everything and add the ones it lacks
+            let missing = all_chars.iter().filter(|ch| !assets.glyph_map.contains_key(*ch));
+            atlas.pending_glyphs.extend(missing);
+            terminal.backend_mut().mark_all_dirty();
+            (assets, generation)
+        }
+        None => {
+            let data = build_atlas_data_for_chars(
+                font,
+                raster_size,
+                &all_chars,
+                config.atlas_filter,
+                options,
+                config.max_atlas_dimension,
+            );
+            let assets = AtlasAssets::add(data, &mut images, &mut layouts);
+            if let Some(key) = key {
+                shared.insert(key, assets.clone());
+            }
+            (assets, 0)
+        }
+    };
 
     // Recompute layout from atlas cell dimensions for 1:1 texel mapping.
     *layout = crate::TerminalLayout::from_config(&config);
-    align_layout_to_atlas(&mut layout, &config, data.cell_size, scale_factor);
-    let image_handle = images.add(data.image);
-    let layout_handle = layouts.add(data.layout);
-    atlas.image = image_handle.clone();
-    atlas.layout = layout_handle.clone();
-    atlas.glyph_map = data.glyph_map;
-    atlas.cell_size = data.cell_size;
+    align_layout_to_atlas(&mut layout, &config, assets.cell_size, scale_factor);
+    let image_handle = assets.image.clone();
+    let layout_handle = assets.layout.clone();
+    atlas.set_assets(assets);
     atlas.font_size = config.font_size;
+    atlas.filter = config.atlas_filter;
+    atlas.options = options;
     atlas.scale_factor = scale_factor;
-    atlas.glyph_count = data.glyph_count;
+    atlas.shared = key;
+    atlas.shared_generation = shared_generation;
 
     // Update all cell positions and BG sprites on parent entities
     let bg_size = layout.bg_sprite_size();
     for (grid_pos, mut base_tf, mut transform, mut bg_sprite) in parent_query.iter_mut() {
-        let world_x =
-            layout.origin.x + (grid_pos.col as f32) * layout.cell_width + layout.cell_width / 2.0;
-        let world_y = layout.origin.y
-            - (grid_pos.row as f32) * layout.cell_height
-            - layout.cell_height / 2.0;
-        let translation = Vec3::new(world_x, world_y, config.z_layer);
+        let translation = layout
+            .grid_to_world(grid_pos.col, grid_pos.row)
+            .extend(config.z_layer);
         base_tf.translation = translation;
         transform.translation = translation;
         bg_sprite.custom_size = Some(bg_size);
@@ -358,4 +1702,516 @@ pub fn rebuild_font_atlas<T: 'static + Send + Sync>(
             }
         }
     }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snap_to_integer_scale() {
+        let tile = UVec2::new(8, 16);
+        let snap = |w, h, scale_factor| snap_to_integer_scale(Vec2::new(w, h), tile, scale_factor);
+        // 3.2x / 2.9x requested -> 3x on both axes
+        assert_eq!(snap(25.6, 46.4, 1.0), Vec2::new(24.0, 48.0));
+        // Never below 1x
+        assert_eq!(snap(2.0, 2.0, 1.0), Vec2::new(8.0, 16.0));
+        // HiDPI: tile is in physical pixels, result in logical
+        assert_eq!(snap(16.0, 32.0, 2.0), Vec2::new(16.0, 32.0));
+    }
+
+    #[test]
+    fn test_nearest_override_layout_is_integer_scaled() {
+        struct Term;
+        let config = crate::TerminalConfig::<Term> {
+            font_size: 10.0,
+            cell_size_override: Some(Vec2::new(30.0, 50.0)),
+            atlas_filter: AtlasFilter::Nearest,
+            ..default()
+        };
+        let data = build_atlas_data_for_chars(
+            GlyphFont::Outline(FontSource::Default.bytes()),
+            config.font_size,
+            &ascii_chars(),
+            config.atlas_filter,
+            GlyphOptions::default(),
+            config.max_atlas_dimension,
+        );
+        let mut layout = crate::TerminalLayout::from_config(&config);
+        align_layout_to_atlas(&mut layout, &config, data.cell_size, 1.0);
+
+        let tile = data.cell_size.as_vec2();
+        let scale = Vec2::new(layout.cell_width, layout.cell_height) / tile;
+        assert_eq!(scale, scale.round());
+        assert!(matches!(
+            data.image.sampler,
+            bevy::image::ImageSampler::Descriptor(ref d)
+                if d.mag_filter == bevy::image::ImageFilterMode::Nearest
+        ));
+    }
+
+    #[test]
+    fn test_atlas_key_matches_same_settings_only() {
+        struct Term;
+        let config = crate::TerminalConfig::<Term>::default();
+        let bytes = FontSource::Default.bytes();
+        let key = |config: &crate::TerminalConfig<Term>, scale_factor| {
+            AtlasKey::new(config, bytes, None, scale_factor)
+        };
+        assert_eq!(key(&config, 1.0), key(&crate::TerminalConfig::default(), 1.0));
+        assert_ne!(key(&config, 1.0), key(&config, 2.0));
+
+        let larger = crate::TerminalConfig::<Term> {
+            font_size: config.font_size + 1.0,
+            ..default()
+        };
+        assert_ne!(key(&config, 1.0), key(&larger, 1.0));
+    }
+
+    #[test]
+    fn test_shared_atlas_users_and_generations() {
+        let key = AtlasKey(1);
+        let assets = AtlasAssets {
+            image: Handle::default(),
+            layout: Handle::default(),
+            glyph_map: HashMap::from([('a', 0)]),
+            color_glyphs: HashSet::new(),
+            cell_size: UVec2::new(8, 16),
+            glyph_count: 1,
+            columns: 16,
+        };
+        let mut shared = SharedAtlases::default();
+        assert!(shared.join(key).is_none());
+
+        shared.insert(key, assets.clone());
+        let (joined, generation) = shared.join(key).unwrap();
+        assert_eq!(joined.glyph_map, assets.glyph_map);
+        assert_eq!(generation, 0);
+        assert_eq!(shared.users(key), 2);
+
+        let mut grown = assets;
+        grown.glyph_map.insert('b', 1);
+        grown.glyph_count = 2;
+        assert_eq!(shared.publish(key, grown), 1);
+        assert_eq!(shared.entries[&key].assets.glyph_count, 2);
+
+        // Dropped with its last user
+        shared.leave(key);
+        assert_eq!(shared.len(), 1);
+        shared.leave(key);
+        assert!(shared.is_empty());
+    }
+
+    #[test]
+    fn test_shared_atlas_resyncs_glyphs_added_by_other_terminal() {
+        use bevy::time::TimeUpdateStrategy;
+        use ratatui::style::Style;
+
+        use crate::testing::FRAME_TIME;
+        use crate::{TerminalEmuPlugin, TerminalResource};
+
+        struct Left;
+        struct Right;
+
+        fn plugin<T: 'static + Send + Sync>() -> TerminalEmuPlugin<T> {
+            TerminalEmuPlugin::new()
+                .columns(2)
+                .rows(1)
+                .share_atlas(true)
+        }
+
+        // The tile 'é' maps to, and the tile its cell actually shows
+        fn shown_glyph<T: 'static + Send + Sync>(app: &App) -> (Option<usize>, usize) {
+            let world = app.world();
+            let fg = world.resource::<CellEntityIndex<T>>().get_fg(0, 0).unwrap();
+            let atlas = world.resource::<FontAtlasResource<T>>();
+            let sprite = world.get::<Sprite>(fg).unwrap();
+            let shown = sprite.texture_atlas.as_ref().unwrap().index;
+            (atlas.glyph_map.get(&'é').copied(), shown)
+        }
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME_TIME))
+            .add_plugins(bevy::asset::AssetPlugin::default())
+            .init_asset::<Image>()
+            .init_asset::<TextureAtlasLayout>()
+            .init_asset::<bevy::text::Font>()
+            .add_plugins(plugin::<Left>())
+            .add_plugins(plugin::<Right>());
+        app.update();
+
+        // Both terminals queue the same char; whichever expands second finds
+        // it already added by the other and must still re-sync its cell
+        let draw = |frame: &mut ratatui::Frame| {
+            frame.buffer_mut().set_string(0, 0, "é", Style::default());
+        };
+        let left = app.world().resource::<TerminalResource<Left>>();
+        left.draw(draw).unwrap();
+        let right = app.world().resource::<TerminalResource<Right>>();
+        right.draw(draw).unwrap();
+        for _ in 0..4 {
+            app.update();
+        }
+
+        let (left_tile, left_shown) = shown_glyph::<Left>(&app);
+        let (right_tile, right_shown) = shown_glyph::<Right>(&app);
+        assert!(left_tile.is_some());
+        assert_eq!(left_tile, right_tile);
+        assert_eq!(left_tile, Some(left_shown));
+        assert_eq!(right_tile, Some(right_shown));
+    }
+
+    #[test]
+    fn test_asset_font_swaps_in_or_keeps_placeholder() {
+        use std::time::Duration;
+
+        use bevy::time::TimeUpdateStrategy;
+
+        use crate::testing::FRAME_TIME;
+        use crate::TerminalEmuPlugin;
+
+        struct Missing;
+        struct Loaded;
+
+        fn plugin<T: 'static + Send + Sync>(path: &str) -> TerminalEmuPlugin<T> {
+            let font = FontSource::Asset(path.into());
+            TerminalEmuPlugin::new().columns(2).rows(1).font(font)
+        }
+
+        fn loading<T: 'static + Send + Sync>(app: &App) -> bool {
+            app.world().contains_resource::<PendingFont<T>>()
+        }
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME_TIME))
+            .add_plugins(bevy::asset::AssetPlugin::default())
+            .init_asset::<Image>()
+            .init_asset::<TextureAtlasLayout>()
+            .init_asset::<Font>()
+            .init_asset_loader::<bevy::text::FontLoader>()
+            .add_plugins(plugin::<Missing>("missing.ttf"))
+            .add_plugins(plugin::<Loaded>("FiraCodeNerdFont-Regular.ttf"));
+        app.update();
+
+        // Both draw from the default font until their load settles
+        let default_font = FontSource::Default.bytes();
+        let atlas = app.world().resource::<FontAtlasResource<Loaded>>();
+        assert_eq!(atlas.font_bytes, default_font);
+
+        // Loads run on the IO task pool
+        for _ in 0..400 {
+            if !loading::<Missing>(&app) && !loading::<Loaded>(&app) {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+            app.update();
+        }
+        app.update();
+
+        let missing = app.world().resource::<FontAtlasResource<Missing>>();
+        assert!(!loading::<Missing>(&app));
+        assert_eq!(missing.font_bytes, default_font);
+        assert!(!missing.font_stale);
+        assert!(missing.glyph_map.contains_key(&'a'));
+
+        let loaded = app.world().resource::<FontAtlasResource<Loaded>>();
+        let fira = include_bytes!("../assets/FiraCodeNerdFont-Regular.ttf");
+        assert!(!loading::<Loaded>(&app));
+        assert_eq!(loaded.font_bytes, fira);
+        assert!(!loaded.font_stale);
+        assert!(loaded.glyph_map.contains_key(&'a'));
+    }
+
+    struct Full;
+    type Harness = crate::testing::TerminalTestHarness<Full>;
+
+    /// A harness showing `text`, its atlas capped at the glyphs it already
+    /// has and `oldest` made the least recently used of them.
+    fn full_atlas(columns: u16, text: &str, oldest: char) -> Harness {
+        use ratatui::style::Style;
+
+        use crate::TerminalEmuPlugin;
+
+        let plugin = TerminalEmuPlugin::<Full>::new().columns(columns).rows(1);
+        let mut harness = Harness::new(plugin);
+        let style = Style::default();
+        harness.draw(|frame| frame.buffer_mut().set_string(0, 0, text, style));
+
+        let world = harness.app.world_mut();
+        let mut atlas = world.resource_mut::<FontAtlasResource<Full>>();
+        atlas.max_glyphs = Some(atlas.glyph_count);
+        let chars: Vec<char> = atlas.glyph_map.keys().copied().collect();
+        for ch in chars {
+            atlas.glyph_last_used.insert(ch, 10);
+        }
+        atlas.glyph_last_used.insert(oldest, 1);
+        harness
+    }
+
+    /// Prints `ch` at `col` behind sync's back and runs `expand_font_atlas`
+    /// on it, returning the dirty flags it leaves.
+    fn add_glyph(harness: &mut Harness, col: u16, ch: char) -> Vec<bool> {
+        use bevy::ecs::system::RunSystemOnce;
+        use ratatui::style::Style;
+
+        let world = harness.app.world_mut();
+        let mut atlas = world.resource_mut::<FontAtlasResource<Full>>();
+        atlas.pending_glyphs.insert(ch);
+        let print = |backend: &mut crate::BevyBackend| {
+            backend.print(col, 0, &ch.to_string(), Style::default());
+            backend.clear_dirty();
+        };
+        harness.terminal().with_backend(print).unwrap();
+        let world = harness.app.world_mut();
+        let expanded = world.run_system_once(expand_font_atlas::<Full>).unwrap();
+        expanded.unwrap();
+        let dirty = |backend: &mut crate::BevyBackend| backend.dirty_cells().to_vec();
+        harness.terminal().with_backend(dirty).unwrap()
+    }
+
+    #[test]
+    fn test_max_glyphs_evicts_least_recently_used_off_screen_glyph() {
+        let mut harness = full_atlas(4, "ab", 'q');
+        let world = harness.app.world_mut();
+        let mut atlas = world.resource_mut::<FontAtlasResource<Full>>();
+        // Older than 'q', but on screen
+        atlas.glyph_last_used.extend([('a', 0), ('b', 0)]);
+        let (count, q_tile) = (atlas.glyph_count, atlas.glyph_map[&'q']);
+
+        let dirty = add_glyph(&mut harness, 2, 'é');
+        let atlas = harness.app.world().resource::<FontAtlasResource<Full>>();
+        assert!(!atlas.glyph_map.contains_key(&'q'));
+        assert!(!atlas.glyph_last_used.contains_key(&'q'));
+        assert_eq!(atlas.glyph_map.get(&'é'), Some(&q_tile));
+        assert!(atlas.glyph_map.contains_key(&'a'));
+        assert!(atlas.glyph_map.contains_key(&'b'));
+        assert_eq!(atlas.glyph_count, count);
+        assert_eq!(atlas.glyph_map.len(), count);
+        assert_eq!(dirty, [false, false, true, false]);
+    }
+
+    #[test]
+    fn test_max_glyphs_evicting_shown_glyph_redraws_its_cells() {
+        // Every glyph on screen, so the victim has to be a shown one
+        let text: String = ('!'..='~').collect();
+        let mut harness = full_atlas(96, &text, 'q');
+        let world = harness.app.world();
+        let count = world.resource::<FontAtlasResource<Full>>().glyph_count;
+
+        let dirty = add_glyph(&mut harness, 95, 'é');
+        let atlas = harness.app.world().resource::<FontAtlasResource<Full>>();
+        assert!(!atlas.glyph_map.contains_key(&'q'));
+        assert!(atlas.glyph_map.contains_key(&'é'));
+        assert_eq!(atlas.glyph_count, count);
+        assert_eq!(atlas.glyph_map.len(), count);
+        let dirty_cols: Vec<usize> = (0..dirty.len()).filter(|&col| dirty[col]).collect();
+        let q_col = text.find('q').unwrap();
+        assert_eq!(dirty_cols, [q_col, 95]);
+    }
+
+    #[test]
+    fn test_snap_strokes_reach_tile_edges() {
+        // 12x12 tile holding a horizontal stroke that stops 2 texels short
+        // of each side, and an isolated dot in the middle.
+        let (w, h) = (12u32, 12u32);
+        let mut pixels = vec![0u8; (w * h * 4) as usize];
+        let mut set = |x: u32, y: u32, a: u8| pixels[((y * w + x) * 4 + 3) as usize] = a;
+        for x in 2..10 {
+            set(x, 5, 255);
+            set(x, 6, 128);
+        }
+        set(6, 3, 255);
+
+        snap_strokes_to_edges(&mut pixels, w, UVec2::ZERO, UVec2::new(w, h));
+        let alpha = |x: u32, y: u32| pixels[((y * w + x) * 4 + 3) as usize];
+        assert_eq!(alpha(0, 5), 255);
+        assert_eq!(alpha(11, 5), 255);
+        // Anti-aliased edge row keeps its partial coverage
+        assert_eq!(alpha(0, 6), 128);
+        // Strokes far from an edge are left alone
+        assert_eq!(alpha(6, 0), 0);
+        assert_eq!(alpha(0, 3), 0);
+    }
+
+    #[test]
+    fn test_block_elements_fill_exact_fractions() {
+        let size = UVec2::new(4, 10);
+        let draw = |ch| {
+            let mut pixels = vec![0u8; (size.x * size.y * 4) as usize];
+            assert!(draw_block_element(&mut pixels, size.x, UVec2::ZERO, size, ch));
+            move |x: u32, y: u32| pixels[((y * size.x + x) * 4 + 3) as usize]
+        };
+
+        let lower_half = draw('▄');
+        assert_eq!((lower_half(0, 4), lower_half(3, 5)), (0, 255));
+
+        // 1/8 of 10 rows = 1.25: last row solid, the one above a quarter
+        let lower_eighth = draw('▁');
+        assert_eq!((lower_eighth(0, 9), lower_eighth(0, 8), lower_eighth(0, 7)), (255, 64, 0));
+
+        let medium_shade = draw('▒');
+        assert_eq!((medium_shade(0, 0), medium_shade(3, 9)), (128, 128));
+
+        let quadrants = draw('▚');
+        assert_eq!((quadrants(0, 0), quadrants(3, 0), quadrants(3, 9)), (255, 0, 255));
+
+        let mut pixels = [0u8; 4];
+        assert!(!draw_block_element(&mut pixels, 1, UVec2::ZERO, UVec2::ONE, '│'));
+    }
+
+    #[test]
+    fn test_is_box_drawing_skips_shades() {
+        assert!(is_box_drawing('│'));
+        assert!(is_box_drawing('┼'));
+        assert!(is_box_drawing('▀'));
+        assert!(!is_box_drawing('░'));
+        assert!(!is_box_drawing('A'));
+    }
+
+    #[test]
+    fn test_color_image_keeps_colors_and_fits_tile() {
+        // Half-covered premultiplied red comes back as full red at half alpha
+        let straight = unpremultiply_bgra(&[0, 0, 128, 128, 9, 9, 9, 0]);
+        assert_eq!(straight, [255, 0, 0, 128, 0, 0, 0, 0]);
+
+        // A 4x2 image halves into a 2x1 strip centered in a 2x3 tile. Red
+        // next to a transparent texel stays red, with the coverage averaged
+        let (red, clear, blue) = ([255, 0, 0, 255], [0; 4], [0, 0, 255, 255]);
+        let image: Vec<u8> = [red, clear, blue, blue, red, clear, blue, blue].concat();
+        let blit = |pixels: &mut [u8], size| {
+            blit_color_image(pixels, 2, UVec2::ZERO, UVec2::new(2, 3), size, &image)
+        };
+        let mut pixels = vec![0u8; 2 * 3 * 4];
+        assert!(blit(&mut pixels, UVec2::new(4, 2)));
+        assert_eq!(pixels[8..16], [255, 0, 0, 127, 0, 0, 255, 255]);
+        assert!(pixels[..8].iter().chain(&pixels[16..]).all(|&b| b == 0));
+        // Too little data for the claimed size
+        assert!(!blit(&mut pixels, UVec2::new(4, 4)));
+
+        // Outline-only fonts have no color tiles even when asked for them
+        let font = GlyphFont::Outline(FontSource::Default.bytes());
+        let options = GlyphOptions {
+            color_glyphs: true,
+            ..default()
+        };
+        let rasterizer = GlyphRasterizer::new(font, 20.0, font.cell_size(20.0), options);
+        let width = rasterizer.stride.x * ATLAS_COLS;
+        let mut pixels = vec![0u8; (width * rasterizer.stride.y * 4) as usize];
+        assert!(!rasterizer.draw(&mut pixels, width, 'A', 0));
+        assert!(pixels.iter().skip(3).step_by(4).any(|&a| a > 0));
+    }
+
+    #[test]
+    fn test_small_padding_keeps_overflow_in_tile() {
+        // '_' and 'j' reach below the baseline; with a one-texel gap nothing
+        // may land in the next tile's columns
+        let font = GlyphFont::Outline(FontSource::Default.bytes());
+        let options = GlyphOptions {
+            padding: AtlasPadding::Pixels(1),
+            ..default()
+        };
+        let rasterizer = GlyphRasterizer::new(font, 20.0, font.cell_size(20.0), options);
+        let width = rasterizer.stride.x * ATLAS_COLS;
+        let mut pixels = vec![0u8; (width * rasterizer.stride.y * 4) as usize];
+        for (i, ch) in ['W', 'j', '_', '@'].into_iter().enumerate() {
+            rasterizer.draw(&mut pixels, width, ch, i * 2);
+        }
+        for y in 0..rasterizer.stride.y {
+            for tile in [1u32, 3, 5, 7] {
+                let x0 = tile * rasterizer.stride.x;
+                for x in x0..x0 + rasterizer.stride.x {
+                    assert_eq!(pixels[((y * width + x) * 4 + 3) as usize], 0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_atlas_grid_fits_max_dimension() {
+        // Default shape when everything fits
+        assert_eq!(atlas_grid(95, UVec2::new(20, 40), 8192), (16, 204));
+        // Tiles too wide for 16 across
+        assert_eq!(atlas_grid(95, UVec2::new(200, 300), 1024), (5, 3));
+        // Too many glyphs for 16 columns: widen, but never past the limit
+        assert_eq!(atlas_grid(1000, UVec2::new(10, 100), 1000), (100, 10));
+        assert_eq!(atlas_grid(5000, UVec2::new(10, 100), 1000), (100, 10));
+    }
+
+    #[test]
+    fn test_atlas_respects_max_dimension() {
+        let chars: Vec<char> = ('!'..='~').collect();
+        let data = build_atlas_data_for_chars(
+            GlyphFont::Outline(FontSource::Default.bytes()),
+            64.0,
+            &chars,
+            AtlasFilter::Linear,
+            GlyphOptions::default(),
+            512,
+        );
+        assert!(data.image.width() <= 512 && data.image.height() <= 512);
+        assert!(data.glyph_count < chars.len());
+        assert_eq!(data.glyph_map.len(), data.glyph_count);
+    }
+
+    #[test]
+    fn test_missing_glyph_log_throttles_warnings() {
+        struct Term;
+
+        let mut log = MissingGlyphLog::<Term>::default();
+        assert_eq!(log.take_due(0.0), None);
+
+        log.record('漢');
+        log.record('字');
+        assert_eq!(log.take_due(1.0), Some(vec!['字', '漢']));
+
+        // Repeats aren't reported again; new chars wait out the interval
+        log.record('漢');
+        log.record('語');
+        assert_eq!(log.take_due(3.0), None);
+        assert_eq!(log.take_due(6.0), Some(vec!['語']));
+        assert_eq!(log.chars.len(), 3);
+    }
+
+    #[test]
+    fn test_missing_glyphs_are_collected_when_enabled() {
+        use ratatui::style::Style;
+
+        use crate::testing::TerminalTestHarness;
+        use crate::TerminalEmuPlugin;
+
+        struct Term;
+
+        let plugin = TerminalEmuPlugin::<Term>::new().columns(4).rows(1);
+        let mut harness = TerminalTestHarness::new(plugin);
+        let draw = |harness: &mut TerminalTestHarness<Term>, text: &str| {
+            harness.draw(|frame| frame.buffer_mut().set_string(0, 0, text, Style::default()));
+            harness.update();
+        };
+
+        draw(&mut harness, "漢é");
+        let log = harness.app.world().resource::<MissingGlyphLog<Term>>();
+        assert!(log.chars.is_empty());
+
+        let world = harness.app.world_mut();
+        world.resource_mut::<MissingGlyphLog<Term>>().enabled = true;
+        draw(&mut harness, "字é");
+        let log = harness.app.world().resource::<MissingGlyphLog<Term>>();
+        assert_eq!(log.chars.iter().collect::<String>(), "字");
+    }
+
+    #[test]
+    fn test_preload_skips_known_and_unrenderable_chars() {
+        let font = GlyphFont::Outline(FontSource::Default.bytes());
+        let chars = with_preloaded(
+            vec!['a', 'b'],
+            &['b', 'é', '漢', 'é'],
+            font,
+            16.0,
+            GlyphOptions::default(),
+        );
+        assert_eq!(chars, vec!['a', 'b', 'é']);
+    }
 }
@@ -0,0 +1,316 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hasher;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use bevy::prelude::*;
+
+/// Identifies cache files written by this crate.
+const MAGIC: &[u8; 4] = b"BTEA";
+/// Bumped whenever the file layout changes, so old files are ignored.
+const VERSION: u32 = 2;
+/// Files kept in the cache directory; older ones are removed on save.
+const MAX_ENTRIES: usize = 8;
+
+/// 64-bit FNV-1a, for cache keys that must come out the same on every run,
+/// platform and Rust release (unlike `DefaultHasher`). Integers are fed in
+/// little-endian and `usize` as 64 bits.
+pub(crate) struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn write_u16(&mut self, value: u16) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// A rasterized atlas as stored on disk: RGBA pixels plus the tile grid and
+/// glyph placement needed to use them.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct CachedAtlas {
+    pub size: UVec2,
+    pub cell_size: UVec2,
+    pub columns: u32,
+    pub glyph_map: HashMap<char, usize>,
+    pub color_glyphs: HashSet<char>,
+    pub pixels: Vec<u8>,
+}
+
+impl CachedAtlas {
+    /// Serialize as little-endian header fields, glyph entries, then pixels.
+    fn encode(&self, key: u64) -> Vec<u8> {
+        let mut out = Vec::with_capacity(48 + self.glyph_map.len() * 12 + self.pixels.len());
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&VERSION.to_le_bytes());
+        out.extend_from_slice(&key.to_le_bytes());
+        for value in [
+            self.size.x,
+            self.size.y,
+            self.cell_size.x,
+            self.cell_size.y,
+            self.columns,
+            self.glyph_map.len() as u32,
+        ] {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+        let mut glyphs: Vec<_> = self.glyph_map.iter().collect();
+        glyphs.sort_by_key(|(_, index)| **index);
+        for (&ch, &index) in glyphs {
+            out.extend_from_slice(&(ch as u32).to_le_bytes());
+            out.extend_from_slice(&(index as u32).to_le_bytes());
+            let color = self.color_glyphs.contains(&ch) as u32;
+            out.extend_from_slice(&color.to_le_bytes());
+        }
+        out.extend_from_slice(&self.pixels);
+        out
+    }
+
+    /// Parse a file written by `encode` for `key`. Returns `None` for other
+    /// keys, other versions, and truncated or inconsistent data.
+    fn decode(bytes: &[u8], key: u64) -> Option<Self> {
+        let mut reader = Reader(bytes);
+        if reader.take(4)? != MAGIC || reader.u32()? != VERSION || reader.u64()? != key {
+            return None;
+        }
+        let size = UVec2::new(reader.u32()?, reader.u32()?);
+        let cell_size = UVec2::new(reader.u32()?, reader.u32()?);
+        let columns = reader.u32()?;
+        let glyphs = reader.u32()? as usize;
+        if columns == 0 || cell_size.cmpeq(UVec2::ZERO).any() {
+            return None;
+        }
+        // The tile grid has to fit the image, padding aside
+        if columns.checked_mul(cell_size.x)? > size.x {
+            return None;
+        }
+
+        let tiles = columns as usize * (size.y / cell_size.y) as usize;
+        let mut glyph_map = HashMap::with_capacity(glyphs.min(tiles));
+        let mut color_glyphs = HashSet::new();
+        for _ in 0..glyphs {
+            let ch = char::from_u32(reader.u32()?)?;
+            let index = reader.u32()? as usize;
+            let color = reader.u32()?;
+            if index >= tiles || color > 1 {
+                return None;
+            }
+            glyph_map.insert(ch, index);
+            if color == 1 {
+                color_glyphs.insert(ch);
+            }
+        }
+
+        let texels = (size.x as usize).checked_mul(size.y as usize)?;
+        let pixels = reader.take(texels.checked_mul(4)?)?.to_vec();
+        if !reader.0.is_empty() {
+            return None;
+        }
+        Some(Self {
+            size,
+            cell_size,
+            columns,
+            glyph_map,
+            color_glyphs,
+            pixels,
+        })
+    }
+}
+
+/// Cursor over the bytes of a cache file.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Some(head)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_le_bytes(self.take(4)?.try_into().ok()?))
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        Some(u64::from_le_bytes(self.take(8)?.try_into().ok()?))
+    }
+}
+
+fn cache_path(dir: &Path, key: u64) -> PathBuf {
+    dir.join(format!("atlas-{key:016x}.bin"))
+}
+
+/// Load the atlas cached under `key` in `dir`, if there is a valid one.
+pub(crate) fn load(dir: &Path, key: u64) -> Option<CachedAtlas> {
+    let path = cache_path(dir, key);
+    let bytes = std::fs::read(&path).ok()?;
+    let cached = CachedAtlas::decode(&bytes, key);
+    if cached.is_none() {
+        warn!("Ignoring invalid font atlas cache file {}", path.display());
+    } else if let Ok(file) = std::fs::File::options().append(true).open(&path) {
+        // Mark it recently used so pruning keeps it
+        let _ = file.set_modified(SystemTime::now());
+    }
+    cached
+}
+
+/// Write `atlas` to `dir` under `key`. Failures are logged, not fatal.
+pub(crate) fn save(dir: &Path, key: u64, atlas: &CachedAtlas) {
+    let path = cache_path(dir, key);
+    let result = std::fs::create_dir_all(dir)
+        .and_then(|()| std::fs::write(&path, atlas.encode(key)));
+    if let Err(err) = result {
+        warn!("Failed to write font atlas cache {}: {err}", path.display());
+    }
+    prune(dir);
+}
+
+/// Remove all but the `MAX_ENTRIES` most recently used cache files, such as
+/// ones left behind by an earlier font or size.
+fn prune(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut files: Vec<(SystemTime, PathBuf)> = entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let name = entry.file_name();
+            let name = name.to_str()?;
+            if !name.starts_with("atlas-") || !name.ends_with(".bin") {
+                return None;
+            }
+            Some((entry.metadata().ok()?.modified().ok()?, entry.path()))
+        })
+        .collect();
+    if files.len() <= MAX_ENTRIES {
+        return;
+    }
+    files.sort_by(|a, b| b.0.cmp(&a.0));
+    for (_, path) in files.split_off(MAX_ENTRIES) {
+        if let Err(err) = std::fs::remove_file(&path) {
+            let path = path.display();
+            warn!("Failed to remove stale font atlas cache {path}: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> CachedAtlas {
+        CachedAtlas {
+            size: UVec2::new(4, 6),
+            cell_size: UVec2::new(2, 3),
+            columns: 2,
+            glyph_map: HashMap::from([('a', 0), ('é', 1), ('Z', 3)]),
+            color_glyphs: HashSet::from(['é']),
+            pixels: (0..4 * 6 * 4).map(|i| i as u8).collect(),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let atlas = sample();
+        assert_eq!(CachedAtlas::decode(&atlas.encode(7), 7), Some(atlas));
+    }
+
+    #[test]
+    fn test_rejects_other_key_and_truncation() {
+        let bytes = sample().encode(7);
+        assert_eq!(CachedAtlas::decode(&bytes, 8), None);
+        assert_eq!(CachedAtlas::decode(&bytes[..bytes.len() - 1], 7), None);
+    }
+
+    #[test]
+    fn test_rejects_impossible_sizes() {
+        let too_wide = CachedAtlas {
+            columns: 3,
+            ..sample()
+        };
+        assert_eq!(CachedAtlas::decode(&too_wide.encode(7), 7), None);
+        // Pixel byte count overflows usize
+        let huge = CachedAtlas {
+            size: UVec2::splat(u32::MAX),
+            ..sample()
+        };
+        assert_eq!(CachedAtlas::decode(&huge.encode(7), 7), None);
+    }
+
+    #[test]
+    fn test_save_then_load() {
+        let dir = std::env::temp_dir().join(format!("bte-atlas-cache-{}", std::process::id()));
+        let atlas = sample();
+        save(&dir, 42, &atlas);
+        assert_eq!(load(&dir, 42), Some(atlas));
+        assert_eq!(load(&dir, 43), None);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_fnv1a_reference_values() {
+        let hash = |bytes: &[u8]| {
+            let mut hasher = Fnv1a::default();
+            hasher.write(bytes);
+            hasher.finish()
+        };
+        assert_eq!(hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(hash(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn test_save_prunes_least_recently_used() {
+        let dir = std::env::temp_dir().join(format!("bte-atlas-prune-{}", std::process::id()));
+        let atlas = sample();
+        let age = |key: u64, secs: u64| {
+            let path = cache_path(&dir, key);
+            let file = std::fs::File::options().append(true).open(path).unwrap();
+            let time = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs);
+            file.set_modified(time).unwrap();
+        };
+        for key in 0..MAX_ENTRIES as u64 {
+            save(&dir, key, &atlas);
+            age(key, 1000 + key);
+        }
+        // Loading the oldest entry makes it the newest
+        assert!(load(&dir, 0).is_some());
+
+        save(&dir, 100, &atlas);
+        assert!(load(&dir, 1).is_none());
+        for key in [0, 2, 100] {
+            assert!(load(&dir, key).is_some());
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,8 +1,13 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
 use std::convert::Infallible;
 
 use ratatui::backend::{Backend, ClearType, WindowSize};
-use ratatui::buffer::Cell;
-use ratatui::layout::{Position, Size};
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::layout::{Position, Rect, Size};
+use ratatui::style::Style;
+use ratatui::text::Line;
+use unicode_width::UnicodeWidthChar;
 
 /// In-memory terminal backend for Bevy integration.
 ///
@@ -18,6 +23,22 @@ pub struct BevyBackend {
     flush_generation: u64,
     /// Per-cell dirty flags set by draw()/clear() and consumed by sync.
     dirty_cells: Vec<bool>,
+    /// Rows scrolled off the top of the screen, oldest first.
+    scrollback: VecDeque<Vec<Cell>>,
+    /// Maximum rows kept in `scrollback` (0 disables scrollback).
+    scrollback_limit: usize,
+    /// How many rows the visible window is scrolled up into the scrollback.
+    view_offset: usize,
+    /// Rows pushed into scrollback since the last `take_scrolled_lines()`.
+    scrolled_lines: usize,
+    /// Whether any cell was written since the last `take_written()`. A
+    /// ratatui redraw with nothing changed doesn't count.
+    written: bool,
+    /// Pixel size of one cell, reported through `window_size()`.
+    cell_width: u16,
+    cell_height: u16,
+    /// Columns between tab stops for `print` (0 = write tabs as-is).
+    tab_width: u8,
 }
 
 impl BevyBackend {
@@ -32,9 +53,147 @@ impl BevyBackend {
             cursor_visible: false,
             flush_generation: 0,
             dirty_cells: vec![true; size], // all dirty initially so first sync populates everything
+            scrollback: VecDeque::new(),
+            scrollback_limit: 0,
+            view_offset: 0,
+            scrolled_lines: 0,
+            written: false,
+            cell_width: 0,
+            cell_height: 0,
+            tab_width: 8,
         }
     }
 
+    /// Create a new backend that reports `cell_w` x `cell_h` pixel cells
+    /// from `window_size()`.
+    pub fn with_cell_size(width: u16, height: u16, cell_w: u16, cell_h: u16) -> Self {
+        let mut backend = Self::new(width, height);
+        backend.set_cell_size(cell_w, cell_h);
+        backend
+    }
+
+    /// Pixel size of one cell as reported by `window_size()`.
+    pub fn cell_size(&self) -> (u16, u16) {
+        (self.cell_width, self.cell_height)
+    }
+
+    /// Update the pixel size of one cell (e.g. after the layout changes).
+    pub fn set_cell_size(&mut self, cell_w: u16, cell_h: u16) {
+        self.cell_width = cell_w;
+        self.cell_height = cell_h;
+    }
+
+    /// Keep up to `lines` rows that scroll off the top of the screen.
+    pub fn with_scrollback(mut self, lines: usize) -> Self {
+        self.scrollback_limit = lines;
+        self
+    }
+
+    /// Expand tabs written by `print` to stops every `width` columns.
+    pub fn with_tab_width(mut self, width: u8) -> Self {
+        self.tab_width = width;
+        self
+    }
+
+    /// Columns between tab stops used by `print` (default: 8).
+    pub fn tab_width(&self) -> u8 {
+        self.tab_width
+    }
+
+    /// Change the tab stop spacing for later `print` calls; 0 writes tabs
+    /// into a single cell like any other char.
+    pub fn set_tab_width(&mut self, width: u8) {
+        self.tab_width = width;
+    }
+
+    /// Number of rows currently held in the scrollback buffer.
+    pub fn scrollback_len(&self) -> usize {
+        self.scrollback.len()
+    }
+
+    /// How many rows the visible window is scrolled up (0 = live screen).
+    pub fn view_offset(&self) -> usize {
+        self.view_offset
+    }
+
+    /// Scroll the visible window `offset` rows up into the scrollback
+    /// (clamped to its length). Repaints the whole grid when it changes.
+    pub fn set_view_offset(&mut self, offset: usize) {
+        let offset = offset.min(self.scrollback.len());
+        if offset != self.view_offset {
+            self.view_offset = offset;
+            self.mark_all_dirty();
+        }
+    }
+
+    /// Cell shown at flat index `idx` of the visible window, taking the
+    /// scrollback offset into account.
+    pub fn view_cell(&self, idx: usize) -> &Cell {
+        if self.view_offset == 0 {
+            return &self.buffer[idx];
+        }
+        let width = self.width as usize;
+        let (row, col) = (idx / width, idx % width);
+        let line = self.scrollback.len() - self.view_offset + row;
+        match self.scrollback.get(line) {
+            Some(cells) => &cells[col],
+            None => &self.buffer[(line - self.scrollback.len()) * width + col],
+        }
+    }
+
+    /// Append `lines` to the scrollback as if they had scrolled off the top
+    /// of the screen, leaving the live screen alone. ratatui's fullscreen
+    /// `Terminal::draw()` repaints in place and never scrolls, so this is how
+    /// a log fills history while the app draws the rest of the screen. Lines
+    /// are cut to the grid width. Does nothing with scrollback disabled.
+    pub fn push_lines<'a>(&mut self, lines: impl IntoIterator<Item = Line<'a>>) {
+        let area = Rect::new(0, 0, self.width, 1);
+        for line in lines {
+            let mut row = Buffer::empty(area);
+            row.set_line(0, 0, &line, self.width);
+            self.push_scrollback(row.content);
+            self.written = true;
+        }
+    }
+
+    /// Return and reset the number of rows pushed into scrollback.
+    pub fn take_scrolled_lines(&mut self) -> usize {
+        std::mem::take(&mut self.scrolled_lines)
+    }
+
+    /// Return and reset whether anything was drawn.
+    pub fn take_written(&mut self) -> bool {
+        std::mem::take(&mut self.written)
+    }
+
+    /// Append a row to the scrollback, dropping the oldest past the limit.
+    fn push_scrollback(&mut self, row: Vec<Cell>) {
+        if self.scrollback_limit == 0 {
+            return;
+        }
+        if self.scrollback.len() == self.scrollback_limit {
+            self.scrollback.pop_front();
+        }
+        self.scrollback.push_back(row);
+        self.scrolled_lines += 1;
+    }
+
+    /// Copy live row `src` over live row `dst`.
+    fn copy_row(&mut self, src: u16, dst: u16) {
+        let width = self.width as usize;
+        let src = src as usize * width;
+        let dst = dst as usize * width;
+        let row = self.buffer[src..src + width].to_vec();
+        self.buffer[dst..dst + width].clone_from_slice(&row);
+    }
+
+    /// Row `row` of the live buffer as a cell slice.
+    fn row_cells(&self, row: u16) -> &[Cell] {
+        let width = self.width as usize;
+        let start = row as usize * width;
+        &self.buffer[start..start + width]
+    }
+
     /// Get the current flush generation counter.
     pub fn generation(&self) -> u64 {
         self.flush_generation
@@ -54,6 +213,16 @@ impl BevyBackend {
         }
     }
 
+    /// Cursor position as last set by ratatui.
+    pub fn cursor_position(&self) -> Position {
+        self.cursor
+    }
+
+    /// Whether ratatui has the cursor shown.
+    pub fn cursor_visible(&self) -> bool {
+        self.cursor_visible
+    }
+
     /// Returns per-cell dirty flags (true = modified since last `clear_dirty()`).
     pub fn dirty_cells(&self) -> &[bool] {
         &self.dirty_cells
@@ -71,6 +240,186 @@ impl BevyBackend {
         self.dirty_cells.fill(true);
         self.flush_generation += 1;
     }
+
+    /// Resize the grid, discarding its contents. All cells start dirty so the
+    /// next sync repaints everything; ratatui's `Terminal` picks up the new
+    /// size on its next `draw()`.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        let size = width as usize * height as usize;
+        self.width = width;
+        self.height = height;
+        self.buffer = vec![Cell::default(); size];
+        self.dirty_cells = vec![true; size];
+        self.cursor.x = self.cursor.x.min(width.saturating_sub(1));
+        self.cursor.y = self.cursor.y.min(height.saturating_sub(1));
+        // Old rows no longer match the new width
+        self.scrollback.clear();
+        self.view_offset = 0;
+        self.flush_generation += 1;
+    }
+
+    /// Mark only the cells matching `predicate` as dirty (e.g. cells showing a
+    /// glyph that was just added to the atlas). Bumps the generation if any
+    /// cell matched.
+    pub fn mark_dirty_where(&mut self, mut predicate: impl FnMut(&Cell) -> bool) {
+        let mut any = false;
+        for (cell, dirty) in self.buffer.iter().zip(self.dirty_cells.iter_mut()) {
+            if predicate(cell) {
+                *dirty = true;
+                any = true;
+            }
+        }
+        if any {
+            self.flush_generation += 1;
+        }
+    }
+
+    /// Write `text` with `style` starting at (col, row), one char per cell
+    /// (two for wide chars such as CJK, as ratatui lays them out), without
+    /// going through a ratatui `Frame`. `\n` continues at `col` on the next
+    /// row; anything past the grid edges is dropped. Returns the number of
+    /// rows written to.
+    ///
+    /// `\t` advances to the next multiple of `tab_width` columns from the
+    /// grid's left edge, filling the skipped cells with spaces in `style`,
+    /// so piped program output lines up the way it would in a terminal.
+    ///
+    /// ratatui only redraws cells that changed in its own buffer, so a later
+    /// `Terminal::draw()` won't necessarily cover printed text. Mixing both on
+    /// the same cells needs a `Terminal::clear()` first.
+    pub fn print(&mut self, col: u16, row: u16, text: &str, style: Style) -> u16 {
+        let text = expand_tabs(text, col, self.tab_width);
+        let lines: Vec<&str> = text.split('\n').collect();
+        self.print_lines(col, row, &lines, style)
+    }
+
+    /// Like `print`, but word-wraps `text` to `width` columns, breaking
+    /// words longer than a line. Tabs are expanded on each wrapped line, so
+    /// their stops still line up with the grid.
+    pub fn print_wrapped(
+        &mut self,
+        col: u16,
+        row: u16,
+        text: &str,
+        style: Style,
+        width: u16,
+    ) -> u16 {
+        let lines: Vec<String> = wrap_lines(text, col, width, self.tab_width)
+            .iter()
+            .map(|line| expand_tabs(line, col, self.tab_width).into_owned())
+            .collect();
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        self.print_lines(col, row, &lines, style)
+    }
+
+    fn print_lines(&mut self, col: u16, row: u16, lines: &[&str], style: Style) -> u16 {
+        let mut rows = 0;
+        for (y, line) in lines.iter().enumerate() {
+            let y = row as usize + y;
+            if y >= self.height as usize {
+                break;
+            }
+            rows += 1;
+            let mut x = col as usize;
+            for ch in line.chars() {
+                let width = cell_width(ch);
+                if x + width > self.width as usize {
+                    break;
+                }
+                let idx = y * self.width as usize + x;
+                self.buffer[idx].set_char(ch).set_style(style);
+                // Cells a wide char covers are reset, as ratatui does
+                for cell in &mut self.buffer[idx + 1..idx + width] {
+                    cell.reset();
+                }
+                self.dirty_cells[idx..idx + width].fill(true);
+                x += width;
+            }
+        }
+        if rows > 0 {
+            self.written = true;
+            self.flush_generation += 1;
+        }
+        rows
+    }
+}
+
+/// Cells `print` gives `ch`: its display width, but at least one, so
+/// combining marks and control chars still get a cell of their own.
+fn cell_width(ch: char) -> usize {
+    ch.width().unwrap_or(1).max(1)
+}
+
+/// Column after `ch` is placed at column `x`: the next multiple of
+/// `tab_width` for a tab, otherwise past its cells.
+fn advance(x: usize, ch: char, tab_width: u8) -> usize {
+    match (ch, tab_width as usize) {
+        ('\t', tab_width @ 1..) => (x / tab_width + 1) * tab_width,
+        _ => x + cell_width(ch),
+    }
+}
+
+/// Replace each tab in `text` with spaces up to the next multiple of
+/// `tab_width` columns, counting from column 0 for a line starting at `col`.
+/// Leaves `text` alone when `tab_width` is 0.
+pub(crate) fn expand_tabs(text: &str, col: u16, tab_width: u8) -> Cow<'_, str> {
+    if tab_width == 0 || !text.contains('\t') {
+        return Cow::Borrowed(text);
+    }
+    let mut expanded = String::with_capacity(text.len());
+    let mut x = col as usize;
+    for ch in text.chars() {
+        let next = advance(x, ch, tab_width);
+        match ch {
+            '\t' => expanded.extend(std::iter::repeat_n(' ', next - x)),
+            '\n' => {
+                expanded.push(ch);
+                x = col as usize;
+                continue;
+            }
+            _ => expanded.push(ch),
+        }
+        x = next;
+    }
+    Cow::Owned(expanded)
+}
+
+/// Split `text` into lines at most `width` columns wide, breaking at spaces
+/// where possible and keeping explicit newlines. Columns are counted the way
+/// `print` places chars on a line starting at `col`, with tabs reaching
+/// their `tab_width` stop, so lines still fit once `expand_tabs` runs.
+pub(crate) fn wrap_lines(text: &str, col: u16, width: u16, tab_width: u8) -> Vec<String> {
+    let start = col as usize;
+    let end = start + width.max(1) as usize;
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        let mut x = start;
+        for word in paragraph.split(' ') {
+            if !line.is_empty() {
+                let after = word.chars().fold(x + 1, |x, ch| advance(x, ch, tab_width));
+                if after > end {
+                    lines.push(std::mem::take(&mut line));
+                    x = start;
+                } else {
+                    line.push(' ');
+                    x += 1;
+                }
+            }
+            // Hard-break words that don't fit on a line of their own
+            for ch in word.chars() {
+                let mut next = advance(x, ch, tab_width);
+                if next > end && !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                    next = advance(start, ch, tab_width);
+                }
+                line.push(ch);
+                x = next;
+            }
+        }
+        lines.push(line);
+    }
+    lines
 }
 
 impl Backend for BevyBackend {
@@ -85,6 +434,19 @@ impl Backend for BevyBackend {
                 let idx = y as usize * self.width as usize + x as usize;
                 self.buffer[idx] = cell.clone();
                 self.dirty_cells[idx] = true;
+                self.written = true;
+            }
+        }
+        Ok(())
+    }
+
+    fn append_lines(&mut self, n: u16) -> Result<(), Self::Error> {
+        // Like a newline on a real terminal: move down, scrolling at the bottom
+        for _ in 0..n {
+            if self.cursor.y + 1 >= self.height {
+                self.scroll_region_up(0..self.height, 1)?;
+            } else {
+                self.cursor.y += 1;
             }
         }
         Ok(())
@@ -176,8 +538,8 @@ impl Backend for BevyBackend {
                 height: self.height,
             },
             pixels: Size {
-                width: 0,
-                height: 0,
+                width: self.width.saturating_mul(self.cell_width),
+                height: self.height.saturating_mul(self.cell_height),
             },
         })
     }
@@ -186,6 +548,67 @@ impl Backend for BevyBackend {
         self.flush_generation += 1;
         Ok(())
     }
+
+    fn scroll_region_up(
+        &mut self,
+        region: std::ops::Range<u16>,
+        line_count: u16,
+    ) -> Result<(), Self::Error> {
+        let region = region.start..region.end.min(self.height);
+        if region.is_empty() {
+            return Ok(());
+        }
+        let region_len = region.end - region.start;
+
+        // Rows scrolled off the top of the screen go into scrollback
+        if region.start == 0 {
+            for i in 0..line_count {
+                let row = if i < region_len {
+                    self.row_cells(i).to_vec()
+                } else {
+                    vec![Cell::default(); self.width as usize]
+                };
+                self.push_scrollback(row);
+            }
+        }
+
+        let width = self.width as usize;
+        for row in region.clone() {
+            let dst = row as usize * width;
+            let src_row = row + line_count;
+            if src_row < region.end {
+                self.copy_row(src_row, row);
+            } else {
+                self.buffer[dst..dst + width].fill(Cell::default());
+            }
+            self.dirty_cells[dst..dst + width].fill(true);
+        }
+        self.written = true;
+        Ok(())
+    }
+
+    fn scroll_region_down(
+        &mut self,
+        region: std::ops::Range<u16>,
+        line_count: u16,
+    ) -> Result<(), Self::Error> {
+        let region = region.start..region.end.min(self.height);
+        if region.is_empty() {
+            return Ok(());
+        }
+
+        let width = self.width as usize;
+        for row in region.clone().rev() {
+            let dst = row as usize * width;
+            match row.checked_sub(line_count) {
+                Some(src_row) if src_row >= region.start => self.copy_row(src_row, row),
+                _ => self.buffer[dst..dst + width].fill(Cell::default()),
+            }
+            self.dirty_cells[dst..dst + width].fill(true);
+        }
+        self.written = true;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -199,6 +622,17 @@ mod tests {
         assert_eq!(backend.size().unwrap(), Size { width: 80, height: 24 });
     }
 
+    #[test]
+    fn test_window_size_pixels() {
+        let mut backend = BevyBackend::with_cell_size(80, 24, 10, 20);
+        let size = backend.window_size().unwrap();
+        assert_eq!(size.pixels, Size { width: 800, height: 480 });
+
+        backend.set_cell_size(8, 16);
+        let size = backend.window_size().unwrap();
+        assert_eq!(size.pixels, Size { width: 640, height: 384 });
+    }
+
     #[test]
     fn test_backend_draw_and_flush() {
         let mut backend = BevyBackend::new(10, 10);
@@ -233,6 +667,149 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn test_backend_resize() {
+        let backend = BevyBackend::new(10, 5);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal.backend_mut().resize(20, 8);
+        assert_eq!(terminal.backend().buffer().len(), 160);
+        assert!(terminal.backend().dirty_cells().iter().all(|&d| d));
+        terminal
+            .draw(|frame| {
+                assert_eq!(frame.area().width, 20);
+                assert_eq!(frame.area().height, 8);
+            })
+            .unwrap();
+    }
+
+    fn row_text(backend: &BevyBackend, row: u16) -> String {
+        (0..backend.width)
+            .map(|col| backend.cell(col, row).unwrap().symbol().to_string())
+            .collect()
+    }
+
+    fn fill_rows(backend: &mut BevyBackend, rows: &[&str]) {
+        for (y, text) in rows.iter().enumerate() {
+            for (x, ch) in text.chars().enumerate() {
+                let mut cell = Cell::default();
+                cell.set_char(ch);
+                backend.draw(std::iter::once((x as u16, y as u16, &cell))).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_scroll_region_up_fills_scrollback() {
+        let mut backend = BevyBackend::new(4, 3).with_scrollback(10);
+        fill_rows(&mut backend, &["aaaa", "bbbb", "cccc"]);
+
+        backend.scroll_region_up(0..3, 1).unwrap();
+        assert_eq!(row_text(&backend, 0), "bbbb");
+        assert_eq!(row_text(&backend, 1), "cccc");
+        assert_eq!(row_text(&backend, 2), "    ");
+        assert_eq!(backend.scrollback_len(), 1);
+        assert_eq!(backend.take_scrolled_lines(), 1);
+
+        // Scrolled up one row, the window shows the pushed-out row on top
+        backend.set_view_offset(1);
+        assert_eq!(backend.view_cell(0).symbol(), "a");
+        assert_eq!(backend.view_cell(4).symbol(), "b");
+        assert_eq!(backend.view_cell(8).symbol(), "c");
+    }
+
+    #[test]
+    fn test_scrollback_limit_and_offset_clamp() {
+        let mut backend = BevyBackend::new(4, 2).with_scrollback(2);
+        for _ in 0..5 {
+            backend.append_lines(1).unwrap();
+        }
+        // Cursor reaches the bottom after one line, then 4 rows scroll off
+        assert_eq!(backend.scrollback_len(), 2);
+        backend.set_view_offset(10);
+        assert_eq!(backend.view_offset(), 2);
+    }
+
+    #[test]
+    fn test_scroll_region_down() {
+        let mut backend = BevyBackend::new(4, 3);
+        fill_rows(&mut backend, &["aaaa", "bbbb", "cccc"]);
+        backend.scroll_region_down(1..3, 1).unwrap();
+        assert_eq!(row_text(&backend, 0), "aaaa");
+        assert_eq!(row_text(&backend, 1), "    ");
+        assert_eq!(row_text(&backend, 2), "bbbb");
+    }
+
+    #[test]
+    fn test_mark_dirty_where() {
+        let mut backend = BevyBackend::new(4, 1);
+        let mut cell = Cell::default();
+        cell.set_symbol("x");
+        backend.draw(vec![(2, 0, &cell)].into_iter()).unwrap();
+        backend.clear_dirty();
+        let generation = backend.generation();
+
+        backend.mark_dirty_where(|c| c.symbol() == "x");
+        assert_eq!(backend.dirty_cells(), &[false, false, true, false]);
+        assert_eq!(backend.generation(), generation + 1);
+
+        // No match leaves the generation untouched
+        backend.mark_dirty_where(|c| c.symbol() == "y");
+        assert_eq!(backend.generation(), generation + 1);
+    }
+
+    #[test]
+    fn test_wrap_lines() {
+        let wrap = |text, width| wrap_lines(text, 0, width, 4);
+        assert_eq!(wrap("the quick brown fox", 10), ["the quick", "brown fox"]);
+        assert_eq!(wrap("a\n\nb", 10), ["a", "", "b"]);
+        assert_eq!(wrap("abcdefgh ij", 3), ["abc", "def", "gh", "ij"]);
+        // Wide chars take two columns, tabs run to their stop
+        assert_eq!(wrap("漢字 ab", 4), ["漢字", "ab"]);
+        assert_eq!(wrap("ab\tc d", 6), ["ab\tc", "d"]);
+    }
+
+    #[test]
+    fn test_print_clips_and_marks_dirty() {
+        let mut backend = BevyBackend::new(4, 2);
+        backend.clear_dirty();
+        let generation = backend.generation();
+
+        let rows = backend.print(1, 0, "abcdef\nxy\nzz", Style::default());
+        assert_eq!(rows, 2);
+        assert_eq!(row_text(&backend, 0), " abc");
+        assert_eq!(row_text(&backend, 1), " xy ");
+        assert_eq!(backend.dirty_cells(), &[false, true, true, true, false, true, true, false]);
+        assert_eq!(backend.generation(), generation + 1);
+
+        let rows = backend.print_wrapped(0, 0, "to be", Style::default(), 3);
+        assert_eq!(rows, 2);
+        assert_eq!(row_text(&backend, 0), "tobc");
+        assert_eq!(row_text(&backend, 1), "bey ");
+    }
+
+    #[test]
+    fn test_print_expands_tabs_to_stops() {
+        let mut backend = BevyBackend::new(12, 2).with_tab_width(4);
+        backend.print(0, 0, "a\tbc\td\nxyz\tw", Style::default());
+        assert_eq!(row_text(&backend, 0), "a   bc  d   ");
+        assert_eq!(row_text(&backend, 1), "xyz w       ");
+
+        // Stops are counted from the grid's edge, not where printing starts
+        assert_eq!(expand_tabs("\tx", 2, 4), "  x");
+        assert_eq!(expand_tabs("a\tb", 0, 0), "a\tb");
+
+        // Wide chars count two columns toward the stop and fill two cells
+        assert_eq!(expand_tabs("漢\tx", 0, 4), "漢  x");
+        let mut backend = BevyBackend::new(6, 2).with_tab_width(4);
+        backend.print(0, 0, "漢\tx", Style::default());
+        assert_eq!(row_text(&backend, 0), "漢   x ");
+
+        // Tabs on a wrapped line stop where they would on the grid
+        backend.print_wrapped(0, 0, "abcdef\tx", Style::default(), 6);
+        assert_eq!(row_text(&backend, 0), "abcdef");
+        assert_eq!(row_text(&backend, 1), "    x ");
+    }
+
     #[test]
     fn test_cursor_operations() {
         let mut backend = BevyBackend::new(80, 24);
@@ -0,0 +1,99 @@
+use bevy::math::UVec2;
+
+/// A fixed-size bitmap font: a sheet of equally sized, pre-rasterized glyph
+/// tiles, for classic terminal fonts that have no outlines.
+///
+/// Sheet format:
+/// - `pixels` is the sheet as row-major RGBA8, `sheet_width` pixels wide.
+/// - Glyphs are `glyph_width` x `glyph_height` tiles packed left-to-right,
+///   top-to-bottom with no spacing, `sheet_width / glyph_width` per row.
+/// - `chars[i]` is the character drawn in tile `i`.
+/// - Each pixel's alpha is its coverage. Color is ignored; glyphs are tinted
+///   by the cell's foreground like outline glyphs.
+///
+/// Glyphs are scaled by a whole factor so they stay crisp: `font_size` equal
+/// to `glyph_height` draws them at native size, `2 * glyph_height` doubles
+/// them, and so on. Pair with `AtlasFilter::Nearest`.
+#[derive(Clone, Debug)]
+pub struct BitmapFont {
+    pub pixels: Vec<u8>,
+    pub sheet_width: u32,
+    pub glyph_width: u32,
+    pub glyph_height: u32,
+    pub chars: Vec<char>,
+}
+
+impl BitmapFont {
+    /// A sheet holding printable ASCII (0x20..=0x7E) in code point order.
+    pub fn ascii(pixels: Vec<u8>, sheet_width: u32, glyph_width: u32, glyph_height: u32) -> Self {
+        Self {
+            pixels,
+            sheet_width,
+            glyph_width,
+            glyph_height,
+            chars: (0x20u8..=0x7E).map(|b| b as char).collect(),
+        }
+    }
+
+    /// Tile index of `ch` in the sheet, if present.
+    pub fn glyph_index(&self, ch: char) -> Option<usize> {
+        self.chars.iter().position(|&c| c == ch)
+    }
+
+    /// Coverage (alpha) of pixel (x, y) within tile `index`; 0 outside the sheet.
+    pub fn coverage(&self, index: usize, x: u32, y: u32) -> u8 {
+        let per_row = (self.sheet_width / self.glyph_width.max(1)).max(1);
+        let sheet_x = (index as u32 % per_row) * self.glyph_width + x;
+        let sheet_y = (index as u32 / per_row) * self.glyph_height + y;
+        let offset = (sheet_y * self.sheet_width + sheet_x) as usize * 4 + 3;
+        self.pixels.get(offset).copied().unwrap_or(0)
+    }
+
+    /// Whole-number scale applied to glyphs rasterized at `font_size` pixels.
+    pub fn scale_for(&self, font_size: f32) -> u32 {
+        (font_size / self.glyph_height.max(1) as f32).round().max(1.0) as u32
+    }
+
+    /// Cell size in pixels when rasterized at `font_size`.
+    pub fn cell_size(&self, font_size: f32) -> UVec2 {
+        UVec2::new(self.glyph_width, self.glyph_height) * self.scale_for(font_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two 2x2 glyphs side by side: 'a' fully covered, 'b' only top-left.
+    fn sheet() -> BitmapFont {
+        let mut pixels = vec![0u8; 4 * 2 * 4];
+        for (x, y) in [(0, 0), (1, 0), (0, 1), (1, 1), (2, 0)] {
+            pixels[(y * 4 + x) * 4 + 3] = 255;
+        }
+        BitmapFont {
+            pixels,
+            sheet_width: 4,
+            glyph_width: 2,
+            glyph_height: 2,
+            chars: vec!['a', 'b'],
+        }
+    }
+
+    #[test]
+    fn test_coverage_reads_tile_alpha() {
+        let font = sheet();
+        let b = font.glyph_index('b').unwrap();
+        assert_eq!(font.coverage(b, 0, 0), 255);
+        assert_eq!(font.coverage(b, 1, 1), 0);
+        assert_eq!(font.coverage(0, 1, 1), 255);
+        assert_eq!(font.glyph_index('z'), None);
+    }
+
+    #[test]
+    fn test_integer_scale() {
+        let font = sheet();
+        assert_eq!(font.scale_for(1.0), 1);
+        assert_eq!(font.scale_for(4.0), 2);
+        assert_eq!(font.cell_size(6.2), UVec2::new(6, 6));
+    }
+}
@@ -1,4 +1,4 @@
-use bevy::color::Color;
+use bevy::color::{Alpha, Color, Mix};
 use ratatui::style::Color as RatColor;
 
 /// Convert a ratatui Color to a Bevy Color.
@@ -66,6 +66,169 @@ fn indexed_color(index: u8) -> Color {
     }
 }
 
+/// The 16 named ratatui colors, in palette order (`Indexed(0)..=15`).
+const NAMED: [RatColor; 16] = [
+    RatColor::Black,
+    RatColor::Red,
+    RatColor::Green,
+    RatColor::Yellow,
+    RatColor::Blue,
+    RatColor::Magenta,
+    RatColor::Cyan,
+    RatColor::Gray,
+    RatColor::DarkGray,
+    RatColor::LightRed,
+    RatColor::LightGreen,
+    RatColor::LightYellow,
+    RatColor::LightBlue,
+    RatColor::LightMagenta,
+    RatColor::LightCyan,
+    RatColor::White,
+];
+
+/// sRGB components of `color` rounded to 8 bits.
+fn srgb_bytes(color: Color) -> [u8; 3] {
+    let srgba = color.to_srgba();
+    [srgba.red, srgba.green, srgba.blue].map(|v| (v.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+/// Convert a Bevy Color back to a ratatui Color.
+///
+/// Lossy inverse of `ratatui_color_to_bevy`: a color matching one of the 16
+/// named colors at 8-bit precision comes back as that name, anything else as
+/// `Rgb`, exact to 8 bits per channel. Indexed colors therefore return as
+/// their named or `Rgb` equivalent. Fully transparent colors return as
+/// `Reset`; other alpha is dropped.
+pub fn bevy_to_ratatui(color: Color) -> RatColor {
+    if color.alpha() == 0.0 {
+        return RatColor::Reset;
+    }
+    let bytes = srgb_bytes(color);
+    NAMED
+        .into_iter()
+        .find(|&named| srgb_bytes(ratatui_color_to_bevy(named)) == bytes)
+        .unwrap_or(RatColor::Rgb(bytes[0], bytes[1], bytes[2]))
+}
+
+/// Index of the 256-color palette entry nearest to `color`, for targets
+/// that only take `Indexed` colors.
+///
+/// Nearest means the smallest squared distance between 8-bit sRGB
+/// components, ties going to the lower index. The metric isn't perceptual,
+/// so a color far from every palette entry can come back a visibly
+/// different hue.
+pub fn nearest_indexed(color: Color) -> u8 {
+    let target = srgb_bytes(color).map(i32::from);
+    (0..=255u8)
+        .min_by_key(|&index| {
+            let entry = srgb_bytes(indexed_color(index)).map(i32::from);
+            (0..3).map(|k| (entry[k] - target[k]).pow(2)).sum::<i32>()
+        })
+        .unwrap_or(0)
+}
+
+/// How far `bright_bold_fg` blends colors without a light variant toward
+/// white.
+const BOLD_BOOST: f32 = 0.25;
+
+/// Foreground of a bold glyph when bold is shown as a brighter color. The
+/// eight base colors become their light palette variants, as in most
+/// terminals; any other color, including `Reset` (which resolves to
+/// `default`), is blended a quarter of the way to white.
+pub fn bright_bold_fg(color: RatColor, default: Color) -> Color {
+    let light = match color {
+        RatColor::Black => RatColor::DarkGray,
+        RatColor::Red => RatColor::LightRed,
+        RatColor::Green => RatColor::LightGreen,
+        RatColor::Yellow => RatColor::LightYellow,
+        RatColor::Blue => RatColor::LightBlue,
+        RatColor::Magenta => RatColor::LightMagenta,
+        RatColor::Cyan => RatColor::LightCyan,
+        RatColor::Gray => RatColor::White,
+        RatColor::Indexed(index @ 0..=7) => RatColor::Indexed(index + 8),
+        other => {
+            let base = ratatui_fg_to_bevy(other, default);
+            let boosted = base.to_srgba().mix(&Color::WHITE.to_srgba(), BOLD_BOOST);
+            return Color::Srgba(boosted).with_alpha(base.alpha());
+        }
+    };
+    ratatui_color_to_bevy(light)
+}
+
+/// How glyphs with `Modifier::DIM` are drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DimMode {
+    /// Make the glyph translucent (default).
+    #[default]
+    Alpha,
+    /// Darken the glyph toward black, keeping it opaque. Reads better over
+    /// busy or transparent backgrounds.
+    Darken,
+}
+
+impl DimMode {
+    /// `color` dimmed to `factor` of its alpha or brightness, clamped to
+    /// 0.0..=1.0: 1.0 leaves it unchanged.
+    pub fn apply(self, color: Color, factor: f32) -> Color {
+        let factor = factor.clamp(0.0, 1.0);
+        match self {
+            DimMode::Alpha => color.with_alpha(color.alpha() * factor),
+            DimMode::Darken => {
+                let srgba = color.to_srgba();
+                Color::srgba(
+                    srgba.red * factor,
+                    srgba.green * factor,
+                    srgba.blue * factor,
+                    srgba.alpha,
+                )
+            }
+        }
+    }
+}
+
+/// How a tint combines with the color under it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TintMode {
+    /// Crossfade toward the tint by `strength` (default).
+    #[default]
+    Mix,
+    /// Multiply the channels, darkening by the tint.
+    Multiply,
+    /// Add the channels, brightening toward white like a bloom.
+    Add,
+    /// Invert, multiply and invert back: brightens, but never past white and
+    /// more gently than `Add`.
+    Screen,
+    /// Take the tint's channels outright, whatever the strength.
+    Replace,
+}
+
+impl TintMode {
+    /// `base` tinted by `tint`, keeping `base`'s alpha. `Multiply`, `Add` and
+    /// `Screen` work on the sRGB channels, clamped to 0.0..=1.0, and fade in
+    /// with `strength` like `Mix` does.
+    pub fn apply(self, base: Color, tint: Color, strength: f32) -> Color {
+        let alpha = base.alpha();
+        let blend = |op: fn(f32, f32) -> f32| {
+            let (b, t) = (base.to_srgba(), tint.to_srgba());
+            let blended = Color::srgb(
+                op(b.red, t.red).clamp(0.0, 1.0),
+                op(b.green, t.green).clamp(0.0, 1.0),
+                op(b.blue, t.blue).clamp(0.0, 1.0),
+            );
+            Color::from(b).mix(&blended, strength.clamp(0.0, 1.0))
+        };
+        let tinted = match self {
+            TintMode::Mix => base.mix(&tint, strength),
+            TintMode::Multiply => blend(|b, t| b * t),
+            TintMode::Add => blend(|b, t| b + t),
+            TintMode::Screen => blend(|b, t| 1.0 - (1.0 - b) * (1.0 - t)),
+            TintMode::Replace => tint,
+        };
+        tinted.with_alpha(alpha)
+    }
+}
+
 /// Convert a ratatui foreground color to a Bevy Color, using a default for Reset.
 pub fn ratatui_fg_to_bevy(color: RatColor, default: Color) -> Color {
     if color == RatColor::Reset {
@@ -84,6 +247,27 @@ pub fn ratatui_bg_to_bevy(color: RatColor, default: Color) -> Color {
     }
 }
 
+/// Sample a piecewise-linear gradient at `t`.
+///
+/// `stops` are `(position, color)` pairs sorted by position; `t` before the
+/// first or after the last stop clamps to that stop's color. Colors are
+/// blended in sRGB space. Returns `None` if there are no stops.
+pub fn sample_gradient(stops: &[(f32, Color)], t: f32) -> Option<Color> {
+    let (first, last) = (stops.first()?, stops.last()?);
+    if t <= first.0 {
+        return Some(first.1);
+    }
+    for pair in stops.windows(2) {
+        let ((p0, c0), (p1, c1)) = (pair[0], pair[1]);
+        if t <= p1 {
+            let span = p1 - p0;
+            let f = if span > 0.0 { (t - p0) / span } else { 1.0 };
+            return Some(Color::Srgba(c0.to_srgba().mix(&c1.to_srgba(), f)));
+        }
+    }
+    Some(last.1)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,6 +294,91 @@ mod tests {
         assert_eq!(color, Color::srgb(v, v, v));
     }
 
+    #[test]
+    fn test_sample_gradient() {
+        let stops = [
+            (0.0, Color::srgb(0.0, 0.0, 0.0)),
+            (0.5, Color::srgb(1.0, 0.0, 0.0)),
+            (1.0, Color::srgb(1.0, 1.0, 1.0)),
+        ];
+        assert_eq!(sample_gradient(&stops, -1.0), Some(stops[0].1));
+        assert_eq!(sample_gradient(&stops, 2.0), Some(stops[2].1));
+
+        let mid = sample_gradient(&stops, 0.25).unwrap().to_srgba();
+        assert!((mid.red - 0.5).abs() < 1e-5);
+        assert_eq!(mid.green, 0.0);
+
+        assert_eq!(sample_gradient(&[], 0.5), None);
+    }
+
+    #[test]
+    fn test_bevy_to_ratatui_round_trip() {
+        for named in NAMED {
+            assert_eq!(bevy_to_ratatui(ratatui_color_to_bevy(named)), named);
+        }
+        let rgb = RatColor::Rgb(12, 200, 99);
+        assert_eq!(bevy_to_ratatui(ratatui_color_to_bevy(rgb)), rgb);
+        // Indexed colors come back as their concrete equivalent
+        assert_eq!(bevy_to_ratatui(indexed_color(1)), RatColor::Red);
+        assert_eq!(bevy_to_ratatui(indexed_color(196)), RatColor::Rgb(255, 0, 0));
+        assert_eq!(bevy_to_ratatui(Color::NONE), RatColor::Reset);
+    }
+
+    #[test]
+    fn test_nearest_indexed() {
+        for index in [0, 9, 21, 100, 196, 250] {
+            assert_eq!(nearest_indexed(indexed_color(index)), index);
+        }
+        // Cube entries that duplicate a base color resolve to the lower index
+        assert_eq!(nearest_indexed(indexed_color(16)), 0);
+        assert_eq!(nearest_indexed(indexed_color(231)), 15);
+        assert_eq!(nearest_indexed(Color::srgb(0.79, 0.01, 0.0)), 1);
+    }
+
+    #[test]
+    fn test_dim_modes() {
+        let fg = Color::srgb(0.8, 0.4, 0.2);
+        assert_eq!(DimMode::Alpha.apply(fg, 0.5), fg.with_alpha(0.5));
+        assert_eq!(DimMode::Darken.apply(fg, 0.5), Color::srgb(0.4, 0.2, 0.1));
+        assert_eq!(DimMode::Darken.apply(fg, 1.0), fg);
+        assert_eq!(DimMode::Alpha.apply(fg, -1.0).alpha(), 0.0);
+    }
+
+    #[test]
+    fn test_tint_modes() {
+        let base = Color::srgba(0.5, 0.25, 1.0, 0.5);
+        let tint = Color::srgb(0.5, 1.0, 0.5);
+        let tinted = |mode: TintMode, strength| {
+            let srgba = mode.apply(base, tint, strength).to_srgba();
+            [srgba.red, srgba.green, srgba.blue, srgba.alpha]
+        };
+        assert_eq!(tinted(TintMode::Mix, 0.5), [0.5, 0.625, 0.75, 0.5]);
+        assert_eq!(tinted(TintMode::Multiply, 1.0), [0.25, 0.25, 0.5, 0.5]);
+        assert_eq!(tinted(TintMode::Add, 1.0), [1.0, 1.0, 1.0, 0.5]);
+        assert_eq!(tinted(TintMode::Screen, 1.0), [0.75, 1.0, 1.0, 0.5]);
+        assert_eq!(tinted(TintMode::Replace, 0.1), [0.5, 1.0, 0.5, 0.5]);
+
+        // Blend modes fade in with strength too
+        assert_eq!(tinted(TintMode::Multiply, 0.0), [0.5, 0.25, 1.0, 0.5]);
+        assert_eq!(tinted(TintMode::Add, 0.5), [0.75, 0.625, 1.0, 0.5]);
+    }
+
+    #[test]
+    fn test_bright_bold_fg() {
+        let default = Color::srgb(0.6, 0.6, 0.6);
+        let bold = |color| bright_bold_fg(color, default);
+        // Red and Indexed(1) both light up to LightRed, Indexed(9)
+        assert_eq!(bold(RatColor::Red), indexed_color(9));
+        assert_eq!(bold(RatColor::Indexed(1)), indexed_color(9));
+        let reset = bold(RatColor::Reset).to_srgba();
+        assert!((reset.red - 0.7).abs() < 1e-6 && reset.red == reset.blue);
+
+        // Already-light colors still get brighter, but stay the same hue
+        let boosted = bold(RatColor::LightRed).to_srgba();
+        assert_eq!(boosted.red, 1.0);
+        assert!(boosted.green > 0.33 && boosted.green < 1.0);
+    }
+
     #[test]
     fn test_reset_defaults() {
         let default_fg = Color::srgb(0.9, 0.9, 0.9);
@@ -0,0 +1,291 @@
+use std::marker::PhantomData;
+
+use bevy::asset::{embedded_path, AssetPath};
+use bevy::camera::visibility::RenderLayers;
+use bevy::camera::RenderTarget;
+use bevy::image::ImageSampler;
+use bevy::prelude::*;
+use bevy::render::render_resource::{AsBindGroup, Extent3d, TextureFormat};
+use bevy::shader::ShaderRef;
+use bevy::sprite_render::Material2d;
+
+use crate::render_target::TerminalRenderTarget;
+
+/// Full-screen CRT look for terminal `T`: scanlines, barrel distortion, a
+/// vignette and chromatic aberration. Toggle `enabled` at runtime.
+///
+/// Works on the offscreen image, so it needs `TerminalConfig::render_layer`
+/// and leaves the rest of the game untouched. While enabled the terminal
+/// camera draws into an intermediate image, and a second camera on `layer`
+/// draws that through the CRT shader into `TerminalRenderTarget::image`, so
+/// whatever shows the image picks up the effect without changes.
+#[derive(Resource, Clone, Debug)]
+pub struct CrtPostProcess<T: 'static + Send + Sync> {
+    /// Toggle the effect (default: false).
+    pub enabled: bool,
+    /// How much every other pixel row is darkened, 0.0..=1.0 (default: 0.25).
+    pub scanlines: f32,
+    /// Barrel distortion strength; 0.0 keeps the image flat (default: 0.1).
+    pub curvature: f32,
+    /// Darkening toward the corners; 0.0 disables it (default: 0.3).
+    pub vignette: f32,
+    /// Red/blue channel split in pixels (default: 1.0).
+    pub aberration: f32,
+    /// Render layer for the CRT pass, which must not be used by anything
+    /// else. Read when the pass is set up (default: 31).
+    pub layer: u8,
+    _marker: PhantomData<T>,
+}
+
+impl<T: 'static + Send + Sync> Default for CrtPostProcess<T> {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            scanlines: 0.25,
+            curvature: 0.1,
+            vignette: 0.3,
+            aberration: 1.0,
+            layer: 31,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: 'static + Send + Sync> CrtPostProcess<T> {
+    /// The intensities packed in the order `crt.wgsl` reads them.
+    pub fn params(&self) -> Vec4 {
+        Vec4::new(
+            self.scanlines,
+            self.curvature,
+            self.vignette,
+            self.aberration,
+        )
+    }
+}
+
+/// Draws `source` with the CRT effect on a quad filling the CRT camera.
+#[derive(Asset, TypePath, AsBindGroup, Clone, Debug)]
+pub struct CrtMaterial {
+    /// Scanlines, curvature, vignette and aberration.
+    #[uniform(0)]
+    pub params: Vec4,
+    /// What the terminal camera rendered this frame.
+    #[texture(1)]
+    #[sampler(2)]
+    pub source: Handle<Image>,
+}
+
+impl Material2d for CrtMaterial {
+    fn fragment_shader() -> ShaderRef {
+        ShaderRef::Path(
+            AssetPath::from_path_buf(embedded_path!("crt.wgsl")).with_source("embedded"),
+        )
+    }
+}
+
+/// Entities and assets of a running CRT pass.
+pub struct CrtStage {
+    /// Intermediate image the terminal camera renders into.
+    raw: Handle<Image>,
+    material: Handle<CrtMaterial>,
+    camera: Entity,
+    quad: Entity,
+    size: UVec2,
+}
+
+/// Sets up, resizes and tears down the CRT pass to match `CrtPostProcess<T>`
+/// and the render target, and copies intensity changes into the material.
+#[allow(clippy::too_many_arguments)]
+pub fn update_crt<T: 'static + Send + Sync>(
+    mut commands: Commands,
+    crt: Res<CrtPostProcess<T>>,
+    target: Option<Res<TerminalRenderTarget<T>>>,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<CrtMaterial>>,
+    mut cameras: Query<(&mut Camera, &mut RenderTarget)>,
+    mut stage: Local<Option<CrtStage>>,
+) {
+    let Some(target) = target else {
+        return;
+    };
+
+    if !crt.enabled {
+        // Point the terminal camera back at the image and drop the pass
+        if let Some(old) = stage.take() {
+            commands.entity(old.camera).despawn();
+            commands.entity(old.quad).despawn();
+            materials.remove(&old.material);
+            images.remove(&old.raw);
+            if let Ok((mut camera, mut render_target)) = cameras.get_mut(target.camera) {
+                camera.order = -1;
+                *render_target = RenderTarget::Image(target.image.clone().into());
+            }
+        }
+        return;
+    }
+
+    if stage.is_none() {
+        let mut raw_image = Image::new_target_texture(
+            target.size.x,
+            target.size.y,
+            TextureFormat::Rgba8UnormSrgb,
+            None,
+        );
+        // Curvature samples between texels, so nearest filtering would alias
+        raw_image.sampler = ImageSampler::linear();
+        let raw = images.add(raw_image);
+        let material = materials.add(CrtMaterial {
+            params: crt.params(),
+            source: raw.clone(),
+        });
+        let layer = RenderLayers::layer(crt.layer as usize);
+
+        let quad = commands
+            .spawn((
+                Mesh2d(meshes.add(Rectangle::from_size(target.size.as_vec2()))),
+                MeshMaterial2d(material.clone()),
+                layer.clone(),
+            ))
+            .id();
+        let camera = commands
+            .spawn((
+                Camera2d,
+                Camera {
+                    order: -1,
+                    clear_color: ClearColorConfig::Custom(Color::BLACK),
+                    ..default()
+                },
+                RenderTarget::Image(target.image.clone().into()),
+                layer,
+            ))
+            .id();
+
+        // The terminal camera has to finish before the CRT camera samples it
+        if let Ok((mut terminal_camera, mut render_target)) = cameras.get_mut(target.camera) {
+            terminal_camera.order = -2;
+            *render_target = RenderTarget::Image(raw.clone().into());
+        }
+
+        *stage = Some(CrtStage {
+            raw,
+            material,
+            camera,
+            quad,
+            size: target.size,
+        });
+        return;
+    }
+    let Some(current) = stage.as_mut() else {
+        return;
+    };
+
+    if current.size != target.size {
+        if let Some(image) = images.get_mut(&current.raw) {
+            image.resize(Extent3d {
+                width: target.size.x,
+                height: target.size.y,
+                depth_or_array_layers: 1,
+            });
+        }
+        let mesh = meshes.add(Rectangle::from_size(target.size.as_vec2()));
+        commands.entity(current.quad).insert(Mesh2d(mesh));
+        current.size = target.size;
+    }
+
+    // `get_mut` re-uploads the material, so only take it on a change
+    let params = crt.params();
+    let stale = materials
+        .get(&current.material)
+        .is_some_and(|material| material.params != params);
+    if stale {
+        if let Some(material) = materials.get_mut(&current.material) {
+            material.params = params;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_target::spawn_render_target;
+    use crate::{TerminalConfig, TerminalLayout};
+
+    struct Term;
+
+    #[test]
+    fn test_params_follow_shader_order() {
+        let crt = CrtPostProcess::<Term> {
+            scanlines: 0.5,
+            curvature: 0.2,
+            vignette: 0.7,
+            aberration: 2.0,
+            ..default()
+        };
+        assert_eq!(crt.params(), Vec4::new(0.5, 0.2, 0.7, 2.0));
+    }
+
+    #[test]
+    fn test_crt_pass_set_up_and_torn_down() {
+        let config = TerminalConfig::<Term> {
+            render_layer: Some(1),
+            ..default()
+        };
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, bevy::asset::AssetPlugin::default()))
+            .init_asset::<Image>()
+            .init_asset::<Mesh>()
+            .init_asset::<CrtMaterial>()
+            .insert_resource(TerminalLayout::from_config(&config))
+            .insert_resource(config)
+            .insert_resource(CrtPostProcess::<Term>::default())
+            .add_systems(Startup, spawn_render_target::<Term>)
+            .add_systems(Update, update_crt::<Term>);
+        app.update();
+
+        let target = app.world().resource::<TerminalRenderTarget<Term>>().clone();
+        // Order and image of a camera
+        let camera = |app: &App, entity| {
+            let world = app.world();
+            let order = world.get::<Camera>(entity).unwrap().order;
+            let render_target = world.get::<RenderTarget>(entity).unwrap();
+            (order, render_target.as_image().cloned())
+        };
+        let crt_cameras = |app: &mut App| {
+            let world = app.world_mut();
+            let mut query = world.query_filtered::<Entity, With<Camera>>();
+            let mut cameras: Vec<Entity> = query.iter(world).collect();
+            cameras.retain(|&entity| entity != target.camera);
+            cameras
+        };
+        let direct = (-1, Some(target.image.clone()));
+        assert_eq!(camera(&app, target.camera), direct);
+
+        // Enabling reroutes the terminal camera through the CRT camera
+        let world = app.world_mut();
+        world.resource_mut::<CrtPostProcess<Term>>().enabled = true;
+        app.update();
+        let (order, raw) = camera(&app, target.camera);
+        assert_eq!(order, -2);
+        assert!(raw.is_some_and(|raw| raw != target.image));
+        let pass = crt_cameras(&mut app);
+        assert_eq!(pass.len(), 1);
+        assert_eq!(camera(&app, pass[0]), direct);
+
+        // Intensity changes reach the material
+        let world = app.world_mut();
+        world.resource_mut::<CrtPostProcess<Term>>().scanlines = 0.8;
+        app.update();
+        let materials = app.world().resource::<Assets<CrtMaterial>>();
+        let (_, material) = materials.iter().next().unwrap();
+        assert_eq!(material.params.x, 0.8);
+
+        // Disabling restores the direct path and drops the pass
+        let world = app.world_mut();
+        world.resource_mut::<CrtPostProcess<Term>>().enabled = false;
+        app.update();
+        assert_eq!(camera(&app, target.camera), direct);
+        assert!(crt_cameras(&mut app).is_empty());
+        assert!(app.world().resource::<Assets<CrtMaterial>>().is_empty());
+    }
+}
@@ -0,0 +1,38 @@
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+// x: scanlines, y: curvature, z: vignette, w: aberration in pixels
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> params: vec4<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var source: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(2) var source_sampler: sampler;
+
+const PI: f32 = 3.14159265;
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let size = vec2<f32>(textureDimensions(source));
+
+    // Barrel distortion: sample further out the further from the center
+    let centered = in.uv * 2.0 - 1.0;
+    let uv = centered * (1.0 + params.y * dot(centered, centered)) * 0.5 + 0.5;
+    let inside = step(vec2<f32>(0.0), uv) * step(uv, vec2<f32>(1.0));
+
+    // Chromatic aberration: red and blue pulled apart horizontally.
+    // Explicit LOD so sampling is allowed after the non-uniform distortion.
+    let shift = vec2<f32>(params.w / size.x, 0.0);
+    let center = textureSampleLevel(source, source_sampler, uv, 0.0);
+    let red = textureSampleLevel(source, source_sampler, uv + shift, 0.0).r;
+    let blue = textureSampleLevel(source, source_sampler, uv - shift, 0.0).b;
+    var color = vec3<f32>(red, center.g, blue);
+
+    // Scanlines: a dark band on every other pixel row. Measured from texel
+    // centers, where the cosine is exactly 1 or -1.
+    let row = uv.y * size.y - 0.5;
+    let band = 0.5 + 0.5 * cos(row * PI);
+    color *= 1.0 - params.x * band;
+
+    // Vignette: 1.0 in the middle, falling to 0.0 at the edges
+    let edge = uv * (1.0 - uv) * 4.0;
+    color *= pow(clamp(edge.x * edge.y, 1e-4, 1.0), params.z);
+
+    return vec4<f32>(color * inside.x * inside.y, 1.0);
+}
@@ -0,0 +1,170 @@
+use std::marker::PhantomData;
+
+use bevy::camera::primitives::{Aabb, Frustum};
+use bevy::prelude::*;
+
+use crate::grid::{CellEntityIndex, CellStyle, TerminalCell};
+use crate::{TerminalConfig, TerminalLayout};
+
+/// Whether any part of the terminal grid is inside an active camera's view.
+///
+/// Only updated when `TerminalConfig::cull_offscreen` is enabled; otherwise it
+/// stays `true` and sync/effects always run.
+#[derive(Resource)]
+pub struct TerminalVisible<T: 'static + Send + Sync> {
+    pub visible: bool,
+    _marker: PhantomData<T>,
+}
+
+impl<T: 'static + Send + Sync> Default for TerminalVisible<T> {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Run condition: true while the terminal is (potentially) on screen.
+pub fn terminal_visible<T: 'static + Send + Sync>(visible: Res<TerminalVisible<T>>) -> bool {
+    visible.visible
+}
+
+/// Compute the grid's world-space bounding box from the layout.
+pub fn grid_aabb<T: 'static + Send + Sync>(
+    config: &TerminalConfig<T>,
+    layout: &TerminalLayout<T>,
+) -> Aabb {
+    let bounds = layout.bounds();
+    // Span both background and glyph depths, plus a little slack so the box
+    // isn't clipped when it sits exactly on a camera's near/far plane.
+    let z_min = config.z_layer + config.fg_z_offset.min(0.0) - 0.5;
+    let z_max = config.z_layer + config.fg_z_offset.max(0.0) + 0.5;
+    Aabb::from_min_max(bounds.min.extend(z_min), bounds.max.extend(z_max))
+}
+
+/// Test the grid's bounding box against every active camera frustum and
+/// update `TerminalVisible<T>`. Uses compare-before-write so downstream
+/// change detection only fires when visibility actually flips.
+pub fn update_terminal_visibility<T: 'static + Send + Sync>(
+    config: Res<TerminalConfig<T>>,
+    layout: Res<TerminalLayout<T>>,
+    cameras: Query<(&Camera, &Frustum)>,
+    mut visible: ResMut<TerminalVisible<T>>,
+) {
+    let aabb = grid_aabb(&config, &layout);
+    let any_visible = cameras
+        .iter()
+        .filter(|(camera, _)| camera.is_active)
+        .any(|(_, frustum)| frustum.intersects_obb_identity(&aabb));
+
+    if visible.visible != any_visible {
+        visible.visible = any_visible;
+    }
+}
+
+/// Whether a cell with this symbol draws nothing in the foreground.
+pub(crate) fn is_blank(symbol: &str) -> bool {
+    symbol.chars().all(|ch| ch == ' ')
+}
+
+/// Hide the glyph sprite of blank cells, and the whole cell when its
+/// background also matches the terminal's reset background, so sparse
+/// layouts submit fewer sprites. Runs after color effects, so a cell an
+/// effect recolors (e.g. a selection highlight) stays visible.
+///
+/// Only added when `TerminalConfig::hide_blank_cells` is enabled.
+pub fn hide_blank_cells<T: 'static + Send + Sync>(
+    config: Res<TerminalConfig<T>>,
+    cell_index: Res<CellEntityIndex<T>>,
+    mut cells: Query<(&CellStyle, &Sprite, &mut Visibility), With<TerminalCell<T>>>,
+    mut fg_visibility: Query<&mut Visibility, Without<TerminalCell<T>>>,
+) {
+    let reset_bg = config.reset_bg();
+    for (idx, &entity) in cell_index.entities.iter().enumerate() {
+        let Ok((style, bg_sprite, mut visibility)) = cells.get_mut(entity) else {
+            continue;
+        };
+        let blank = is_blank(&style.symbol);
+        let cell = if blank && bg_sprite.color == reset_bg {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+        if *visibility != cell {
+            *visibility = cell;
+        }
+
+        let glyph = if blank {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+        if let Ok(mut fg) = fg_visibility.get_mut(cell_index.fg_entities[idx]) {
+            if *fg != glyph {
+                *fg = glyph;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::{Color as RatColor, Style};
+
+    use super::*;
+    use crate::grid::ColorOverride;
+    use crate::testing::TerminalTestHarness;
+    use crate::TerminalEmuPlugin;
+
+    struct Term;
+
+    #[test]
+    fn test_hide_blank_cells() {
+        let plugin = TerminalEmuPlugin::<Term>::new()
+            .columns(3)
+            .rows(1)
+            .hide_blank_cells(true);
+        let mut harness = TerminalTestHarness::new(plugin);
+        // "a", then a space on a blue background, then whatever `text` holds
+        let draw = |harness: &mut TerminalTestHarness<Term>, text: &str| {
+            harness.draw(|frame| {
+                let buffer = frame.buffer_mut();
+                buffer.set_string(0, 0, "a", Style::default());
+                buffer.set_string(1, 0, " ", Style::default().bg(RatColor::Blue));
+                buffer.set_string(2, 0, text, Style::default());
+            });
+        };
+        // Whether the cell and its glyph are shown
+        let shown = |harness: &TerminalTestHarness<Term>, col: u16| {
+            let world = harness.app.world();
+            let index = world.resource::<CellEntityIndex<Term>>();
+            let visible = |entity: Option<Entity>| {
+                world.get::<Visibility>(entity.unwrap()) != Some(&Visibility::Hidden)
+            };
+            (visible(index.get(col, 0)), visible(index.get_fg(col, 0)))
+        };
+
+        draw(&mut harness, " ");
+        assert_eq!(shown(&harness, 0), (true, true));
+        assert_eq!(shown(&harness, 1), (true, false));
+        assert_eq!(shown(&harness, 2), (false, false));
+
+        draw(&mut harness, "b");
+        assert_eq!(shown(&harness, 2), (true, true));
+
+        draw(&mut harness, " ");
+        assert_eq!(shown(&harness, 2), (false, false));
+
+        // An effect recoloring the blank cell brings it back
+        let world = harness.app.world();
+        let cell = world.resource::<CellEntityIndex<Term>>().get(2, 0).unwrap();
+        let red = ColorOverride {
+            fg: None,
+            bg: Some(Color::srgb(1.0, 0.0, 0.0)),
+        };
+        harness.app.world_mut().entity_mut(cell).insert(red);
+        harness.update();
+        assert_eq!(shown(&harness, 2), (true, false));
+    }
+}
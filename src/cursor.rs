@@ -0,0 +1,337 @@
+use std::marker::PhantomData;
+
+#[cfg(feature = "render")]
+use bevy::camera::visibility::RenderLayers;
+use bevy::prelude::*;
+
+#[cfg(feature = "render")]
+use crate::grid::{CellEntityIndex, ForegroundSprite, TerminalCell};
+use crate::GridPosition;
+#[cfg(feature = "render")]
+use crate::{TerminalConfig, TerminalLayout};
+
+/// Copy of the ratatui cursor state, refreshed by the sync system each frame
+/// so other systems can read it without locking the terminal.
+///
+/// `visible` follows `Frame::set_cursor_position`: ratatui shows the cursor
+/// only on frames that place it.
+#[derive(Resource, Clone, Debug)]
+pub struct CursorState<T: 'static + Send + Sync> {
+    pub position: GridPosition,
+    pub visible: bool,
+    _marker: PhantomData<T>,
+}
+
+impl<T: 'static + Send + Sync> Default for CursorState<T> {
+    fn default() -> Self {
+        Self {
+            position: GridPosition { col: 0, row: 0 },
+            visible: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Shape of the cursor drawn while ratatui shows it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CursorStyle {
+    /// Cover the whole cell (default).
+    #[default]
+    Block,
+    /// A thin line along the bottom of the cell.
+    Underline,
+    /// A thin line along the left edge of the cell.
+    Bar,
+    /// Swap the glyph and background colors of the cell under the cursor,
+    /// like most hardware terminals. Applied on top of reverse video and
+    /// the selection highlight; the cell's own colors return the frame the
+    /// cursor leaves it.
+    Invert,
+    /// Don't draw the cursor; `CursorState` is still updated.
+    Hidden,
+}
+
+/// Seconds the cursor stays solid after it moves before blinking resumes,
+/// so it doesn't vanish while the user is typing.
+pub const BLINK_RESUME_DELAY: f32 = 0.5;
+
+/// Whether a cursor blinking at `hz` is in its shown half `since_move`
+/// seconds after it last moved. Always true when `hz` is None or not
+/// positive.
+pub fn cursor_blink_on(hz: Option<f32>, since_move: f32) -> bool {
+    let Some(hz) = hz.filter(|hz| *hz > 0.0) else {
+        return true;
+    };
+    if since_move < BLINK_RESUME_DELAY {
+        return true;
+    }
+    ((since_move - BLINK_RESUME_DELAY) * hz).fract() < 0.5
+}
+
+/// The sprite drawing a terminal's cursor, one per terminal instance.
+#[cfg(feature = "render")]
+#[derive(Component)]
+pub struct CursorSprite<T: 'static + Send + Sync> {
+    /// Cell the cursor was on last frame.
+    position: GridPosition,
+    /// Seconds since the cursor last moved, for the blink.
+    since_move: f32,
+    /// Whether the cursor is showing this frame, in any style.
+    lit: bool,
+    _marker: PhantomData<T>,
+}
+
+#[cfg(feature = "render")]
+impl<T: 'static + Send + Sync> Default for CursorSprite<T> {
+    fn default() -> Self {
+        Self {
+            position: GridPosition { col: 0, row: 0 },
+            since_move: 0.0,
+            lit: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Startup system that spawns the (initially hidden) cursor sprite.
+#[cfg(feature = "render")]
+pub fn spawn_cursor<T: 'static + Send + Sync>(
+    mut commands: Commands,
+    config: Res<TerminalConfig<T>>,
+) {
+    let mut cursor = commands.spawn((
+        CursorSprite::<T>::default(),
+        Sprite::from_color(Color::NONE, Vec2::ONE),
+        Transform::default(),
+        Visibility::Hidden,
+    ));
+    if let Some(layer) = config.render_layer {
+        cursor.insert(RenderLayers::layer(layer as usize));
+    }
+}
+
+/// Places the cursor sprite over the cursor's cell and blinks it at
+/// `TerminalConfig::cursor_blink_hz`. Runs after geometry effects so the
+/// cursor follows its cell when an effect moves it.
+#[cfg(feature = "render")]
+pub fn update_cursor_sprite<T: 'static + Send + Sync>(
+    time: Res<Time>,
+    config: Res<TerminalConfig<T>>,
+    layout: Res<TerminalLayout<T>>,
+    cursor: Res<CursorState<T>>,
+    cell_index: Res<CellEntityIndex<T>>,
+    cells: Query<&Transform, With<TerminalCell<T>>>,
+    mut sprites: Query<
+        (
+            &mut CursorSprite<T>,
+            &mut Sprite,
+            &mut Transform,
+            &mut Visibility,
+        ),
+        Without<TerminalCell<T>>,
+    >,
+) {
+    let cell_transform = cell_index
+        .get(cursor.position.col, cursor.position.row)
+        .and_then(|entity| cells.get(entity).ok());
+
+    for (mut state, mut sprite, mut transform, mut visibility) in sprites.iter_mut() {
+        if state.position != cursor.position {
+            state.position = cursor.position;
+            state.since_move = 0.0;
+        } else {
+            state.since_move += time.delta_secs();
+        }
+
+        state.lit = cursor.visible
+            && cell_transform.is_some()
+            && cursor_blink_on(config.cursor_blink_hz, state.since_move);
+        let shown = state.lit
+            && matches!(
+                config.cursor_style,
+                CursorStyle::Block | CursorStyle::Underline | CursorStyle::Bar
+            );
+        let target = if shown {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        if *visibility != target {
+            *visibility = target;
+        }
+        let Some(cell_transform) = cell_transform.filter(|_| shown) else {
+            continue;
+        };
+
+        let cell = Vec2::new(layout.cell_width, layout.cell_height);
+        let (size, offset) = match config.cursor_style {
+            CursorStyle::Underline => {
+                let thickness = (cell.y / 8.0).max(1.0);
+                (
+                    Vec2::new(cell.x, thickness),
+                    Vec2::new(0.0, (thickness - cell.y) / 2.0),
+                )
+            }
+            CursorStyle::Bar => {
+                let thickness = (cell.x / 8.0).max(1.0);
+                (
+                    Vec2::new(thickness, cell.y),
+                    Vec2::new((thickness - cell.x) / 2.0, 0.0),
+                )
+            }
+            CursorStyle::Block | CursorStyle::Invert | CursorStyle::Hidden => (cell, Vec2::ZERO),
+        };
+        // Just above the glyph, so text under a block cursor shows through
+        let translation = cell_transform.translation + offset.extend(config.fg_z_offset * 1.5);
+        let color = config
+            .cursor_color
+            .unwrap_or(config.default_fg.with_alpha(0.5));
+
+        if sprite.custom_size != Some(size) {
+            sprite.custom_size = Some(size);
+        }
+        if sprite.color != color {
+            sprite.color = color;
+        }
+        if transform.translation != translation {
+            transform.translation = translation;
+        }
+    }
+}
+
+/// Swaps the colors of the cursor's cell for `CursorStyle::Invert`. Runs
+/// after the selection highlight and works on whatever colors the cell
+/// shows by then, so a reversed or selected cell inverts back. Nothing needs
+/// undoing when the cursor moves: `reset_colors` repaints every cell from
+/// its `CellStyle` at the start of the next frame.
+#[cfg(feature = "render")]
+pub fn invert_cursor_cell<T: 'static + Send + Sync>(
+    config: Res<TerminalConfig<T>>,
+    cursor: Res<CursorState<T>>,
+    cell_index: Res<CellEntityIndex<T>>,
+    sprites: Query<&CursorSprite<T>>,
+    mut cells: Query<&mut Sprite, With<TerminalCell<T>>>,
+    mut fg_sprites: Query<&mut Sprite, (With<ForegroundSprite<T>>, Without<TerminalCell<T>>)>,
+) {
+    if config.cursor_style != CursorStyle::Invert || !sprites.iter().any(|state| state.lit) {
+        return;
+    }
+    let GridPosition { col, row } = cursor.position;
+    let (Some(entity), Some(fg_entity)) = (cell_index.get(col, row), cell_index.get_fg(col, row))
+    else {
+        return;
+    };
+    let (Ok(mut bg_sprite), Ok(mut fg_sprite)) =
+        (cells.get_mut(entity), fg_sprites.get_mut(fg_entity))
+    else {
+        return;
+    };
+
+    // A transparent background would turn the glyph invisible
+    let bg = if bg_sprite.color.alpha() > 0.0 {
+        bg_sprite.color
+    } else {
+        config.default_bg
+    };
+    let fg = fg_sprite.color;
+    bg_sprite.color = fg.with_alpha(1.0);
+    fg_sprite.color = bg.with_alpha(fg.alpha());
+}
+
+#[cfg(all(test, feature = "render"))]
+mod tests {
+    use ratatui::layout::Position;
+    use ratatui::style::{Modifier, Style};
+
+    use super::*;
+    use crate::testing::TerminalTestHarness;
+    use crate::TerminalEmuPlugin;
+
+    struct Term;
+
+    fn cursor_shown(harness: &mut TerminalTestHarness<Term>) -> bool {
+        let world = harness.app.world_mut();
+        let mut query = world.query_filtered::<&Visibility, With<CursorSprite<Term>>>();
+        *query.single(world).unwrap() != Visibility::Hidden
+    }
+
+    fn draw_at(harness: &mut TerminalTestHarness<Term>, col: u16) {
+        harness.draw(|frame| frame.set_cursor_position(Position::new(col, 0)));
+    }
+
+    #[test]
+    fn test_blink_phases() {
+        assert!(cursor_blink_on(None, 10.3));
+        assert!(cursor_blink_on(Some(2.0), 0.4));
+        assert!(cursor_blink_on(Some(2.0), BLINK_RESUME_DELAY + 0.1));
+        assert!(!cursor_blink_on(Some(2.0), BLINK_RESUME_DELAY + 0.35));
+        assert!(cursor_blink_on(Some(2.0), BLINK_RESUME_DELAY + 0.6));
+    }
+
+    #[test]
+    fn test_cursor_blinks_and_pauses_after_moving() {
+        let plugin = TerminalEmuPlugin::<Term>::new()
+            .columns(10)
+            .rows(2)
+            .cursor_blink_hz(2.0);
+        let mut harness = TerminalTestHarness::new(plugin);
+
+        draw_at(&mut harness, 1);
+        assert!(cursor_shown(&mut harness));
+
+        // Solid for BLINK_RESUME_DELAY, then on for 0.25 s and off for 0.25 s
+        for _ in 0..50 {
+            draw_at(&mut harness, 1);
+        }
+        assert!(!cursor_shown(&mut harness));
+
+        // Moving shows it again straight away
+        draw_at(&mut harness, 2);
+        assert!(cursor_shown(&mut harness));
+
+        // No blinking once ratatui hides the cursor
+        harness.draw(|_| {});
+        assert!(!cursor_shown(&mut harness));
+    }
+
+    /// Displayed (glyph, background) colors of the cell at (col, 0).
+    fn colors(harness: &TerminalTestHarness<Term>, col: u16) -> (Color, Color) {
+        let world = harness.app.world();
+        let index = world.resource::<CellEntityIndex<Term>>();
+        let sprite_color = |entity| world.get::<Sprite>(entity).unwrap().color;
+        (
+            sprite_color(index.get_fg(col, 0).unwrap()),
+            sprite_color(index.get(col, 0).unwrap()),
+        )
+    }
+
+    #[test]
+    fn test_invert_swaps_and_restores_colors() {
+        let plugin = TerminalEmuPlugin::<Term>::new()
+            .columns(4)
+            .rows(1)
+            .cursor_style(CursorStyle::Invert);
+        let mut harness = TerminalTestHarness::new(plugin);
+        let (fg, bg) = {
+            let config = harness.app.world().resource::<TerminalConfig<Term>>();
+            (config.default_fg, config.default_bg)
+        };
+        let draw = |harness: &mut TerminalTestHarness<Term>, cursor_col: u16| {
+            harness.draw(|frame| {
+                let buffer = frame.buffer_mut();
+                buffer.set_string(0, 0, "a", Style::default());
+                buffer.set_string(1, 0, "b", Style::default().add_modifier(Modifier::REVERSED));
+                frame.set_cursor_position(Position::new(cursor_col, 0));
+            });
+        };
+
+        draw(&mut harness, 0);
+        assert_eq!(colors(&harness, 0), (bg, fg));
+        assert_eq!(colors(&harness, 1), (bg, fg));
+
+        // The reversed cell flips back under the cursor; the old one recovers
+        draw(&mut harness, 1);
+        assert_eq!(colors(&harness, 0), (fg, bg));
+        assert_eq!(colors(&harness, 1), (fg, bg));
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::BTreeSet;
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+
+use crate::atlas::FontAtlasResource;
+use crate::TerminalLayout;
+
+/// Shows terminal `T`'s raw glyph atlas on screen, with lines on the tile
+/// boundaries, for diagnosing clipped, blurry or misplaced glyphs.
+#[derive(Resource, Clone, Debug)]
+pub struct AtlasDebugOverlay<T: 'static + Send + Sync> {
+    /// Toggle the overlay (default: false).
+    pub enabled: bool,
+    /// World-space top-left corner (default: None = just right of the grid).
+    pub position: Option<Vec2>,
+    /// Display size per atlas texel.
+    pub scale: f32,
+    /// Color of the tile boundary lines.
+    pub line_color: Color,
+    _marker: PhantomData<T>,
+}
+
+impl<T: 'static + Send + Sync> Default for AtlasDebugOverlay<T> {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            position: None,
+            scale: 1.0,
+            line_color: Color::srgba(1.0, 0.0, 1.0, 0.6),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Root entity of the overlay spawned by `debug_draw_atlas`.
+#[derive(Component)]
+pub struct AtlasDebugSprite<T: 'static + Send + Sync>(PhantomData<T>);
+
+/// Draw the overlay above everything the terminal draws.
+const OVERLAY_Z: f32 = 100.0;
+
+/// What the overlay was last built from, to rebuild only when it changes.
+#[derive(Clone, PartialEq)]
+pub struct OverlayKey {
+    image: AssetId<Image>,
+    tiles: usize,
+    position: Vec2,
+    scale: f32,
+    line_color: Color,
+}
+
+/// Spawns, rebuilds and removes the atlas overlay to match
+/// `AtlasDebugOverlay<T>` and the current atlas.
+pub fn debug_draw_atlas<T: 'static + Send + Sync>(
+    mut commands: Commands,
+    overlay: Res<AtlasDebugOverlay<T>>,
+    layout: Res<TerminalLayout<T>>,
+    atlas: Res<FontAtlasResource<T>>,
+    atlas_layouts: Res<Assets<TextureAtlasLayout>>,
+    existing: Query<Entity, With<AtlasDebugSprite<T>>>,
+    mut shown: Local<Option<OverlayKey>>,
+) {
+    let tex_layout = atlas_layouts.get(&atlas.layout);
+    let key = match tex_layout {
+        Some(tex_layout) if overlay.enabled => Some(OverlayKey {
+            image: atlas.image.id(),
+            tiles: tex_layout.textures.len(),
+            position: overlay
+                .position
+                .unwrap_or_else(|| layout.origin + Vec2::new(layout.pixel_width() + 16.0, 0.0)),
+            scale: overlay.scale,
+            line_color: overlay.line_color,
+        }),
+        _ => None,
+    };
+    if *shown == key {
+        return;
+    }
+
+    // Despawning the root takes its line children with it
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+    *shown = key.clone();
+    let (Some(key), Some(tex_layout)) = (key, tex_layout) else {
+        return;
+    };
+
+    let size = tex_layout.size.as_vec2() * key.scale;
+    let root = commands
+        .spawn((
+            AtlasDebugSprite::<T>(PhantomData),
+            Sprite::from_color(Color::srgb(0.05, 0.05, 0.05), size),
+            Anchor::TOP_LEFT,
+            Transform::from_translation(key.position.extend(OVERLAY_Z)),
+        ))
+        .id();
+
+    let mut children = vec![commands
+        .spawn((
+            Sprite {
+                image: atlas.image.clone(),
+                custom_size: Some(size),
+                ..default()
+            },
+            Anchor::TOP_LEFT,
+            Transform::from_xyz(0.0, 0.0, 0.1),
+        ))
+        .id()];
+
+    // One line per distinct tile edge; padding shows up as paired lines
+    let xs: BTreeSet<u32> = tex_layout.textures.iter().flat_map(|r| [r.min.x, r.max.x]).collect();
+    let ys: BTreeSet<u32> = tex_layout.textures.iter().flat_map(|r| [r.min.y, r.max.y]).collect();
+    for x in xs {
+        children.push(
+            commands
+                .spawn((
+                    Sprite::from_color(key.line_color, Vec2::new(1.0, size.y)),
+                    Anchor::TOP_LEFT,
+                    Transform::from_xyz(x as f32 * key.scale, 0.0, 0.2),
+                ))
+                .id(),
+        );
+    }
+    for y in ys {
+        children.push(
+            commands
+                .spawn((
+                    Sprite::from_color(key.line_color, Vec2::new(size.x, 1.0)),
+                    Anchor::TOP_LEFT,
+                    Transform::from_xyz(0.0, -(y as f32) * key.scale, 0.2),
+                ))
+                .id(),
+        );
+    }
+    commands.entity(root).add_children(&children);
+}
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 
-use super::{EffectRegion, TargetTerminal};
-use crate::grid::{GridPosition, TerminalCell};
+use super::{cell_in_effect, CellMask, EffectClock, EffectRegion, RunningEffect};
+use crate::grid::{CellStyle, GridPosition, TerminalCell};
 
 #[derive(Component, Clone, Debug)]
 pub struct Breathe {
@@ -23,18 +23,18 @@ impl Default for Breathe {
 }
 
 pub fn breathe_system<T: 'static + Send + Sync>(
-    time: Res<Time>,
-    effects: Query<(&Breathe, &EffectRegion), With<TargetTerminal<T>>>,
-    mut cells: Query<(&GridPosition, &mut Transform), With<TerminalCell<T>>>,
+    clock: Res<EffectClock<T>>,
+    effects: Query<(&Breathe, &EffectRegion, Option<&CellMask>), RunningEffect<T>>,
+    mut cells: Query<(&GridPosition, &CellStyle, &mut Transform), With<TerminalCell<T>>>,
 ) {
-    let t = time.elapsed_secs();
+    let t = clock.elapsed_secs();
 
-    for (breathe, region) in effects.iter() {
+    for (breathe, region, mask) in effects.iter() {
         let mid = (breathe.min_scale + breathe.max_scale) / 2.0;
         let range = (breathe.max_scale - breathe.min_scale) / 2.0;
 
-        for (pos, mut transform) in cells.iter_mut() {
-            if !region.contains(pos.col, pos.row) {
+        for (pos, style, mut transform) in cells.iter_mut() {
+            if !cell_in_effect(region, mask, pos.col, pos.row, style) {
                 continue;
             }
 
@@ -43,7 +43,7 @@ pub fn breathe_system<T: 'static + Send + Sync>(
             let wave = (std::f32::consts::TAU * breathe.speed * t + phase_offset).sin();
             let scale = mid + range * wave;
 
-            transform.scale = Vec3::splat(scale);
+            transform.scale *= Vec3::splat(scale);
         }
     }
 }
@@ -1,13 +1,15 @@
 use bevy::prelude::*;
 
-use super::{simple_hash, EffectRegion, TargetTerminal};
-use crate::grid::{GridPosition, TerminalCell};
+use super::{cell_in_effect, seeded_hash, CellMask, EffectClock, EffectRegion, RunningEffect};
+use crate::grid::{CellStyle, GridPosition, TerminalCell};
 
 #[derive(Component, Clone, Debug)]
 pub struct Bubbly {
     pub speed: f32,
     pub density: f32,
     pub max_scale: f32,
+    /// Mixed into the hash to pick a different set of bubbling cells (0 = original pattern).
+    pub seed: u32,
 }
 
 impl Default for Bubbly {
@@ -16,26 +18,27 @@ impl Default for Bubbly {
             speed: 0.8,
             density: 0.15,
             max_scale: 1.4,
+            seed: 0,
         }
     }
 }
 
 pub fn bubbly_system<T: 'static + Send + Sync>(
-    time: Res<Time>,
-    effects: Query<(&Bubbly, &EffectRegion), With<TargetTerminal<T>>>,
-    mut cells: Query<(&GridPosition, &mut Transform), With<TerminalCell<T>>>,
+    clock: Res<EffectClock<T>>,
+    effects: Query<(&Bubbly, &EffectRegion, Option<&CellMask>), RunningEffect<T>>,
+    mut cells: Query<(&GridPosition, &CellStyle, &mut Transform), With<TerminalCell<T>>>,
 ) {
-    let t = time.elapsed_secs();
+    let t = clock.elapsed_secs();
 
-    for (bubbly, region) in effects.iter() {
+    for (bubbly, region, mask) in effects.iter() {
         let threshold = (bubbly.density * 1000.0) as u32;
 
-        for (pos, mut transform) in cells.iter_mut() {
-            if !region.contains(pos.col, pos.row) {
+        for (pos, style, mut transform) in cells.iter_mut() {
+            if !cell_in_effect(region, mask, pos.col, pos.row, style) {
                 continue;
             }
 
-            let h = simple_hash(pos.col as u32, pos.row as u32);
+            let h = seeded_hash(pos.col as u32, pos.row as u32, bubbly.seed);
 
             if (h % 1000) >= threshold {
                 continue;
@@ -1,13 +1,50 @@
 use bevy::prelude::*;
 
-use super::{EffectRegion, TargetTerminal};
-use crate::grid::{GridPosition, TerminalCell};
+use super::{cell_in_effect, CellMask, EffectClock, EffectRegion, RunningEffect};
+use crate::grid::{CellStyle, GridPosition, TerminalCell};
+
+/// Direction `Collapse` pulls cells in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CollapseDir {
+    /// Fall toward the bottom of the screen (default).
+    #[default]
+    Down,
+    Up,
+    Left,
+    Right,
+}
+
+impl CollapseDir {
+    /// Unit vector of the fall in world space (+y up).
+    pub fn vector(self) -> Vec2 {
+        match self {
+            CollapseDir::Down => Vec2::NEG_Y,
+            CollapseDir::Up => Vec2::Y,
+            CollapseDir::Left => Vec2::NEG_X,
+            CollapseDir::Right => Vec2::X,
+        }
+    }
+}
+
+/// Grid axis whose index delays each cell's start in a `Collapse`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StaggerAxis {
+    /// Later rows start later (default).
+    #[default]
+    Row,
+    /// Later columns start later.
+    Column,
+}
 
 #[derive(Component, Clone, Debug)]
 pub struct Collapse {
     pub gravity: f32,
     pub elapsed: f32,
     pub duration: f32,
+    pub direction: CollapseDir,
+    pub stagger_axis: StaggerAxis,
+    /// Extra delay in seconds per step along `stagger_axis`; a row, unless
+    /// that's set to `StaggerAxis::Column`.
     pub stagger_per_row: f32,
     pub active: bool,
 }
@@ -18,38 +55,52 @@ impl Default for Collapse {
             gravity: 800.0,
             elapsed: 0.0,
             duration: 3.0,
+            direction: CollapseDir::Down,
+            stagger_axis: StaggerAxis::Row,
             stagger_per_row: 0.05,
             active: true,
         }
     }
 }
 
+impl Collapse {
+    /// Distance in pixels the cell at (col, row) has fallen so far.
+    pub fn fall(&self, col: u16, row: u16) -> f32 {
+        let step = match self.stagger_axis {
+            StaggerAxis::Row => row,
+            StaggerAxis::Column => col,
+        };
+        let t = (self.elapsed - step as f32 * self.stagger_per_row).max(0.0);
+        0.5 * self.gravity * t * t
+    }
+}
+
 pub fn collapse_system<T: 'static + Send + Sync>(
-    time: Res<Time>,
-    mut effects: Query<(&mut Collapse, &EffectRegion), With<TargetTerminal<T>>>,
-    mut cells: Query<(&GridPosition, &mut Transform), With<TerminalCell<T>>>,
+    clock: Res<EffectClock<T>>,
+    mut effects: Query<(&mut Collapse, &EffectRegion, Option<&CellMask>), RunningEffect<T>>,
+    mut cells: Query<(&GridPosition, &CellStyle, &mut Transform), With<TerminalCell<T>>>,
 ) {
-    for (mut collapse, region) in effects.iter_mut() {
+    for (mut collapse, region, mask) in effects.iter_mut() {
         if !collapse.active {
             continue;
         }
 
-        collapse.elapsed += time.delta_secs();
+        collapse.elapsed += clock.delta_secs();
 
         if collapse.elapsed > collapse.duration {
             collapse.active = false;
             continue;
         }
 
-        for (pos, mut transform) in cells.iter_mut() {
-            if !region.contains(pos.col, pos.row) {
+        let direction = collapse.direction.vector();
+        for (pos, style, mut transform) in cells.iter_mut() {
+            if !cell_in_effect(region, mask, pos.col, pos.row, style) {
                 continue;
             }
 
-            let row_delay = pos.row as f32 * collapse.stagger_per_row;
-            let t = (collapse.elapsed - row_delay).max(0.0);
-            let fall = 0.5 * collapse.gravity * t * t;
-            transform.translation.y -= fall;
+            let offset = direction * collapse.fall(pos.col, pos.row);
+            transform.translation.x += offset.x;
+            transform.translation.y += offset.y;
         }
     }
 }
@@ -1,7 +1,9 @@
 use bevy::prelude::*;
 
-use super::{simple_hash, EffectRegion, TargetTerminal};
-use crate::grid::{GridPosition, TerminalCell};
+use super::{
+    cell_in_effect, rotate_about, seeded_hash, CellMask, EffectClock, EffectRegion, RunningEffect,
+};
+use crate::grid::{CellStyle, GridPosition, TerminalCell};
 use crate::TerminalLayout;
 
 #[derive(Component, Clone, Debug)]
@@ -13,6 +15,14 @@ pub struct Explode {
     pub elapsed: f32,
     pub duration: f32,
     pub active: bool,
+    /// Mixed into the hash so instances scatter differently (0 = original pattern).
+    pub seed: u32,
+    /// Downward acceleration in pixels per second squared, so debris arcs
+    /// and falls (0 = straight lines).
+    pub gravity: f32,
+    /// Point each cell spins around, in cell sizes from its center
+    /// (default: the center). See `rotate_about`.
+    pub pivot: Vec2,
 }
 
 impl Default for Explode {
@@ -25,22 +35,32 @@ impl Default for Explode {
             elapsed: 0.0,
             duration: 2.5,
             active: true,
+            seed: 0,
+            gravity: 0.0,
+            pivot: Vec2::ZERO,
         }
     }
 }
 
+impl Explode {
+    /// Distance in pixels gravity has pulled every cell down so far.
+    pub fn fall(&self) -> f32 {
+        0.5 * self.gravity * self.elapsed * self.elapsed
+    }
+}
+
 pub fn explode_system<T: 'static + Send + Sync>(
-    time: Res<Time>,
+    clock: Res<EffectClock<T>>,
     layout: Res<TerminalLayout<T>>,
-    mut effects: Query<(&mut Explode, &EffectRegion), With<TargetTerminal<T>>>,
-    mut cells: Query<(&GridPosition, &mut Transform), With<TerminalCell<T>>>,
+    mut effects: Query<(&mut Explode, &EffectRegion, Option<&CellMask>), RunningEffect<T>>,
+    mut cells: Query<(&GridPosition, &CellStyle, &mut Transform), With<TerminalCell<T>>>,
 ) {
-    for (mut explode, region) in effects.iter_mut() {
+    for (mut explode, region, mask) in effects.iter_mut() {
         if !explode.active {
             continue;
         }
 
-        explode.elapsed += time.delta_secs();
+        explode.elapsed += clock.delta_secs();
 
         if explode.elapsed > explode.duration {
             explode.active = false;
@@ -49,18 +69,20 @@ pub fn explode_system<T: 'static + Send + Sync>(
 
         let t = explode.elapsed;
         let progress = t / explode.duration;
+        let fall = explode.fall();
+        let cell_size = Vec2::new(layout.cell_width, layout.cell_height);
 
-        for (pos, mut transform) in cells.iter_mut() {
-            if !region.contains(pos.col, pos.row) {
+        for (pos, style, mut transform) in cells.iter_mut() {
+            if !cell_in_effect(region, mask, pos.col, pos.row, style) {
                 continue;
             }
 
             let cell_id = pos.col as u32 * 1000 + pos.row as u32;
 
-            let h1 = simple_hash(cell_id, 111);
-            let h2 = simple_hash(cell_id, 222);
-            let h3 = simple_hash(cell_id, 333);
-            let h4 = simple_hash(cell_id, 444);
+            let h1 = seeded_hash(cell_id, 111, explode.seed);
+            let h2 = seeded_hash(cell_id, 222, explode.seed);
+            let h3 = seeded_hash(cell_id, 333, explode.seed);
+            let h4 = seeded_hash(cell_id, 444, explode.seed);
 
             let r1 = (h1 % 10000) as f32 / 10000.0;
             let r2 = (h2 % 10000) as f32 / 10000.0;
@@ -84,17 +106,17 @@ pub fn explode_system<T: 'static + Send + Sync>(
             let displacement = explode.force * speed_mult * t;
 
             transform.translation.x += dir_x * displacement;
-            transform.translation.y += dir_y * displacement;
+            transform.translation.y += dir_y * displacement - fall;
 
             let spin_dir = if r3 > 0.5 { 1.0 } else { -1.0 };
             let spin_speed = 2.0 + r3 * 6.0;
-            let angle = spin_dir * spin_speed * t;
-            transform.rotation = Quat::from_rotation_z(angle);
+            let spin = Quat::from_rotation_z(spin_dir * spin_speed * t);
+            rotate_about(&mut transform, spin, explode.pivot, cell_size);
 
             let timing_offset = (r4 - 0.5) * 0.3 * explode.chaos;
             let shrink_progress = (progress + timing_offset).clamp(0.0, 1.0);
             let scale = 1.0 - shrink_progress;
-            transform.scale = Vec3::splat(scale.max(0.0));
+            transform.scale *= Vec3::splat(scale.max(0.0));
         }
     }
 }
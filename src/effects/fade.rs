@@ -0,0 +1,114 @@
+use bevy::prelude::*;
+
+use super::{cell_in_effect, CellMask, EffectClock, EffectFinished, EffectRegion, RunningEffect};
+use crate::grid::{CellEntityIndex, CellOpacity, CellStyle, TerminalCell};
+
+/// Fade transition that scales foreground and background alpha.
+///
+/// One-shot: the alpha multiplier moves from `from` to `to` over `duration`,
+/// then holds at `to` until the component is removed. Sends
+/// `EffectFinished` when the transition completes.
+#[derive(Component, Clone, Debug)]
+pub struct Fade {
+    /// Alpha multiplier at the start (0.0 = invisible, 1.0 = unchanged).
+    pub from: f32,
+    /// Alpha multiplier at the end.
+    pub to: f32,
+    /// Length of the transition in seconds.
+    pub duration: f32,
+    /// How long the fade has been running.
+    pub elapsed: f32,
+    /// Cleared once the transition completes and `EffectFinished` is sent.
+    pub active: bool,
+}
+
+impl Default for Fade {
+    fn default() -> Self {
+        Self::fade_in(1.0)
+    }
+}
+
+impl Fade {
+    /// Fade from invisible to fully visible.
+    pub fn fade_in(duration: f32) -> Self {
+        Self {
+            from: 0.0,
+            to: 1.0,
+            duration,
+            elapsed: 0.0,
+            active: true,
+        }
+    }
+
+    /// Fade from fully visible to invisible.
+    pub fn fade_out(duration: f32) -> Self {
+        Self {
+            from: 1.0,
+            to: 0.0,
+            duration,
+            elapsed: 0.0,
+            active: true,
+        }
+    }
+
+    /// Current alpha multiplier.
+    pub fn alpha(&self) -> f32 {
+        let progress = if self.duration > 0.0 {
+            (self.elapsed / self.duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        self.from + (self.to - self.from) * progress
+    }
+
+    /// Whether the transition has reached `to`.
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
+
+/// System that applies fades to cell sprite alpha.
+///
+/// Scales the background alpha that `reset_colors` restored this frame and
+/// the glyph's `CellOpacity`, so the fade never compounds across frames.
+pub fn fade_system<T: 'static + Send + Sync>(
+    clock: Res<EffectClock<T>>,
+    mut effects: Query<(Entity, &mut Fade, &EffectRegion, Option<&CellMask>), RunningEffect<T>>,
+    cell_index: Res<CellEntityIndex<T>>,
+    mut cells: Query<(&CellStyle, &mut Sprite, &mut CellOpacity), With<TerminalCell<T>>>,
+    mut finished: MessageWriter<EffectFinished>,
+) {
+    let columns = cell_index.columns as usize;
+
+    for (entity, mut fade, region, mask) in effects.iter_mut() {
+        // Checked after advancing, so a zero `duration` still reports
+        if fade.active {
+            fade.elapsed += clock.delta_secs();
+            if fade.is_finished() {
+                fade.active = false;
+                finished.write(EffectFinished { effect: entity });
+            }
+        }
+
+        let alpha = fade.alpha().clamp(0.0, 1.0);
+        if alpha == 1.0 {
+            continue;
+        }
+
+        for (idx, &parent_entity) in cell_index.entities.iter().enumerate() {
+            let col = (idx % columns) as u16;
+            let row = (idx / columns) as u16;
+
+            let Ok((style, mut bg_sprite, mut opacity)) = cells.get_mut(parent_entity) else {
+                continue;
+            };
+            if !cell_in_effect(region, mask, col, row, style) {
+                continue;
+            }
+
+            let bg_alpha = bg_sprite.color.alpha() * alpha;
+            bg_sprite.color.set_alpha(bg_alpha);
+            opacity.0 *= alpha;
+        }
+    }
+}
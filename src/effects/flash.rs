@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use bevy::color::Mix;
+use bevy::prelude::*;
+
+use super::{cell_in_effect, CellMask, EffectClock, EffectRegion, RunningEffect};
+use crate::grid::{CellEntityIndex, CellStyle, GridPosition, TerminalCell};
+
+/// Briefly tints a cell's background whenever sync writes new content to it,
+/// e.g. a changed value in a live table.
+///
+/// The first draw after the grid is spawned or resized doesn't flash.
+#[derive(Component, Clone, Debug)]
+pub struct FlashOnChange {
+    /// Color the background is tinted toward.
+    pub flash_color: Color,
+    /// Seconds for the tint to fade out.
+    pub decay: f32,
+    /// Peak blend toward `flash_color` (0.0..=1.0).
+    pub strength: f32,
+}
+
+impl Default for FlashOnChange {
+    fn default() -> Self {
+        Self {
+            flash_color: Color::srgb(1.0, 0.85, 0.3),
+            decay: 0.6,
+            strength: 0.7,
+        }
+    }
+}
+
+/// Running flashes for a terminal: cell -> (flashing effect, seconds since
+/// the change).
+#[derive(Resource)]
+pub struct FlashTimers<T: 'static + Send + Sync> {
+    pub timers: HashMap<GridPosition, (Entity, f32)>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: 'static + Send + Sync> Default for FlashTimers<T> {
+    fn default() -> Self {
+        Self {
+            timers: HashMap::new(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Starts a flash on every targeted cell whose `CellStyle` changed this
+/// frame, and fades running flashes out.
+pub fn flash_on_change_system<T: 'static + Send + Sync>(
+    clock: Res<EffectClock<T>>,
+    effects: Query<
+        (Entity, &FlashOnChange, &EffectRegion, Option<&CellMask>),
+        RunningEffect<T>,
+    >,
+    changed: Query<(&GridPosition, Ref<CellStyle>), (With<TerminalCell<T>>, Changed<CellStyle>)>,
+    cell_index: Res<CellEntityIndex<T>>,
+    mut timers: ResMut<FlashTimers<T>>,
+    mut sprites: Query<&mut Sprite, With<TerminalCell<T>>>,
+) {
+    if effects.is_empty() && timers.timers.is_empty() {
+        return;
+    }
+
+    let dt = clock.delta_secs();
+    for (_, elapsed) in timers.timers.values_mut() {
+        *elapsed += dt;
+    }
+
+    for (pos, style) in changed.iter() {
+        // Freshly spawned cells get their first content; that's not a change
+        if style.is_added() {
+            continue;
+        }
+        let hit = effects
+            .iter()
+            .find(|(_, _, region, mask)| cell_in_effect(region, *mask, pos.col, pos.row, &style));
+        if let Some((entity, ..)) = hit {
+            timers.timers.insert(*pos, (entity, 0.0));
+        }
+    }
+
+    timers.timers.retain(|pos, (effect, elapsed)| {
+        let Ok((_, flash, ..)) = effects.get(*effect) else {
+            return false;
+        };
+        let remaining = if flash.decay > 0.0 {
+            1.0 - *elapsed / flash.decay
+        } else {
+            0.0
+        };
+        if remaining <= 0.0 {
+            return false;
+        }
+        let cell = cell_index.get(pos.col, pos.row);
+        if let Some(mut sprite) = cell.and_then(|entity| sprites.get_mut(entity).ok()) {
+            sprite.color = sprite.color.mix(&flash.flash_color, flash.strength * remaining);
+        }
+        true
+    });
+}
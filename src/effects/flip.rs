@@ -0,0 +1,150 @@
+use bevy::prelude::*;
+
+use super::{cell_in_effect, CellMask, EffectClock, EffectFinished, EffectRegion, RunningEffect};
+use crate::atlas::FontAtlasResource;
+use crate::grid::{CellEntityIndex, CellStyle, ForegroundSprite, GridPosition, TerminalCell};
+
+/// Axis a `Flip` turns cells around.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FlipAxis {
+    /// Turn around the horizontal axis; cells squash vertically.
+    X,
+    /// Turn around the vertical axis; cells squash horizontally (default).
+    #[default]
+    Y,
+}
+
+/// Flip-card transition: each cell turns half a revolution around `axis`,
+/// shown by squashing it to nothing and back out. Past the halfway point the
+/// cell shows its back side, tinted with `back_fg`/`back_bg` and drawn as
+/// `back_glyph` if set, until the component is removed.
+///
+/// One-shot: sends `EffectFinished` once every targeted cell has turned.
+#[derive(Component, Clone, Debug)]
+pub struct Flip {
+    pub axis: FlipAxis,
+    /// Seconds for one cell to turn over.
+    pub duration: f32,
+    /// Extra delay per step along the diagonal (col + row), for a sweep.
+    pub per_cell_stagger: f32,
+    /// Glyph color on the back side (None = unchanged).
+    pub back_fg: Option<Color>,
+    /// Background color on the back side (None = unchanged).
+    pub back_bg: Option<Color>,
+    /// Char drawn on the back side instead of the cell's own (None =
+    /// unchanged), e.g. a card back.
+    pub back_glyph: Option<char>,
+    pub elapsed: f32,
+    pub active: bool,
+}
+
+impl Default for Flip {
+    fn default() -> Self {
+        Self {
+            axis: FlipAxis::Y,
+            duration: 0.6,
+            per_cell_stagger: 0.02,
+            back_fg: None,
+            back_bg: None,
+            back_glyph: None,
+            elapsed: 0.0,
+            active: true,
+        }
+    }
+}
+
+impl Flip {
+    /// How far the cell at (col, row) has turned, 0.0..=1.0.
+    pub fn progress(&self, col: u16, row: u16) -> f32 {
+        let delay = (col as f32 + row as f32) * self.per_cell_stagger;
+        if self.duration <= 0.0 {
+            return if self.elapsed >= delay { 1.0 } else { 0.0 };
+        }
+        ((self.elapsed - delay) / self.duration).clamp(0.0, 1.0)
+    }
+
+    /// Apparent size along the squashed axis for a given progress.
+    pub fn squash(progress: f32) -> f32 {
+        (progress * std::f32::consts::PI).cos().abs()
+    }
+}
+
+pub fn flip_system<T: 'static + Send + Sync>(
+    clock: Res<EffectClock<T>>,
+    mut effects: Query<(Entity, &mut Flip, &EffectRegion, Option<&CellMask>), RunningEffect<T>>,
+    mut cells: Query<(&GridPosition, &CellStyle, &mut Transform), With<TerminalCell<T>>>,
+    mut finished: MessageWriter<EffectFinished>,
+) {
+    for (entity, mut flip, region, mask) in effects.iter_mut() {
+        if !flip.active {
+            continue;
+        }
+        flip.elapsed += clock.delta_secs();
+
+        let mut done = true;
+        for (pos, style, mut transform) in cells.iter_mut() {
+            if !cell_in_effect(region, mask, pos.col, pos.row, style) {
+                continue;
+            }
+            let progress = flip.progress(pos.col, pos.row);
+            done &= progress >= 1.0;
+            let squash = Flip::squash(progress);
+            match flip.axis {
+                FlipAxis::X => transform.scale.y *= squash,
+                FlipAxis::Y => transform.scale.x *= squash,
+            }
+        }
+
+        if done {
+            flip.active = false;
+            finished.write(EffectFinished { effect: entity });
+        }
+    }
+}
+
+/// Applies the back-side colors and glyph to cells that have turned past
+/// halfway. `reset_colors` restores the cell's own glyph once they're gone.
+pub fn flip_color_system<T: 'static + Send + Sync>(
+    effects: Query<(&Flip, &EffectRegion, Option<&CellMask>), RunningEffect<T>>,
+    cell_index: Res<CellEntityIndex<T>>,
+    mut atlas: Option<ResMut<FontAtlasResource<T>>>,
+    mut cells: Query<(&GridPosition, &CellStyle, &mut Sprite), With<TerminalCell<T>>>,
+    mut fg_sprites: Query<&mut Sprite, (With<ForegroundSprite<T>>, Without<TerminalCell<T>>)>,
+) {
+    for (flip, region, mask) in effects.iter() {
+        if flip.back_fg.is_none() && flip.back_bg.is_none() && flip.back_glyph.is_none() {
+            continue;
+        }
+        // Queue a back glyph the atlas doesn't have yet; it shows once added
+        let back_index = flip.back_glyph.and_then(|ch| {
+            let atlas = atlas.as_mut()?;
+            let index = atlas.glyph_map.get(&ch).copied();
+            if index.is_none() {
+                atlas.pending_glyphs.insert(ch);
+            }
+            index
+        });
+        for (idx, &parent_entity) in cell_index.entities.iter().enumerate() {
+            let Ok((pos, style, mut bg_sprite)) = cells.get_mut(parent_entity) else {
+                continue;
+            };
+            if !cell_in_effect(region, mask, pos.col, pos.row, style)
+                || flip.progress(pos.col, pos.row) < 0.5
+            {
+                continue;
+            }
+            if let Some(bg) = flip.back_bg {
+                bg_sprite.color = bg;
+            }
+            let Ok(mut fg_sprite) = fg_sprites.get_mut(cell_index.fg_entities[idx]) else {
+                continue;
+            };
+            if let Some(fg) = flip.back_fg {
+                fg_sprite.color = fg.with_alpha(fg.alpha() * fg_sprite.color.alpha());
+            }
+            if let (Some(index), Some(tile)) = (back_index, fg_sprite.texture_atlas.as_mut()) {
+                tile.index = index;
+            }
+        }
+    }
+}
@@ -1,7 +1,15 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use bevy::camera::visibility::RenderLayers;
 use bevy::prelude::*;
 
-use super::{simple_hash, EffectRegion, TargetTerminal};
-use crate::grid::{GridPosition, TerminalCell};
+use super::{cell_in_effect, seeded_hash, CellMask, EffectClock, EffectRegion, RunningEffect};
+use crate::atlas::FontAtlasResource;
+use crate::grid::{
+    glyph_translation, CellEntityIndex, CellStyle, ForegroundSprite, GridPosition, TerminalCell,
+};
+use crate::{TerminalConfig, TerminalLayout};
 
 #[derive(Component, Clone, Debug)]
 pub struct Glitch {
@@ -9,6 +17,13 @@ pub struct Glitch {
     pub intensity: f32,
     pub frequency: f32,
     pub active: bool,
+    /// Mixed into the hash so instances glitch differently (0 = original pattern).
+    pub seed: u32,
+    /// Distance in pixels the red and blue copies of each glyph are split
+    /// apart on glitched rows (0 = no chromatic aberration).
+    pub channel_offset: f32,
+    /// Opacity of the red/blue copies (0.0..=1.0).
+    pub aberration_intensity: f32,
 }
 
 impl Default for Glitch {
@@ -18,36 +33,182 @@ impl Default for Glitch {
             intensity: 0.3,
             frequency: 8.0,
             active: true,
+            seed: 0,
+            channel_offset: 0.0,
+            aberration_intensity: 0.6,
+        }
+    }
+}
+
+impl Glitch {
+    /// Horizontal shift in -1.0..1.0 (scaled by `max_offset`) if `row` is
+    /// glitched at time `t`, otherwise `None`.
+    pub fn row_shift(&self, row: u16, t: f32) -> Option<f32> {
+        let time_slot = (t * self.frequency) as u32;
+        let row_hash = seeded_hash(row as u32, time_slot, self.seed);
+        let row_frac = (row_hash % 1000) as f32 / 1000.0;
+        if row_frac >= self.intensity {
+            return None;
         }
+        let offset_hash = seeded_hash(row as u32, time_slot.wrapping_add(7919), self.seed);
+        Some((offset_hash % 2000) as f32 / 1000.0 - 1.0)
+    }
+}
+
+/// Red/blue copy of a glyph drawn behind it on glitched rows. Spawned lazily
+/// as a child of the cell by `glitch_aberration_system`.
+#[derive(Component)]
+pub struct ChromaGhost<T: 'static + Send + Sync>(PhantomData<T>);
+
+impl<T: 'static + Send + Sync> Default for ChromaGhost<T> {
+    fn default() -> Self {
+        Self(PhantomData)
     }
 }
 
 pub fn glitch_system<T: 'static + Send + Sync>(
-    time: Res<Time>,
-    effects: Query<(&Glitch, &EffectRegion), With<TargetTerminal<T>>>,
-    mut cells: Query<(&GridPosition, &mut Transform), With<TerminalCell<T>>>,
+    clock: Res<EffectClock<T>>,
+    effects: Query<(&Glitch, &EffectRegion, Option<&CellMask>), RunningEffect<T>>,
+    mut cells: Query<(&GridPosition, &CellStyle, &mut Transform), With<TerminalCell<T>>>,
 ) {
-    let t = time.elapsed_secs();
+    let t = clock.elapsed_secs();
 
-    for (glitch, region) in effects.iter() {
+    for (glitch, region, mask) in effects.iter() {
         if !glitch.active {
             continue;
         }
 
-        let time_slot = (t * glitch.frequency) as u32;
+        for (pos, style, mut transform) in cells.iter_mut() {
+            if !cell_in_effect(region, mask, pos.col, pos.row, style) {
+                continue;
+            }
+
+            if let Some(shift) = glitch.row_shift(pos.row, t) {
+                transform.translation.x += shift * glitch.max_offset;
+            }
+        }
+    }
+}
+
+/// Shows offset red and blue copies of the glyphs on rows `glitch_system`
+/// shifted this frame, for a CRT-style RGB split. Copies are spawned on
+/// first use and hidden, not despawned, when their row stops glitching.
+pub fn glitch_aberration_system<T: 'static + Send + Sync>(
+    mut commands: Commands,
+    clock: Res<EffectClock<T>>,
+    config: Res<TerminalConfig<T>>,
+    layout: Res<TerminalLayout<T>>,
+    atlas: Res<FontAtlasResource<T>>,
+    cell_index: Res<CellEntityIndex<T>>,
+    effects: Query<(&Glitch, &EffectRegion, Option<&CellMask>), RunningEffect<T>>,
+    cells: Query<(&GridPosition, &CellStyle), With<TerminalCell<T>>>,
+    fg_sprites: Query<&Sprite, (With<ForegroundSprite<T>>, Without<ChromaGhost<T>>)>,
+    mut ghosts: Query<
+        (&mut Sprite, &mut Transform, &mut Visibility),
+        (With<ChromaGhost<T>>, Without<ForegroundSprite<T>>),
+    >,
+    mut ghost_map: Local<HashMap<Entity, [Entity; 2]>>,
+) {
+    let t = clock.elapsed_secs();
 
-        for (pos, mut transform) in cells.iter_mut() {
-            if !region.contains(pos.col, pos.row) {
+    // Cell index -> (signed split distance, ghost alpha) for this frame
+    let mut wanted: HashMap<usize, (f32, f32)> = HashMap::new();
+    for (glitch, region, mask) in effects.iter() {
+        if !glitch.active || glitch.channel_offset == 0.0 || glitch.aberration_intensity <= 0.0 {
+            continue;
+        }
+        for (idx, &entity) in cell_index.entities.iter().enumerate() {
+            let Ok((pos, style)) = cells.get(entity) else {
                 continue;
+            };
+            if !cell_in_effect(region, mask, pos.col, pos.row, style) {
+                continue;
+            }
+            if let Some(shift) = glitch.row_shift(pos.row, t) {
+                // Split along the direction the row was shoved
+                let split = glitch.channel_offset * if shift < 0.0 { -1.0 } else { 1.0 };
+                wanted.insert(idx, (split, glitch.aberration_intensity.min(1.0)));
+            }
+        }
+    }
+
+    if wanted.is_empty() && ghost_map.is_empty() {
+        return;
+    }
+
+    // Forget ghosts despawned along with their cell (e.g. on resize)
+    ghost_map.retain(|_, pair| ghosts.contains(pair[0]) && ghosts.contains(pair[1]));
+
+    let wanted_cells: HashMap<Entity, usize> = wanted
+        .keys()
+        .map(|&idx| (cell_index.entities[idx], idx))
+        .collect();
+    for (cell, pair) in ghost_map.iter() {
+        if wanted_cells.contains_key(cell) {
+            continue;
+        }
+        for &ghost in pair {
+            if let Ok((_, _, mut visibility)) = ghosts.get_mut(ghost) {
+                if *visibility != Visibility::Hidden {
+                    *visibility = Visibility::Hidden;
+                }
             }
+        }
+    }
+
+    let ghost_z = config.fg_z_offset * 0.5;
+    let glyph_offset = glyph_translation(&config, &layout, &atlas).extend(0.0);
+    for (idx, (split, alpha)) in wanted {
+        let cell = cell_index.entities[idx];
+        let Ok(fg) = fg_sprites.get(cell_index.fg_entities[idx]) else {
+            continue;
+        };
+        let channels = [
+            (Color::srgba(1.0, 0.0, 0.0, alpha), -split),
+            (Color::srgba(0.0, 0.4, 1.0, alpha), split),
+        ];
 
-            let row_hash = simple_hash(pos.row as u32, time_slot);
-            let row_frac = (row_hash % 1000) as f32 / 1000.0;
+        let Some(pair) = ghost_map.get(&cell) else {
+            let pair = channels.map(|(color, x)| {
+                let mut ghost = commands.spawn((
+                    ChromaGhost::<T>::default(),
+                    Sprite {
+                        color,
+                        ..fg.clone()
+                    },
+                    Transform::from_translation(glyph_offset + Vec3::new(x, 0.0, ghost_z)),
+                    Visibility::Inherited,
+                ));
+                if let Some(layer) = config.render_layer {
+                    ghost.insert(RenderLayers::layer(layer as usize));
+                }
+                ghost.id()
+            });
+            commands.entity(cell).add_children(&pair);
+            ghost_map.insert(cell, pair);
+            continue;
+        };
 
-            if row_frac < glitch.intensity {
-                let offset_hash = simple_hash(pos.row as u32, time_slot.wrapping_add(7919));
-                let offset_frac = (offset_hash % 2000) as f32 / 1000.0 - 1.0;
-                transform.translation.x += offset_frac * glitch.max_offset;
+        for (&ghost, (color, x)) in pair.iter().zip(channels) {
+            let Ok((mut sprite, mut transform, mut visibility)) = ghosts.get_mut(ghost) else {
+                continue;
+            };
+            if *visibility != Visibility::Inherited {
+                *visibility = Visibility::Inherited;
+            }
+            let index = fg.texture_atlas.as_ref().map(|a| a.index);
+            if sprite.texture_atlas.as_ref().map(|a| a.index) != index
+                || sprite.image != fg.image
+                || sprite.custom_size != fg.custom_size
+            {
+                *sprite = fg.clone();
+            }
+            if sprite.color != color {
+                sprite.color = color;
+            }
+            let translation = glyph_offset + Vec3::new(x, 0.0, ghost_z);
+            if transform.translation != translation {
+                transform.translation = translation;
             }
         }
     }
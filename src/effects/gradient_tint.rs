@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+
+use super::{cell_in_effect, CellMask, EffectClock, EffectRegion, RunningEffect};
+use crate::color::{sample_gradient, TintMode};
+use crate::grid::{CellEntityIndex, CellStyle, ForegroundSprite, TerminalCell};
+
+/// Gradient color-cycling effect.
+///
+/// Like `Rainbow`, but sweeps foreground sprites through a user-supplied
+/// palette instead of the HSL hue wheel.
+#[derive(Component, Clone, Debug)]
+pub struct GradientTint {
+    /// Gradient stops as `(position, color)` with positions in 0.0..=1.0,
+    /// sorted. Repeat the first color at 1.0 for a seamless loop.
+    pub stops: Vec<(f32, Color)>,
+    /// Speed the gradient scrolls across the grid (cycles per second).
+    pub speed: f32,
+    /// Spatial spread — how far along the gradient each grid step moves.
+    pub spread: f32,
+    /// How far to blend toward the gradient color (0.0 = none, 1.0 = replace).
+    pub strength: f32,
+    /// How the gradient color combines with the glyph's (default: `Mix`,
+    /// a crossfade by `strength`). `Multiply` darkens and `Add` blooms.
+    pub mode: TintMode,
+}
+
+impl Default for GradientTint {
+    fn default() -> Self {
+        // Fire palette
+        Self {
+            stops: vec![
+                (0.0, Color::srgb(0.8, 0.1, 0.0)),
+                (0.5, Color::srgb(1.0, 0.8, 0.2)),
+                (1.0, Color::srgb(0.8, 0.1, 0.0)),
+            ],
+            speed: 0.5,
+            spread: 0.05,
+            strength: 1.0,
+            mode: TintMode::Mix,
+        }
+    }
+}
+
+/// System that applies the gradient tint to foreground sprite colors.
+pub fn gradient_tint_system<T: 'static + Send + Sync>(
+    clock: Res<EffectClock<T>>,
+    effects: Query<(&GradientTint, &EffectRegion, Option<&CellMask>), RunningEffect<T>>,
+    cell_index: Res<CellEntityIndex<T>>,
+    styles: Query<&CellStyle, With<TerminalCell<T>>>,
+    mut sprites: Query<&mut Sprite, With<ForegroundSprite<T>>>,
+) {
+    let t = clock.elapsed_secs();
+    let columns = cell_index.columns as usize;
+
+    for (tint, region, mask) in effects.iter() {
+        for (idx, &fg_entity) in cell_index.fg_entities.iter().enumerate() {
+            let col = (idx % columns) as u16;
+            let row = (idx / columns) as u16;
+
+            let Ok(style) = styles.get(cell_index.entities[idx]) else {
+                continue;
+            };
+            if !cell_in_effect(region, mask, col, row, style) {
+                continue;
+            }
+
+            let position =
+                ((col as f32 + row as f32) * tint.spread + t * tint.speed).rem_euclid(1.0);
+            let Some(color) = sample_gradient(&tint.stops, position) else {
+                continue;
+            };
+
+            if let Ok(mut sprite) = sprites.get_mut(fg_entity) {
+                sprite.color = tint.mode.apply(sprite.color, color, tint.strength);
+            }
+        }
+    }
+}
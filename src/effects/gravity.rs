@@ -1,11 +1,23 @@
+use std::collections::HashMap;
+
 use bevy::prelude::*;
 
-use super::{EffectRegion, TargetTerminal};
-use crate::grid::{GridPosition, TerminalCell};
+use super::{cell_in_effect, CellMask, EffectClock, EffectRegion, RunningEffect};
+use crate::culling::is_blank;
+use crate::grid::{BaseTransform, CellStyle, GridPosition, TerminalCell};
+use crate::TerminalLayout;
 
+/// Per-cell motion state integrated by `Gravity`. Insert it on the cells to
+/// animate and remove it to snap them back. It lives on the cell entity, so
+/// each terminal's cells keep their own and only that terminal's `Gravity`
+/// (per `TargetTerminal<T>`) moves them.
 #[derive(Component, Clone, Copy, Debug, Default)]
 pub struct CellVelocity {
     pub velocity: Vec2,
+    /// Accumulated displacement from the cell's base position, in pixels.
+    pub offset: Vec2,
+    /// Set once the cell has come to rest on `Gravity::floor_row`.
+    pub settled: bool,
 }
 
 #[derive(Component, Clone, Debug)]
@@ -13,6 +25,13 @@ pub struct Gravity {
     pub acceleration: Vec2,
     pub damping: f32,
     pub active: bool,
+    /// Row cells land on instead of falling off screen. Cells in the same
+    /// column pile up on top of each other, keeping their order. Blank cells
+    /// stay put and don't take up room in the pile.
+    pub floor_row: Option<u16>,
+    /// Fraction of vertical speed kept when bouncing off the floor
+    /// (0.0 = no bounce).
+    pub restitution: f32,
 }
 
 impl Default for Gravity {
@@ -21,39 +40,95 @@ impl Default for Gravity {
             acceleration: Vec2::new(0.0, -200.0),
             damping: 0.0,
             active: true,
+            floor_row: None,
+            restitution: 0.3,
         }
     }
 }
 
+/// Bounces slower than this (pixels per second) come to rest.
+const SETTLE_SPEED: f32 = 20.0;
+
 pub fn gravity_system<T: 'static + Send + Sync>(
-    time: Res<Time>,
-    effects: Query<(&Gravity, &EffectRegion), With<TargetTerminal<T>>>,
+    clock: Res<EffectClock<T>>,
+    layout: Res<TerminalLayout<T>>,
+    effects: Query<(&Gravity, &EffectRegion, Option<&CellMask>), RunningEffect<T>>,
     mut cells: Query<
-        (&GridPosition, &mut Transform, &mut CellVelocity),
+        (
+            &GridPosition,
+            &CellStyle,
+            &BaseTransform,
+            &mut Transform,
+            &mut CellVelocity,
+        ),
         With<TerminalCell<T>>,
     >,
 ) {
-    let dt = time.delta_secs();
+    let (steps, dt) = (clock.steps(), clock.step_secs());
 
-    for (gravity, region) in effects.iter() {
+    for (gravity, region, mask) in effects.iter() {
         if !gravity.active {
             continue;
         }
 
-        for (pos, mut transform, mut vel) in cells.iter_mut() {
-            if !region.contains(pos.col, pos.row) {
+        // Rows of the falling cells in each column, for stacking on the floor
+        let falls = |pos: &GridPosition, style: &CellStyle| {
+            !is_blank(&style.symbol) && cell_in_effect(region, mask, pos.col, pos.row, style)
+        };
+        let mut columns: HashMap<u16, Vec<u16>> = HashMap::new();
+        if let Some(floor) = gravity.floor_row {
+            for (pos, style, ..) in cells.iter() {
+                if pos.row <= floor && falls(pos, style) {
+                    columns.entry(pos.col).or_default().push(pos.row);
+                }
+            }
+            for rows in columns.values_mut() {
+                rows.sort_unstable();
+            }
+        }
+
+        for (pos, style, base, mut transform, mut vel) in cells.iter_mut() {
+            if !falls(pos, style) {
                 continue;
             }
 
-            vel.velocity += gravity.acceleration * dt;
+            // One update per fixed step, so the path doesn't depend on frame rate
+            for _ in 0..steps {
+                if vel.settled {
+                    break;
+                }
+                vel.velocity += gravity.acceleration * dt;
+
+                if gravity.damping > 0.0 {
+                    let damping_factor = (1.0 - gravity.damping).powf(dt);
+                    vel.velocity *= damping_factor;
+                }
+
+                let step = vel.velocity * dt;
+                vel.offset += step;
 
-            if gravity.damping > 0.0 {
-                let damping_factor = (1.0 - gravity.damping).powf(dt);
-                vel.velocity *= damping_factor;
+                if let Some(floor) = gravity.floor_row {
+                    // Land on top of the targeted cells below in this column.
+                    // Cells already under the floor rest where they are.
+                    let below = columns
+                        .get(&pos.col)
+                        .map_or(0, |rows| rows.len() - rows.partition_point(|&r| r <= pos.row));
+                    let landing_row = floor.saturating_sub(below as u16).max(pos.row);
+                    let floor_y = layout.grid_to_world(pos.col, landing_row).y;
+                    let floor_offset = floor_y - base.translation.y;
+                    if vel.offset.y <= floor_offset {
+                        vel.offset.y = floor_offset;
+                        vel.velocity.y = -vel.velocity.y * gravity.restitution;
+                        if vel.velocity.y.abs() < SETTLE_SPEED {
+                            vel.velocity = Vec2::ZERO;
+                            vel.settled = true;
+                        }
+                    }
+                }
             }
 
-            transform.translation.x += vel.velocity.x * dt;
-            transform.translation.y += vel.velocity.y * dt;
+            transform.translation.x += vel.offset.x;
+            transform.translation.y += vel.offset.y;
         }
     }
 }
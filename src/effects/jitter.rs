@@ -1,7 +1,11 @@
 use bevy::prelude::*;
 
-use super::{simple_hash, EffectRegion, TargetTerminal};
-use crate::grid::{GridPosition, TerminalCell};
+use super::{
+    cell_in_effect, envelope_gain, rotate_about, seeded_hash, CellMask, EffectClock,
+    EffectEnvelope, EffectRegion, RunningEffect,
+};
+use crate::grid::{CellStyle, GridPosition, TerminalCell};
+use crate::TerminalLayout;
 
 /// Per-cell random vibration effect.
 ///
@@ -16,6 +20,11 @@ pub struct Jitter {
     pub rotate: bool,
     /// Maximum rotation in radians (when `rotate` is true).
     pub max_rotation: f32,
+    /// Point each cell wobbles around, in cell sizes from its center
+    /// (default: the center). See `rotate_about`.
+    pub pivot: Vec2,
+    /// Mixed into the hash so instances jitter differently (0 = original pattern).
+    pub seed: u32,
 }
 
 impl Default for Jitter {
@@ -25,43 +34,58 @@ impl Default for Jitter {
             speed: 20.0,
             rotate: true,
             max_rotation: 0.05,
+            pivot: Vec2::ZERO,
+            seed: 0,
         }
     }
 }
 
 /// System that applies the jitter effect to cell transforms.
 pub fn jitter_system<T: 'static + Send + Sync>(
-    time: Res<Time>,
-    effects: Query<(&Jitter, &EffectRegion), With<TargetTerminal<T>>>,
-    mut cells: Query<(&GridPosition, &mut Transform), With<TerminalCell<T>>>,
+    clock: Res<EffectClock<T>>,
+    layout: Res<TerminalLayout<T>>,
+    effects: Query<
+        (
+            &Jitter,
+            &EffectRegion,
+            Option<&CellMask>,
+            Option<&EffectEnvelope>,
+        ),
+        RunningEffect<T>,
+    >,
+    mut cells: Query<(&GridPosition, &CellStyle, &mut Transform), With<TerminalCell<T>>>,
 ) {
-    let t = time.elapsed_secs();
+    let t = clock.elapsed_secs();
 
-    for (jitter, region) in effects.iter() {
+    for (jitter, region, mask, envelope) in effects.iter() {
         let time_slot = (t * jitter.speed) as u32;
+        let gain = envelope_gain(envelope);
+        let amplitude = jitter.amplitude * gain;
+        let cell_size = Vec2::new(layout.cell_width, layout.cell_height);
 
-        for (pos, mut transform) in cells.iter_mut() {
-            if !region.contains(pos.col, pos.row) {
+        for (pos, style, mut transform) in cells.iter_mut() {
+            if !cell_in_effect(region, mask, pos.col, pos.row, style) {
                 continue;
             }
 
             let cell_id = pos.row as u32 * 1000 + pos.col as u32;
 
             // X offset
-            let hx = simple_hash(cell_id, time_slot);
+            let hx = seeded_hash(cell_id, time_slot, jitter.seed);
             let dx = (hx % 2000) as f32 / 1000.0 - 1.0; // -1.0 to 1.0
-            transform.translation.x += dx * jitter.amplitude;
+            transform.translation.x += dx * amplitude;
 
             // Y offset
-            let hy = simple_hash(cell_id, time_slot.wrapping_add(3571));
+            let hy = seeded_hash(cell_id, time_slot.wrapping_add(3571), jitter.seed);
             let dy = (hy % 2000) as f32 / 1000.0 - 1.0;
-            transform.translation.y += dy * jitter.amplitude;
+            transform.translation.y += dy * amplitude;
 
             // Optional rotation
             if jitter.rotate {
-                let hr = simple_hash(cell_id, time_slot.wrapping_add(6947));
+                let hr = seeded_hash(cell_id, time_slot.wrapping_add(6947), jitter.seed);
                 let r = (hr % 2000) as f32 / 1000.0 - 1.0;
-                transform.rotation = Quat::from_rotation_z(r * jitter.max_rotation);
+                let rotation = Quat::from_rotation_z(r * jitter.max_rotation * gain);
+                rotate_about(&mut transform, rotation, jitter.pivot, cell_size);
             }
         }
     }
@@ -1,7 +1,9 @@
 use bevy::prelude::*;
 
-use super::{simple_hash, EffectRegion, TargetTerminal};
-use crate::grid::{GridPosition, TerminalCell};
+use super::{
+    cell_in_effect, rotate_about, seeded_hash, CellMask, EffectClock, EffectRegion, RunningEffect,
+};
+use crate::grid::{CellStyle, GridPosition, TerminalCell};
 use crate::TerminalLayout;
 
 /// Blunt-impact knock effect — all cells in the region jolt in a uniform
@@ -17,12 +19,20 @@ pub struct Knock {
     pub deviation: f32,
     /// Per-cell rotation strength in radians at peak.
     pub rotation: f32,
+    /// Point each cell rotates around, in cell sizes from its center
+    /// (default: the center). See `rotate_about`.
+    pub pivot: Vec2,
     /// How long the effect has been running.
     pub elapsed: f32,
     /// Total duration of the effect.
     pub duration: f32,
     /// Whether the effect is currently active.
     pub active: bool,
+    /// Mixed into the per-cell deviation hash (0 = original pattern).
+    pub seed: u32,
+    /// Swings past rest and back this many times while settling, for a
+    /// springy, meatier hit (default: 0.0 = ease straight back to rest).
+    pub springiness: f32,
 }
 
 impl Default for Knock {
@@ -32,55 +42,74 @@ impl Default for Knock {
             amplitude: 12.0,
             deviation: 0.3,
             rotation: 0.1,
+            pivot: Vec2::ZERO,
             elapsed: 0.0,
             duration: 0.6,
             active: true,
+            seed: 0,
+            springiness: 0.0,
         }
     }
 }
 
+impl Knock {
+    /// Displacement so far as a fraction of `amplitude`: a sharp onset
+    /// peaking at 1.0 a quarter of the way in, then a decay. With
+    /// `springiness` the decay oscillates, going negative past rest.
+    pub fn strength(&self) -> f32 {
+        let progress = self.elapsed / self.duration;
+
+        // Damped impulse t * exp(-decay * t), normalized so the peak (at
+        // 1 / decay) is 1.0
+        let decay = 4.0;
+        let raw = progress * (-decay * progress).exp();
+        let peak_at = 1.0 / decay;
+        let impulse = raw / (peak_at * (-1.0_f32).exp());
+        if self.springiness <= 0.0 || progress <= peak_at {
+            return impulse;
+        }
+
+        // Full swings spread over the settle after the peak
+        let settle = (progress - peak_at) / (1.0 - peak_at);
+        impulse * (std::f32::consts::TAU * self.springiness * settle).cos()
+    }
+}
+
 /// System that applies the knock effect to cell transforms.
 pub fn knock_system<T: 'static + Send + Sync>(
-    time: Res<Time>,
+    clock: Res<EffectClock<T>>,
     layout: Res<TerminalLayout<T>>,
-    mut effects: Query<(&mut Knock, &EffectRegion), With<TargetTerminal<T>>>,
-    mut cells: Query<(&GridPosition, &mut Transform), With<TerminalCell<T>>>,
+    mut effects: Query<(&mut Knock, &EffectRegion, Option<&CellMask>), RunningEffect<T>>,
+    mut cells: Query<(&GridPosition, &CellStyle, &mut Transform), With<TerminalCell<T>>>,
 ) {
-    for (mut knock, region) in effects.iter_mut() {
+    for (mut knock, region, mask) in effects.iter_mut() {
         if !knock.active {
             continue;
         }
 
-        knock.elapsed += time.delta_secs();
+        knock.elapsed += clock.delta_secs();
 
         if knock.elapsed > knock.duration {
             knock.active = false;
             continue;
         }
 
-        let progress = knock.elapsed / knock.duration;
-
-        // Sharp onset, smooth settle: peaks at ~15% then decays
-        // Using a damped impulse: t * exp(-decay * t) normalized
-        let decay = 4.0;
-        let raw = progress * (-decay * progress).exp();
-        // Normalize so peak = 1.0 (peak is at 1/decay)
-        let peak = (1.0 / decay) * (-1.0_f32).exp();
-        let strength = raw / peak;
+        let strength = knock.strength();
 
         let base_dx = knock.angle.cos();
         let base_dy = knock.angle.sin();
+        let cell_size = Vec2::new(layout.cell_width, layout.cell_height);
 
-        for (pos, mut transform) in cells.iter_mut() {
-            if !region.contains(pos.col, pos.row) {
+        for (pos, style, mut transform) in cells.iter_mut() {
+            if !cell_in_effect(region, mask, pos.col, pos.row, style) {
                 continue;
             }
 
             let cell_id = pos.col as u32 * 1000 + pos.row as u32;
 
             // Per-cell slight deviation from the main knock direction
-            let h1 = simple_hash(cell_id, 777);
-            let h2 = simple_hash(cell_id, 888);
+            let h1 = seeded_hash(cell_id, 777, knock.seed);
+            let h2 = seeded_hash(cell_id, 888, knock.seed);
             let r1 = (h1 % 10000) as f32 / 10000.0; // 0..1
             let r2 = (h2 % 10000) as f32 / 10000.0; // 0..1
 
@@ -99,8 +128,8 @@ pub fn knock_system<T: 'static + Send + Sync>(
 
             // Slight rotation matching the knock direction
             let rot_dir = if r1 > 0.5 { 1.0 } else { -1.0 };
-            let rot = knock.rotation * amp_mult * strength * rot_dir;
-            transform.rotation *= Quat::from_rotation_z(rot);
+            let rot = Quat::from_rotation_z(knock.rotation * amp_mult * strength * rot_dir);
+            rotate_about(&mut transform, rot, knock.pivot, cell_size);
         }
     }
 }
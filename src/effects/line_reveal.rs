@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use super::{cell_in_effect, CellMask, EffectClock, EffectFinished, EffectRegion, RunningEffect};
+use crate::culling::is_blank;
+use crate::grid::{CellEntityIndex, CellOpacity, CellStyle, TerminalCell};
+use crate::sync::SyncGeneration;
+
+/// What a `LineReveal` brings in at a time.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RevealUnit {
+    /// A run of non-blank cells within a row.
+    Word,
+    /// A row with any content (default). Blank rows are skipped.
+    #[default]
+    Line,
+}
+
+/// Reveals text a word or a line at a time, in reading order, each unit
+/// starting its fade `interval` seconds after the previous one. Suits
+/// narrative text better than a per-character reveal.
+///
+/// Units are found from the cells' symbols and found again whenever the
+/// buffer is synced, so text that is redrawn mid-animation is split up
+/// again. Only glyphs fade; backgrounds
+/// show from the start (pair it with `Fade` to bring those in too).
+///
+/// One-shot: sends `EffectFinished` once every unit is fully shown.
+#[derive(Component, Clone, Debug)]
+pub struct LineReveal {
+    pub unit: RevealUnit,
+    /// Seconds between the start of one unit's fade and the next.
+    pub interval: f32,
+    /// Seconds each unit takes to fade in.
+    pub fade_duration: f32,
+    pub elapsed: f32,
+    pub active: bool,
+}
+
+impl Default for LineReveal {
+    fn default() -> Self {
+        Self {
+            unit: RevealUnit::Line,
+            interval: 0.4,
+            fade_duration: 0.3,
+            elapsed: 0.0,
+            active: true,
+        }
+    }
+}
+
+impl LineReveal {
+    /// Opacity of the `index`th unit, 0.0..=1.0.
+    pub fn unit_alpha(&self, index: usize) -> f32 {
+        let start = index as f32 * self.interval;
+        if self.fade_duration <= 0.0 {
+            return if self.elapsed >= start { 1.0 } else { 0.0 };
+        }
+        ((self.elapsed - start) / self.fade_duration).clamp(0.0, 1.0)
+    }
+}
+
+/// Number the units in row-major `cells`, where each entry is `None` for a
+/// cell outside the effect and otherwise whether the cell is blank. Returns
+/// each cell's unit (None if it belongs to none) and the unit count.
+pub(crate) fn reveal_units(
+    unit: RevealUnit,
+    columns: usize,
+    cells: &[Option<bool>],
+) -> (Vec<Option<usize>>, usize) {
+    let mut units = vec![None; cells.len()];
+    let mut count = 0;
+    if columns == 0 {
+        return (units, count);
+    }
+
+    for (row, flags) in cells.chunks(columns).enumerate() {
+        let base = row * columns;
+        match unit {
+            RevealUnit::Line => {
+                if !flags.iter().any(|&cell| cell == Some(false)) {
+                    continue;
+                }
+                for (col, cell) in flags.iter().enumerate() {
+                    if cell.is_some() {
+                        units[base + col] = Some(count);
+                    }
+                }
+                count += 1;
+            }
+            RevealUnit::Word => {
+                let mut in_word = false;
+                for (col, &cell) in flags.iter().enumerate() {
+                    if cell == Some(false) {
+                        if !in_word {
+                            in_word = true;
+                            count += 1;
+                        }
+                        units[base + col] = Some(count - 1);
+                    } else {
+                        in_word = false;
+                    }
+                }
+            }
+        }
+    }
+    (units, count)
+}
+
+/// Units a `LineReveal` last split its cells into, kept until the buffer,
+/// the grid or the effect's region changes.
+pub struct RevealCache {
+    generation: u64,
+    unit: RevealUnit,
+    units: Vec<Option<usize>>,
+    count: usize,
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn line_reveal_system<T: 'static + Send + Sync>(
+    clock: Res<EffectClock<T>>,
+    sync_gen: Res<SyncGeneration<T>>,
+    mut effects: Query<
+        (
+            Entity,
+            &mut LineReveal,
+            Ref<EffectRegion>,
+            Option<Ref<CellMask>>,
+        ),
+        RunningEffect<T>,
+    >,
+    cell_index: Res<CellEntityIndex<T>>,
+    mut cells: Query<(&CellStyle, &mut CellOpacity), With<TerminalCell<T>>>,
+    mut finished: MessageWriter<EffectFinished>,
+    mut caches: Local<HashMap<Entity, RevealCache>>,
+    mut content: Local<Vec<Option<bool>>>,
+) {
+    let columns = cell_index.columns as usize;
+    caches.retain(|&entity, _| effects.contains(entity));
+
+    for (entity, mut reveal, region, mask) in effects.iter_mut() {
+        if !reveal.active {
+            continue;
+        }
+        reveal.elapsed += clock.delta_secs();
+
+        let fresh = caches.get(&entity).is_some_and(|cache| {
+            cache.generation == sync_gen.generation && cache.unit == reveal.unit
+        });
+        let moved = region.is_changed() || mask.as_ref().is_some_and(|mask| mask.is_changed());
+        if !fresh || moved || cell_index.is_changed() {
+            content.clear();
+            content.extend(cell_index.entities.iter().enumerate().map(|(idx, &cell)| {
+                let (style, _) = cells.get(cell).ok()?;
+                let col = (idx % columns) as u16;
+                let row = (idx / columns) as u16;
+                cell_in_effect(&region, mask.as_deref(), col, row, style)
+                    .then(|| is_blank(&style.symbol))
+            }));
+            let (units, count) = reveal_units(reveal.unit, columns, &content);
+            let cache = RevealCache {
+                generation: sync_gen.generation,
+                unit: reveal.unit,
+                units,
+                count,
+            };
+            caches.insert(entity, cache);
+        }
+        let RevealCache { units, count, .. } = &caches[&entity];
+        let count = *count;
+
+        for (idx, &unit) in units.iter().enumerate() {
+            let Some(unit) = unit else {
+                continue;
+            };
+            let alpha = reveal.unit_alpha(unit);
+            if alpha == 1.0 {
+                continue;
+            }
+            if let Ok((_, mut opacity)) = cells.get_mut(cell_index.entities[idx]) {
+                opacity.0 *= alpha;
+            }
+        }
+
+        // Nothing drawn yet means nothing revealed yet, not done
+        if count > 0 && reveal.unit_alpha(count - 1) >= 1.0 {
+            reveal.active = false;
+            finished.write(EffectFinished { effect: entity });
+        }
+    }
+}
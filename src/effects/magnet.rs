@@ -0,0 +1,106 @@
+use bevy::prelude::*;
+
+use super::{cell_in_effect, CellMask, EffectClock, EffectFinished, EffectRegion, RunningEffect};
+use crate::grid::{CellStyle, GridPosition, TerminalCell};
+use crate::TerminalLayout;
+
+/// Pulls cells toward a point, easing in and out so they accelerate, then
+/// settle into a cluster, e.g. for "collect the letters" animations.
+///
+/// One-shot: cells travel for `duration`, then hold their final position
+/// until the component is removed. Sends `EffectFinished` on arrival;
+/// `retrigger` starts the pull over.
+#[derive(Component, Clone, Debug)]
+pub struct Magnet {
+    /// Origin column (grid coords).
+    pub origin_col: f32,
+    /// Origin row (grid coords).
+    pub origin_row: f32,
+    /// Fraction of the way to the origin cells end up (1.0 = all the way).
+    pub strength: f32,
+    /// Seconds for the pull to complete.
+    pub duration: f32,
+    /// How long the pull has been running.
+    pub elapsed: f32,
+    /// Cleared once the cells arrive and `EffectFinished` is sent.
+    pub active: bool,
+}
+
+impl Default for Magnet {
+    fn default() -> Self {
+        Self {
+            origin_col: 40.0,
+            origin_row: 12.0,
+            strength: 0.9,
+            duration: 1.5,
+            elapsed: 0.0,
+            active: true,
+        }
+    }
+}
+
+impl Magnet {
+    /// Eased progress of the pull, 0.0..=1.0.
+    pub fn progress(&self) -> f32 {
+        let t = if self.duration > 0.0 {
+            (self.elapsed / self.duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        // Smoothstep: slow start, fast middle, gentle arrival
+        t * t * (3.0 - 2.0 * t)
+    }
+
+    /// Whether the cells have arrived.
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// Restart the pull from the cells' home positions.
+    pub fn retrigger(&mut self) {
+        self.elapsed = 0.0;
+        self.active = true;
+    }
+}
+
+/// Cells closer to the origin than this (in cells) are left alone.
+const DEAD_ZONE: f32 = 0.5;
+
+pub fn magnet_system<T: 'static + Send + Sync>(
+    clock: Res<EffectClock<T>>,
+    layout: Res<TerminalLayout<T>>,
+    mut effects: Query<(Entity, &mut Magnet, &EffectRegion, Option<&CellMask>), RunningEffect<T>>,
+    mut cells: Query<(&GridPosition, &CellStyle, &mut Transform), With<TerminalCell<T>>>,
+    mut finished: MessageWriter<EffectFinished>,
+) {
+    for (entity, mut magnet, region, mask) in effects.iter_mut() {
+        // Checked after advancing, so a zero `duration` still reports
+        if magnet.active {
+            magnet.elapsed += clock.delta_secs();
+            if magnet.is_finished() {
+                magnet.active = false;
+                finished.write(EffectFinished { effect: entity });
+            }
+        }
+
+        let pull = magnet.progress() * magnet.strength.clamp(0.0, 1.0);
+        if pull == 0.0 {
+            continue;
+        }
+
+        for (pos, style, mut transform) in cells.iter_mut() {
+            if !cell_in_effect(region, mask, pos.col, pos.row, style) {
+                continue;
+            }
+
+            let dc = magnet.origin_col - pos.col as f32;
+            let dr = magnet.origin_row - pos.row as f32;
+            if dc.abs() < DEAD_ZONE && dr.abs() < DEAD_ZONE {
+                continue;
+            }
+
+            transform.translation.x += dc * layout.cell_width * pull;
+            transform.translation.y -= dr * layout.cell_height * pull;
+        }
+    }
+}
@@ -2,9 +2,15 @@ pub mod breathe;
 pub mod bubbly;
 pub mod collapse;
 pub mod explode;
+pub mod fade;
+pub mod flash;
+pub mod flip;
 pub mod glitch;
 pub mod glow;
+pub mod gradient_tint;
 pub mod knock;
+pub mod line_reveal;
+pub mod magnet;
 pub mod gravity;
 pub mod jitter;
 pub mod rainbow;
@@ -12,13 +18,99 @@ pub mod ripple;
 pub mod scatter;
 pub mod shiny;
 pub mod slash;
+pub mod swirl;
 pub mod wave;
 
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::marker::PhantomData;
 
 use bevy::prelude::*;
 
-use crate::grid::{BaseTransform, CellEntityIndex, CellStyle, ForegroundSprite, TerminalCell};
+use crate::atlas::FontAtlasResource;
+use crate::cursor::{CursorState, CursorStyle};
+use crate::grid::{
+    BaseTransform, CellEntityIndex, CellOpacity, CellStyle, ColorOverride, ForegroundSprite,
+    TerminalCell,
+};
+use crate::selection::{LineHighlight, Selection};
+use crate::sync::SyncGeneration;
+use crate::{TerminalConfig, TerminalLayout};
+
+/// Where a positional effect (`Ripple`, `Scatter`, `Explode`, `Magnet`)
+/// is centered. Overrides the effect's `origin_col`/`origin_row` every frame.
+///
+/// `Entity` tracks the entity's `GlobalTransform`, converted to grid coords
+/// through `TerminalLayout`, so e.g. a ripple follows a moving sprite. The
+/// transform is the one propagated last frame, so the origin lags by a frame.
+/// If the entity is despawned the effect keeps its last origin.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub enum EffectOrigin {
+    /// Fixed grid coords (fractional; cell centers are whole numbers).
+    Grid { col: f32, row: f32 },
+    /// A world-space entity in the terminal's coordinate space.
+    Entity(Entity),
+}
+
+/// Effects with an origin point that `EffectOrigin` can drive.
+trait OriginEffect {
+    fn origin(&self) -> Vec2;
+    fn set_origin(&mut self, origin: Vec2);
+}
+
+macro_rules! impl_origin_effect {
+    ($($effect:ty),*) => {$(
+        impl OriginEffect for $effect {
+            fn origin(&self) -> Vec2 {
+                Vec2::new(self.origin_col, self.origin_row)
+            }
+
+            fn set_origin(&mut self, origin: Vec2) {
+                self.origin_col = origin.x;
+                self.origin_row = origin.y;
+            }
+        }
+    )*};
+}
+
+impl_origin_effect!(ripple::Ripple, scatter::Scatter, explode::Explode, magnet::Magnet);
+
+fn follow<E: OriginEffect>(effect: Option<Mut<E>>, origin: Vec2) {
+    if let Some(mut effect) = effect {
+        if effect.origin() != origin {
+            effect.set_origin(origin);
+        }
+    }
+}
+
+/// Copies each `EffectOrigin` into its effect's origin fields.
+pub fn follow_effect_origin<T: 'static + Send + Sync>(
+    layout: Res<TerminalLayout<T>>,
+    targets: Query<&GlobalTransform>,
+    mut effects: Query<
+        (
+            &EffectOrigin,
+            Option<&mut ripple::Ripple>,
+            Option<&mut scatter::Scatter>,
+            Option<&mut explode::Explode>,
+            Option<&mut magnet::Magnet>,
+        ),
+        With<TargetTerminal<T>>,
+    >,
+) {
+    for (origin, ripple, scatter, explode, magnet) in effects.iter_mut() {
+        let origin = match *origin {
+            EffectOrigin::Grid { col, row } => Vec2::new(col, row),
+            EffectOrigin::Entity(target) => match targets.get(target) {
+                Ok(transform) => layout.world_to_grid_coords(transform.translation().truncate()),
+                Err(_) => continue,
+            },
+        };
+        follow(ripple, origin);
+        follow(scatter, origin);
+        follow(explode, origin);
+        follow(magnet, origin);
+    }
+}
 
 /// A rectangle in grid coordinates.
 #[derive(Clone, Debug)]
@@ -30,6 +122,15 @@ pub struct GridRect {
 }
 
 impl GridRect {
+    pub fn new(col: u16, row: u16, width: u16, height: u16) -> Self {
+        Self {
+            col,
+            row,
+            width,
+            height,
+        }
+    }
+
     pub fn contains(&self, col: u16, row: u16) -> bool {
         col >= self.col
             && col < self.col + self.width
@@ -93,11 +194,145 @@ impl EffectRegion {
             exclude: vec![],
         }
     }
+
+    /// Create an EffectRegion covering a single rectangle.
+    pub fn rect(col: u16, row: u16, width: u16, height: u16) -> Self {
+        Self::all().with_include(col, row, width, height)
+    }
+
+    /// Start building a region from include/exclude rectangles.
+    pub fn builder() -> EffectRegionBuilder {
+        EffectRegionBuilder::default()
+    }
+
+    /// Add a rectangle to `include`.
+    pub fn with_include(mut self, col: u16, row: u16, width: u16, height: u16) -> Self {
+        self.include.push(GridRect::new(col, row, width, height));
+        self
+    }
+
+    /// Add a rectangle to `exclude`.
+    pub fn with_exclude(mut self, col: u16, row: u16, width: u16, height: u16) -> Self {
+        self.exclude.push(GridRect::new(col, row, width, height));
+        self
+    }
+
+    /// Left half of a `cols` x `rows` grid. With an odd column count the
+    /// middle column belongs to the right half.
+    pub fn left_half(cols: u16, rows: u16) -> Self {
+        Self::rect(0, 0, cols / 2, rows)
+    }
+
+    /// Right half of a `cols` x `rows` grid.
+    pub fn right_half(cols: u16, rows: u16) -> Self {
+        Self::rect(cols / 2, 0, cols - cols / 2, rows)
+    }
+
+    /// Top half of a `cols` x `rows` grid. With an odd row count the middle
+    /// row belongs to the bottom half.
+    pub fn top_half(cols: u16, rows: u16) -> Self {
+        Self::rect(0, 0, cols, rows / 2)
+    }
+
+    /// Bottom half of a `cols` x `rows` grid.
+    pub fn bottom_half(cols: u16, rows: u16) -> Self {
+        Self::rect(0, rows / 2, cols, rows - rows / 2)
+    }
+
+    /// A `width` x `height` rectangle centered in a `cols` x `rows` grid,
+    /// clamped to the grid.
+    pub fn center(cols: u16, rows: u16, width: u16, height: u16) -> Self {
+        let (width, height) = (width.min(cols), height.min(rows));
+        Self::rect((cols - width) / 2, (rows - height) / 2, width, height)
+    }
+}
+
+/// Fluent builder for `EffectRegion`, from `EffectRegion::builder()`.
+#[derive(Clone, Debug, Default)]
+pub struct EffectRegionBuilder {
+    include: Vec<GridRect>,
+    exclude: Vec<GridRect>,
+}
+
+impl EffectRegionBuilder {
+    /// Target cells in this rectangle.
+    pub fn include(mut self, col: u16, row: u16, width: u16, height: u16) -> Self {
+        self.include.push(GridRect::new(col, row, width, height));
+        self
+    }
+
+    /// Skip cells in this rectangle, even if included.
+    pub fn exclude(mut self, col: u16, row: u16, width: u16, height: u16) -> Self {
+        self.exclude.push(GridRect::new(col, row, width, height));
+        self
+    }
+
+    pub fn build(self) -> EffectRegion {
+        EffectRegion {
+            include: self.include,
+            exclude: self.exclude,
+        }
+    }
+}
+
+/// Restricts an effect to cells whose displayed character matches.
+///
+/// Optional; add it next to `EffectRegion`. When both are present a cell must
+/// be inside the region *and* match the mask.
+#[derive(Component, Clone, Debug)]
+pub enum CellMask {
+    /// Cells whose character satisfies the predicate.
+    Predicate(fn(char) -> bool),
+    /// Cells showing one of these characters.
+    Chars(Vec<char>),
+}
+
+impl CellMask {
+    /// Only cells showing a visible (non-whitespace) character.
+    pub fn non_space() -> Self {
+        Self::Predicate(|ch| !ch.is_whitespace())
+    }
+
+    /// Only box-drawing characters (U+2500..U+257F).
+    pub fn box_drawing() -> Self {
+        Self::Predicate(|ch| ('\u{2500}'..='\u{257F}').contains(&ch))
+    }
+
+    /// Check if a cell showing `ch` is targeted.
+    pub fn matches(&self, ch: char) -> bool {
+        match self {
+            Self::Predicate(f) => f(ch),
+            Self::Chars(chars) => chars.contains(&ch),
+        }
+    }
+
+    /// Check a cell's symbol (as stored in `CellStyle`); empty symbols count
+    /// as a space.
+    pub fn matches_symbol(&self, symbol: &str) -> bool {
+        self.matches(symbol.chars().next().unwrap_or(' '))
+    }
+}
+
+/// Check if an effect with `region` and optional `mask` applies to the cell
+/// at (col, row) with the given style.
+pub fn cell_in_effect(
+    region: &EffectRegion,
+    mask: Option<&CellMask>,
+    col: u16,
+    row: u16,
+    style: &CellStyle,
+) -> bool {
+    region.contains(col, row) && mask.is_none_or(|mask| mask.matches_symbol(&style.symbol))
 }
 
 /// Marker component that scopes an effect entity to a specific terminal instance.
 /// Effects without this component are ignored by generic effect systems.
+///
+/// An effect spawned with an `EffectRegion` but no target is given the first
+/// registered terminal's, so single-terminal apps can leave it out. With
+/// several terminals, spawn it in the same bundle as the region.
 #[derive(Component)]
+#[require(EffectTargeted)]
 pub struct TargetTerminal<T: 'static + Send + Sync>(PhantomData<T>);
 
 impl<T: 'static + Send + Sync> Clone for TargetTerminal<T> {
@@ -112,6 +347,277 @@ impl<T: 'static + Send + Sync> Default for TargetTerminal<T> {
     }
 }
 
+/// Marker that pauses one effect entity without despawning it. Effect
+/// systems skip paused effects, so their cells look as if the effect were
+/// gone, and one-shots pick up where they stopped once it's removed.
+/// Continuous effects driven by `EffectClock` resume at the current time.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct EffectPaused;
+
+/// Query filter for the effects that run on terminal `T`: targeted at it
+/// and not paused. Custom effect systems should use it too.
+pub type RunningEffect<T> = (With<TargetTerminal<T>>, Without<EffectPaused>);
+
+/// Ramps a continuous effect in over `attack` seconds after it's spawned,
+/// and out over `release` seconds once `stop` is called, then despawns the
+/// effect entity. Add it next to the effect to fade it in and out instead of
+/// popping on and off.
+///
+/// `Wave`, `Jitter`, `Rainbow` and `Glow` scale their output by `gain`.
+/// Custom effects can do the same with `envelope_gain`.
+#[derive(Component, Clone, Debug)]
+pub struct EffectEnvelope {
+    /// Seconds to ramp from nothing to full strength.
+    pub attack: f32,
+    /// Seconds to ramp from full strength to nothing after `stop`.
+    pub release: f32,
+    /// Seconds into the current ramp.
+    pub elapsed: f32,
+    /// Whether the effect is ramping out.
+    pub releasing: bool,
+}
+
+impl Default for EffectEnvelope {
+    fn default() -> Self {
+        Self::new(0.5, 0.5)
+    }
+}
+
+impl EffectEnvelope {
+    pub fn new(attack: f32, release: f32) -> Self {
+        Self {
+            attack,
+            release,
+            elapsed: 0.0,
+            releasing: false,
+        }
+    }
+
+    /// Current strength of the effect, 0.0..=1.0.
+    pub fn gain(&self) -> f32 {
+        let (duration, rising) = if self.releasing {
+            (self.release, false)
+        } else {
+            (self.attack, true)
+        };
+        let t = if duration > 0.0 {
+            (self.elapsed / duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        if rising {
+            t
+        } else {
+            1.0 - t
+        }
+    }
+
+    /// Start ramping out from the current strength, so stopping mid-attack
+    /// doesn't jump to full first.
+    pub fn stop(&mut self) {
+        if self.releasing {
+            return;
+        }
+        let gain = self.gain();
+        self.releasing = true;
+        self.elapsed = (1.0 - gain) * self.release.max(0.0);
+    }
+
+    /// Whether the release has run its course.
+    pub fn is_finished(&self) -> bool {
+        self.releasing && self.elapsed >= self.release
+    }
+}
+
+/// Strength an effect with this (optional) envelope should run at.
+pub fn envelope_gain(envelope: Option<&EffectEnvelope>) -> f32 {
+    envelope.map_or(1.0, EffectEnvelope::gain)
+}
+
+/// Turns a cell's `transform` by `rotation` around `pivot`, given in cell
+/// sizes from the cell's center (+y up): `Vec2::ZERO` spins the cell in
+/// place, `Vec2::new(0.0, -0.5)` hinges it on its bottom edge. The pivot
+/// turns with rotations earlier effects applied, and the result composes
+/// with them, so rotating effects stack like the rest.
+pub fn rotate_about(transform: &mut Transform, rotation: Quat, pivot: Vec2, cell_size: Vec2) {
+    let arm = transform.rotation * (pivot * cell_size).extend(0.0);
+    transform.translation += arm - rotation * arm;
+    transform.rotation = rotation * transform.rotation;
+}
+
+/// Advances each running effect's `EffectEnvelope` by the effect clock and
+/// despawns the effects whose release has finished.
+pub fn advance_effect_envelopes<T: 'static + Send + Sync>(
+    clock: Res<EffectClock<T>>,
+    mut envelopes: Query<(Entity, &mut EffectEnvelope), RunningEffect<T>>,
+    mut commands: Commands,
+) {
+    for (entity, mut envelope) in envelopes.iter_mut() {
+        envelope.elapsed += clock.delta_secs();
+        if envelope.is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Spawn `effect` on terminal `T`, limited to `region`, with the
+/// `TargetTerminal<T>` the effect systems look for.
+pub fn spawn_effect<T: 'static + Send + Sync>(
+    commands: &mut Commands,
+    effect: impl Bundle,
+    region: EffectRegion,
+) -> Entity {
+    commands.spawn_effect::<T>(effect, region).id()
+}
+
+/// `spawn_effect` as a `Commands` method, returning the entity's commands so
+/// e.g. a `CellMask` can be added.
+pub trait SpawnEffectExt {
+    fn spawn_effect<T: 'static + Send + Sync>(
+        &mut self,
+        effect: impl Bundle,
+        region: EffectRegion,
+    ) -> EntityCommands<'_>;
+}
+
+impl SpawnEffectExt for Commands<'_, '_> {
+    fn spawn_effect<T: 'static + Send + Sync>(
+        &mut self,
+        effect: impl Bundle,
+        region: EffectRegion,
+    ) -> EntityCommands<'_> {
+        self.spawn((effect, region, TargetTerminal::<T>::default()))
+    }
+}
+
+/// Added with every `TargetTerminal<T>`, so untargeted effects can be found
+/// without knowing each terminal's type.
+#[derive(Component, Default)]
+pub struct EffectTargeted;
+
+/// Observer that targets effects spawned without a `TargetTerminal` at this
+/// terminal. Only the first `TerminalEmuPlugin` registers it.
+pub fn target_untargeted_effects<T: 'static + Send + Sync>(
+    add: On<Add, EffectRegion>,
+    targeted: Query<(), With<EffectTargeted>>,
+    mut commands: Commands,
+) {
+    if !targeted.contains(add.entity) {
+        commands
+            .entity(add.entity)
+            .insert(TargetTerminal::<T>::default());
+    }
+}
+
+/// Sent when a one-shot effect that reports completion (`Fade`, `Flip`,
+/// `Magnet`, or a `Ripple` with an envelope) reaches its end.
+#[derive(Message, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EffectFinished {
+    /// The effect entity that finished.
+    pub effect: Entity,
+}
+
+/// Scaled clock that drives every built-in effect for a terminal.
+///
+/// Set `time_scale` to slow down or speed up all effects at once, or `paused`
+/// to freeze them (e.g. behind a pause menu). Custom effects should read
+/// `delta_secs()`/`elapsed_secs()` from here instead of `Time`.
+///
+/// With `fixed_step` set, the clock only moves in whole steps, carrying the
+/// remainder to the next frame, so `elapsed_secs()` lands on the same times
+/// at any frame rate. A slow frame can cover several steps at once, so
+/// effects that integrate state, like `Gravity`, run `steps()` updates of
+/// `step_secs()` each to stay in step across frame rates.
+#[derive(Resource, Clone, Debug)]
+pub struct EffectClock<T: 'static + Send + Sync> {
+    /// Multiplier applied to real frame time (1.0 = normal, 0.0 = frozen).
+    pub time_scale: f32,
+    /// Freeze all effects regardless of `time_scale`.
+    pub paused: bool,
+    /// Advance in multiples of this many seconds (default: None = smooth).
+    pub fixed_step: Option<f32>,
+    delta: f32,
+    elapsed: f32,
+    steps: u32,
+    /// Scaled time not yet consumed by a fixed step.
+    accumulator: f32,
+    _marker: PhantomData<T>,
+}
+
+impl<T: 'static + Send + Sync> Default for EffectClock<T> {
+    fn default() -> Self {
+        Self {
+            time_scale: 1.0,
+            paused: false,
+            fixed_step: None,
+            delta: 0.0,
+            elapsed: 0.0,
+            steps: 1,
+            accumulator: 0.0,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: 'static + Send + Sync> EffectClock<T> {
+    /// Scaled seconds since last frame (0 while paused). A whole number of
+    /// steps, possibly 0, when `fixed_step` is set.
+    pub fn delta_secs(&self) -> f32 {
+        self.delta
+    }
+
+    /// Scaled seconds accumulated since startup.
+    pub fn elapsed_secs(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// Number of `fixed_step` steps in this frame's `delta_secs()`, or 1
+    /// when the clock moves smoothly.
+    pub fn steps(&self) -> u32 {
+        self.steps
+    }
+
+    /// Length of one of this frame's `steps()`: `fixed_step` when set,
+    /// otherwise the whole `delta_secs()`.
+    pub fn step_secs(&self) -> f32 {
+        match self.fixed_step {
+            Some(step) if step > 0.0 => step,
+            _ => self.delta,
+        }
+    }
+
+    /// Advance the clock by `real_delta` seconds of real time.
+    pub fn advance(&mut self, real_delta: f32) {
+        let scaled = if self.paused {
+            0.0
+        } else {
+            real_delta * self.time_scale.max(0.0)
+        };
+        self.delta = match self.fixed_step {
+            Some(step) if step > 0.0 => {
+                self.accumulator += scaled;
+                let steps = (self.accumulator / step).floor();
+                self.accumulator -= steps * step;
+                self.steps = steps as u32;
+                steps * step
+            }
+            _ => {
+                self.steps = 1;
+                scaled
+            }
+        };
+        self.elapsed += self.delta;
+    }
+}
+
+/// Advances `EffectClock<T>` from `Time` once per frame, before effects run.
+pub fn tick_effect_clock<T: 'static + Send + Sync>(
+    time: Res<Time>,
+    mut clock: ResMut<EffectClock<T>>,
+) {
+    clock.advance(time.delta_secs());
+}
+
 /// System that resets all cell transforms to their base positions each frame.
 /// This runs before effects so they can additively modify transforms.
 /// Uses compare-before-write to avoid triggering Bevy change detection when
@@ -131,48 +637,351 @@ pub fn reset_transforms<T: 'static + Send + Sync>(
     }
 }
 
-/// Resets foreground sprite colors to their CellStyle values each frame.
-/// Effects that modify sprite color (Glow, Rainbow, Shiny) run after this,
-/// so their changes last exactly one frame and don't accumulate.
+/// Resets foreground and background sprite colors to their CellStyle values
+/// each frame, the color counterpart of `reset_transforms`. Effects that modify
+/// sprite color (Glow, Rainbow, GradientTint, Shiny, Fade, selection highlight)
+/// run after this, so their changes last exactly one frame, don't accumulate,
+/// and disappear as soon as the effect is removed. Also resets `CellOpacity`,
+/// applies any `ColorOverride`, and puts back glyph tiles swapped out by
+/// effects such as `Flip::back_glyph`.
 pub fn reset_colors<T: 'static + Send + Sync>(
+    config: Res<TerminalConfig<T>>,
+    atlas: Option<Res<FontAtlasResource<T>>>,
     cell_index: Res<CellEntityIndex<T>>,
-    cell_query: Query<&CellStyle, With<TerminalCell<T>>>,
-    mut fg_query: Query<&mut Sprite, With<ForegroundSprite<T>>>,
+    mut cell_query: Query<
+        (&CellStyle, &mut Sprite, Option<&mut CellOpacity>, Option<&ColorOverride>),
+        With<TerminalCell<T>>,
+    >,
+    mut fg_query: Query<&mut Sprite, (With<ForegroundSprite<T>>, Without<TerminalCell<T>>)>,
 ) {
+    // Same lookup as sync: unknown glyphs show as a space until they're added
+    let glyph_index = |ch: char| {
+        let atlas = atlas.as_ref()?;
+        let map = &atlas.glyph_map;
+        Some(map.get(&ch).or_else(|| map.get(&' ')).copied().unwrap_or(0))
+    };
     for (idx, &parent_entity) in cell_index.entities.iter().enumerate() {
-        let Ok(cell_style) = cell_query.get(parent_entity) else {
+        let Ok((cell_style, mut bg_sprite, opacity, color_override)) =
+            cell_query.get_mut(parent_entity)
+        else {
             continue;
         };
+        let color_override = color_override.copied().unwrap_or_default();
+        if let Some(mut opacity) = opacity {
+            if opacity.0 != 1.0 {
+                opacity.0 = 1.0;
+            }
+        }
+        let bg = color_override.bg.unwrap_or(cell_style.bg);
+        if bg_sprite.color != bg {
+            bg_sprite.color = bg;
+        }
         let fg_entity = cell_index.fg_entities[idx];
         if let Ok(mut fg_sprite) = fg_query.get_mut(fg_entity) {
-            let target = if cell_style.dim {
-                cell_style.fg.with_alpha(0.5)
-            } else {
-                cell_style.fg
-            };
+            let ch = cell_style.symbol.chars().next().unwrap_or(' ');
+            let target = color_override.fg.unwrap_or_else(|| {
+                let color_glyph = atlas.as_ref().is_some_and(|a| a.is_color_glyph(ch));
+                config.glyph_tint(cell_style.fg, cell_style.dim, color_glyph)
+            });
             if fg_sprite.color != target {
                 fg_sprite.color = target;
             }
+            if let Some(index) = glyph_index(ch) {
+                let shown = fg_sprite.texture_atlas.as_ref().map(|tile| tile.index);
+                if shown.is_some_and(|shown| shown != index) {
+                    if let Some(tile) = fg_sprite.texture_atlas.as_mut() {
+                        tile.index = index;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Multiplies each glyph's alpha by its cell's `CellOpacity`. Runs last in
+/// `TerminalSet::ColorEffects`, after every effect has had its say.
+pub fn apply_cell_opacity<T: 'static + Send + Sync>(
+    cell_index: Res<CellEntityIndex<T>>,
+    cells: Query<&CellOpacity, With<TerminalCell<T>>>,
+    mut fg_query: Query<&mut Sprite, With<ForegroundSprite<T>>>,
+) {
+    for (idx, &parent_entity) in cell_index.entities.iter().enumerate() {
+        let Ok(opacity) = cells.get(parent_entity) else {
+            continue;
+        };
+        if opacity.0 == 1.0 {
+            continue;
+        }
+        if let Ok(mut fg_sprite) = fg_query.get_mut(cell_index.fg_entities[idx]) {
+            let alpha = (fg_sprite.color.alpha() * opacity.0).clamp(0.0, 1.0);
+            fg_sprite.color.set_alpha(alpha);
+        }
+    }
+}
+
+/// What last frame's color pass was computed from, so a frame with the same
+/// inputs can skip `reset_colors` and the built-in color effects and keep the
+/// sprite colors it already has. Saves rewriting every cell's `Sprite` twice
+/// a frame for effects that sit still, like a `Rainbow` on a paused clock.
+///
+/// Only `Rainbow`, `Shiny` and `GradientTint` can be idle: their colors
+/// follow from their fields and the effect clock alone. `Glow` also scales
+/// cells, which `reset_transforms` undoes every frame, so it always runs.
+/// Any other running effect, a redraw, or a change to a cell, the selection
+/// or line highlight, the cursor or the config makes the pass run.
+#[derive(Resource)]
+pub struct ColorFrameCache<T: 'static + Send + Sync> {
+    /// Fingerprint of last frame's inputs, None if they couldn't be taken.
+    key: Option<u64>,
+    idle: bool,
+    _marker: PhantomData<T>,
+}
+
+impl<T: 'static + Send + Sync> Default for ColorFrameCache<T> {
+    fn default() -> Self {
+        Self {
+            key: None,
+            idle: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: 'static + Send + Sync> ColorFrameCache<T> {
+    /// Whether this frame's color pass is being skipped.
+    pub fn is_idle(&self) -> bool {
+        self.idle
+    }
+
+    /// Run the color pass next frame. For custom systems that recolor
+    /// cells without an effect entity to show for it.
+    pub fn invalidate(&mut self) {
+        self.key = None;
+    }
+}
+
+/// The color effects `ColorFrameCache` can prove idle.
+type PureColorEffects = (
+    Option<Ref<'static, rainbow::Rainbow>>,
+    Option<Ref<'static, shiny::Shiny>>,
+    Option<Ref<'static, gradient_tint::GradientTint>>,
+);
+
+/// Effects that recolor cells from state they advance themselves.
+type StatefulColorEffects = Or<(
+    With<fade::Fade>,
+    With<flash::FlashOnChange>,
+    With<flip::Flip>,
+    With<glitch::Glitch>,
+    With<line_reveal::LineReveal>,
+)>;
+
+/// Effects that only move cells and never touch their colors.
+type GeometryOnlyEffects = Or<(
+    With<breathe::Breathe>,
+    With<bubbly::Bubbly>,
+    With<collapse::Collapse>,
+    With<explode::Explode>,
+    With<gravity::Gravity>,
+    With<jitter::Jitter>,
+    With<knock::Knock>,
+    With<magnet::Magnet>,
+    With<ripple::Ripple>,
+    With<scatter::Scatter>,
+    With<slash::Slash>,
+    With<swirl::Swirl>,
+    With<wave::Wave>,
+)>;
+
+/// Fingerprints this frame's color inputs into `ColorFrameCache<T>` and
+/// marks the frame idle when they match last frame's. Runs just before
+/// `reset_colors`.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn update_color_frame_cache<T: 'static + Send + Sync>(
+    mut cache: ResMut<ColorFrameCache<T>>,
+    clock: Res<EffectClock<T>>,
+    config: Res<TerminalConfig<T>>,
+    sync_gen: Res<SyncGeneration<T>>,
+    selection: Res<Selection<T>>,
+    line_highlight: Res<LineHighlight<T>>,
+    cursor: Res<CursorState<T>>,
+    flash_timers: Res<flash::FlashTimers<T>>,
+    effects: Query<
+        (
+            Entity,
+            Has<EffectPaused>,
+            PureColorEffects,
+            Option<Ref<EffectRegion>>,
+            Option<Ref<CellMask>>,
+            Option<Ref<EffectEnvelope>>,
+        ),
+        With<TargetTerminal<T>>,
+    >,
+    stateful: Query<(), (StatefulColorEffects, RunningEffect<T>)>,
+    geometry: Query<(), GeometryOnlyEffects>,
+    changed_cells: Query<
+        (),
+        (
+            With<TerminalCell<T>>,
+            Or<(Changed<CellStyle>, Changed<ColorOverride>)>,
+        ),
+    >,
+    mut removed_overrides: RemovedComponents<ColorOverride>,
+) {
+    // Removing an override has to restore the buffer's colors
+    let overrides_removed = !removed_overrides.is_empty();
+    removed_overrides.clear();
+
+    // A sync repaints dirty cells' sprites even when their style is unchanged
+    let mut dirty = config.is_changed()
+        || sync_gen.is_changed()
+        || selection.is_changed()
+        || line_highlight.is_changed()
+        || cursor.is_changed()
+        || !changed_cells.is_empty()
+        || overrides_removed
+        || !stateful.is_empty()
+        || !flash_timers.timers.is_empty();
+    // A blinking inverted cursor recolors its cell on its own timer
+    dirty |= config.cursor_style == CursorStyle::Invert
+        && cursor.visible
+        && config.cursor_blink_hz.is_some();
+
+    let mut hasher = DefaultHasher::new();
+    let mut animated = false;
+    for (entity, paused, (rainbow, shiny, tint), region, mask, envelope) in effects.iter() {
+        // Pausing or removing an effect changes the set, and so the key
+        (entity, paused).hash(&mut hasher);
+        if paused {
+            continue;
+        }
+        let pure = [
+            rainbow.map(|e| (e.is_changed(), e.speed)),
+            shiny.map(|e| (e.is_changed(), e.speed)),
+            tint.map(|e| (e.is_changed(), e.speed)),
+        ];
+        // Custom effects may recolor too, so assume they do
+        if pure.iter().all(Option::is_none) {
+            dirty |= !geometry.contains(entity);
+            continue;
         }
+        for (changed, speed) in pure.into_iter().flatten() {
+            dirty |= changed;
+            animated |= speed != 0.0;
+        }
+        dirty |= region.is_some_and(|r| r.is_changed())
+            || mask.is_some_and(|m| m.is_changed())
+            || envelope.is_some_and(|e| e.is_changed());
+    }
+    if animated {
+        clock.elapsed_secs().to_bits().hash(&mut hasher);
     }
+
+    let key = (!dirty).then(|| hasher.finish());
+    cache.idle = key.is_some() && key == cache.key;
+    cache.key = key;
+}
+
+/// Run condition: true unless `ColorFrameCache<T>` found this frame's color
+/// inputs unchanged.
+pub fn color_frame_dirty<T: 'static + Send + Sync>(cache: Res<ColorFrameCache<T>>) -> bool {
+    !cache.idle
 }
 
-/// Deterministic xor-shift hash for procedural effects (Glitch, Jitter).
+/// Deterministic integer hash for procedural effects (Glitch, Jitter).
 /// Avoids pulling in a `rand` dependency.
+///
+/// `a` is fully mixed before `b` is folded in, then the result gets two more
+/// rounds, so neighboring cells and consecutive time slots come out
+/// uncorrelated instead of drifting together along the grid.
 pub fn simple_hash(a: u32, b: u32) -> u32 {
-    let mut h = a.wrapping_mul(2654435761).wrapping_add(b.wrapping_mul(2246822519));
+    let mut h = fmix32(a.wrapping_add(0x9E37_79B9));
+    h ^= b.wrapping_mul(0x85EB_CA77).rotate_left(15);
+    h = fmix32(h.rotate_left(13));
+    fmix32(h.wrapping_mul(5).wrapping_add(0xE654_6B64))
+}
+
+/// Murmur3's 32-bit finalizer: every input bit flips each output bit with
+/// roughly even odds.
+fn fmix32(mut h: u32) -> u32 {
     h ^= h >> 16;
-    h = h.wrapping_mul(2246822519);
+    h = h.wrapping_mul(0x85EB_CA6B);
     h ^= h >> 13;
-    h = h.wrapping_mul(3266489917);
+    h = h.wrapping_mul(0xC2B2_AE35);
     h ^= h >> 16;
     h
 }
 
+/// `simple_hash` with a per-effect seed mixed in. Seed 0 gives exactly
+/// `simple_hash(a, b)`, so unseeded effects keep their original pattern.
+pub fn seeded_hash(a: u32, b: u32, seed: u32) -> u32 {
+    simple_hash(a, b ^ seed.wrapping_mul(0x9E37_79B9))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_ripple_envelope_gain() {
+        use super::ripple::Ripple;
+
+        let mut ripple = Ripple::at(3, 4);
+        assert_eq!((ripple.origin_col, ripple.origin_row), (3.0, 4.0));
+        assert_eq!(ripple.envelope_gain(), 1.0);
+        ripple.elapsed = ripple.envelope.unwrap() / 2.0;
+        assert_eq!(ripple.envelope_gain(), 0.25);
+        ripple.elapsed = 10.0;
+        assert_eq!(ripple.envelope_gain(), 0.0);
+
+        // Continuous ripples never decay
+        let continuous = Ripple {
+            elapsed: 10.0,
+            ..default()
+        };
+        assert_eq!(continuous.envelope_gain(), 1.0);
+    }
+
+    #[test]
+    fn test_region_builder() {
+        let region = EffectRegion::builder()
+            .include(0, 0, 10, 10)
+            .include(20, 0, 5, 5)
+            .exclude(2, 2, 2, 2)
+            .build();
+        assert!(region.contains(0, 0) && region.contains(22, 1));
+        assert!(!region.contains(3, 3) && !region.contains(15, 0));
+
+        // Without includes, only the excludes are skipped
+        let holes = EffectRegion::all().with_exclude(0, 0, 1, 1);
+        assert!(!holes.contains(0, 0) && holes.contains(70, 20));
+    }
+
+    #[test]
+    fn test_region_split_helpers() {
+        let (cols, rows) = (81, 25);
+        let left = EffectRegion::left_half(cols, rows);
+        let right = EffectRegion::right_half(cols, rows);
+        for col in 0..cols {
+            // Halves partition the grid exactly
+            assert_ne!(left.contains(col, 0), right.contains(col, 0));
+        }
+        assert!(left.contains(39, 24) && right.contains(40, 24));
+
+        let top = EffectRegion::top_half(cols, rows);
+        let bottom = EffectRegion::bottom_half(cols, rows);
+        assert!(top.contains(0, 11) && !top.contains(0, 12));
+        assert!(bottom.contains(80, 12) && bottom.contains(80, 24));
+
+        let center = EffectRegion::center(cols, rows, 41, 13);
+        assert!(center.contains(20, 6) && center.contains(60, 18));
+        assert!(!center.contains(19, 6) && !center.contains(61, 18));
+
+        // Oversized centers clamp to the whole grid
+        let clamped = EffectRegion::center(10, 10, 20, 20);
+        assert_eq!((clamped.include[0].col, clamped.include[0].width), (0, 10));
+    }
+
     #[test]
     fn test_grid_rect_contains() {
         let rect = GridRect { col: 5, row: 10, width: 3, height: 2 };
@@ -198,6 +1007,1356 @@ mod tests {
         assert!(!region.contains(10, 10)); // outside include
     }
 
+    #[test]
+    fn test_cell_mask_intersects_region() {
+        let region = EffectRegion {
+            include: vec![GridRect { col: 0, row: 0, width: 4, height: 1 }],
+            exclude: vec![],
+        };
+        let mask = CellMask::non_space();
+        let text = CellStyle { symbol: "a".to_string(), ..default() };
+        let blank = CellStyle::default();
+
+        assert!(cell_in_effect(&region, Some(&mask), 0, 0, &text));
+        assert!(!cell_in_effect(&region, Some(&mask), 0, 0, &blank));
+        assert!(!cell_in_effect(&region, Some(&mask), 5, 0, &text)); // outside region
+        assert!(cell_in_effect(&region, None, 0, 0, &blank));
+    }
+
+    #[test]
+    fn test_cell_mask_chars() {
+        let mask = CellMask::Chars(vec!['│', '─']);
+        assert!(mask.matches_symbol("─"));
+        assert!(!mask.matches_symbol("x"));
+        assert!(CellMask::box_drawing().matches('┼'));
+    }
+
+    #[test]
+    fn test_effect_clock_scale_and_pause() {
+        struct Term;
+        let mut clock = EffectClock::<Term>::default();
+        clock.advance(0.5);
+        assert_eq!(clock.elapsed_secs(), 0.5);
+
+        clock.time_scale = 0.5;
+        clock.advance(1.0);
+        assert_eq!(clock.delta_secs(), 0.5);
+        assert_eq!(clock.elapsed_secs(), 1.0);
+
+        clock.paused = true;
+        clock.advance(1.0);
+        assert_eq!(clock.delta_secs(), 0.0);
+        assert_eq!(clock.elapsed_secs(), 1.0);
+    }
+
+    #[test]
+    fn test_effect_clock_fixed_step() {
+        struct Term;
+        let mut clock = EffectClock::<Term>::default();
+        clock.fixed_step = Some(0.25);
+        clock.advance(0.1);
+        assert_eq!(clock.delta_secs(), 0.0);
+        clock.advance(0.2);
+        assert_eq!(clock.delta_secs(), 0.25);
+        clock.advance(0.6);
+        assert_eq!(clock.delta_secs(), 0.5);
+        assert_eq!(clock.elapsed_secs(), 0.75);
+        assert_eq!((clock.steps(), clock.step_secs()), (2, 0.25));
+
+        clock.fixed_step = None;
+        clock.advance(0.1);
+        assert_eq!((clock.steps(), clock.step_secs()), (1, 0.1));
+    }
+
+    #[test]
+    fn test_seeded_hash() {
+        assert_eq!(seeded_hash(12, 34, 0), simple_hash(12, 34));
+        assert_ne!(seeded_hash(12, 34, 1), seeded_hash(12, 34, 2));
+        assert_eq!(seeded_hash(12, 34, 7), seeded_hash(12, 34, 7));
+    }
+
+    /// Pearson correlation between the two values of each pair.
+    fn correlation(pairs: &[(f64, f64)]) -> f64 {
+        let n = pairs.len() as f64;
+        let (mx, my) = pairs.iter().fold((0.0, 0.0), |(x, y), p| (x + p.0 / n, y + p.1 / n));
+        let (mut cov, mut vx, mut vy) = (0.0, 0.0, 0.0);
+        for &(x, y) in pairs {
+            cov += (x - mx) * (y - my);
+            vx += (x - mx) * (x - mx);
+            vy += (y - my) * (y - my);
+        }
+        cov / (vx * vy).sqrt()
+    }
+
+    #[test]
+    fn test_simple_hash_neighbors_uncorrelated() {
+        // The hash this replaced mixed `a` and `b` in one multiply-add, so it
+        // only ever saw `a * 2654435761 + b * 2246822519`
+        fn linear_hash(a: u32, b: u32) -> u32 {
+            let mut h = a
+                .wrapping_mul(2654435761)
+                .wrapping_add(b.wrapping_mul(2246822519));
+            h ^= h >> 16;
+            h = h.wrapping_mul(2246822519);
+            h ^= h >> 13;
+            h = h.wrapping_mul(3266489917);
+            h ^= h >> 16;
+            h
+        }
+
+        // Values the way Bubbly and Jitter derive them, paired between each
+        // cell of a 128x128 grid and the one (dc, dr) away
+        let grid = |hash: fn(u32, u32) -> u32, dc: u32, dr: u32| {
+            let value = |a: u32, b: u32| (hash(a, b) % 1000) as f64;
+            let pairs: Vec<_> = (0..128u32)
+                .flat_map(|r| (0..128u32).map(move |c| (c, r)))
+                .map(|(c, r)| (value(c, r), value(c + dc, r + dr)))
+                .collect();
+            correlation(&pairs)
+        };
+        let value = |a: u32, b: u32| (simple_hash(a, b) % 1000) as f64;
+        let time: Vec<_> = (0..16384).map(|id| (value(id, 5), value(id, 6))).collect();
+
+        // Independent noise lands within about 0.01 of zero at these sizes
+        let neighbors = [
+            grid(simple_hash, 1, 0),
+            grid(simple_hash, 0, 1),
+            correlation(&time),
+        ];
+        for r in neighbors {
+            assert!(r.abs() < 0.03, "neighbor correlation {r}");
+        }
+
+        // The old hash repeated exactly every (29758, 19566), e.g. for Jitter
+        // cell ids that far apart that many time slots later
+        assert!(grid(linear_hash, 29758, 19566) > 0.999);
+        assert!(grid(simple_hash, 29758, 19566).abs() < 0.03);
+    }
+
+    #[test]
+    fn test_reset_colors_restores_fg_after_rainbow_removed() {
+        use crate::grid::GridPosition;
+        use rainbow::{rainbow_system, Rainbow};
+
+        struct Term;
+        let fg = Color::srgb(0.2, 0.4, 0.6);
+        let mut app = App::new();
+        let cell = app
+            .world_mut()
+            .spawn((
+                TerminalCell::<Term>::default(),
+                GridPosition { col: 0, row: 0 },
+                CellStyle { fg, symbol: "a".to_string(), ..default() },
+                Sprite::from_color(Color::BLACK, Vec2::ONE),
+            ))
+            .id();
+        let fg_entity = app
+            .world_mut()
+            .spawn((ForegroundSprite::<Term>::default(), Sprite::from_color(fg, Vec2::ONE)))
+            .id();
+        app.insert_resource(CellEntityIndex::<Term>::new(1, 1, vec![cell], vec![fg_entity]))
+            .insert_resource(TerminalConfig::<Term>::default())
+            .insert_resource(EffectClock::<Term>::default())
+            .add_systems(Update, (reset_colors::<Term>, rainbow_system::<Term>).chain());
+
+        let effect = app
+            .world_mut()
+            .spawn((Rainbow::default(), EffectRegion::all(), TargetTerminal::<Term>::default()))
+            .id();
+        app.update();
+        let fg_color = |app: &App| app.world().get::<Sprite>(fg_entity).unwrap().color;
+        assert_ne!(fg_color(&app), fg);
+
+        app.world_mut().despawn(effect);
+        app.update();
+        assert_eq!(fg_color(&app), fg);
+    }
+
+    #[test]
+    fn test_untargeted_effects_get_default_terminal() {
+        struct Term;
+        struct Other;
+        let mut app = App::new();
+        app.add_observer(target_untargeted_effects::<Term>);
+
+        let untargeted = app.world_mut().spawn(EffectRegion::all()).id();
+        let other = app
+            .world_mut()
+            .spawn((EffectRegion::all(), TargetTerminal::<Other>::default()))
+            .id();
+        app.update();
+
+        let world = app.world();
+        assert!(world.get::<TargetTerminal<Term>>(untargeted).is_some());
+        assert!(world.get::<TargetTerminal<Term>>(other).is_none());
+        assert!(world.get::<TargetTerminal<Other>>(other).is_some());
+    }
+
+    #[test]
+    fn test_spawn_effect_adds_region_and_target() {
+        use super::wave::Wave;
+
+        struct Term;
+        let mut world = World::new();
+        let mut queue = bevy::ecs::world::CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        let entity = spawn_effect::<Term>(&mut commands, Wave::default(), EffectRegion::all());
+        queue.apply(&mut world);
+
+        assert!(world.get::<Wave>(entity).is_some());
+        assert!(world.get::<EffectRegion>(entity).is_some());
+        assert!(world.get::<TargetTerminal<Term>>(entity).is_some());
+    }
+
+    #[test]
+    fn test_paused_effect_is_skipped_until_resumed() {
+        use super::wave::{wave_system, Wave};
+        use crate::grid::GridPosition;
+
+        struct Term;
+        let mut app = App::new();
+        app.insert_resource(EffectClock::<Term>::default())
+            .add_systems(Update, wave_system::<Term>);
+        let cell = app
+            .world_mut()
+            .spawn((
+                TerminalCell::<Term>::default(),
+                // A quarter wavelength in, so the wave moves it at t = 0
+                GridPosition { col: 2, row: 0 },
+                CellStyle::default(),
+                Transform::default(),
+            ))
+            .id();
+        let target = TargetTerminal::<Term>::default();
+        let effect = app
+            .world_mut()
+            .spawn((Wave::default(), EffectRegion::all(), target, EffectPaused))
+            .id();
+
+        let translation = |app: &App| app.world().get::<Transform>(cell).unwrap().translation;
+        app.update();
+        assert_eq!(translation(&app), Vec3::ZERO);
+
+        app.world_mut().entity_mut(effect).remove::<EffectPaused>();
+        app.update();
+        assert_ne!(translation(&app), Vec3::ZERO);
+    }
+
+    #[test]
+    fn test_gravity_only_moves_its_target_terminal() {
+        use super::gravity::{gravity_system, CellVelocity, Gravity};
+        use crate::grid::{BaseTransform, GridPosition};
+        use crate::{TerminalConfig, TerminalLayout};
+
+        struct Left;
+        struct Right;
+
+        fn spawn_cell<T: 'static + Send + Sync>(app: &mut App) -> Entity {
+            let mut clock = EffectClock::<T>::default();
+            clock.advance(0.1);
+            let layout = TerminalLayout::<T>::from_config(&TerminalConfig::default());
+            app.insert_resource(clock)
+                .insert_resource(layout)
+                .add_systems(Update, gravity_system::<T>);
+            app.world_mut()
+                .spawn((
+                    TerminalCell::<T>::default(),
+                    GridPosition { col: 0, row: 0 },
+                    CellStyle {
+                        symbol: "#".into(),
+                        ..default()
+                    },
+                    BaseTransform {
+                        translation: Vec3::ZERO,
+                        rotation: Quat::IDENTITY,
+                        scale: Vec3::ONE,
+                    },
+                    Transform::default(),
+                    CellVelocity::default(),
+                ))
+                .id()
+        }
+
+        let mut app = App::new();
+        let left = spawn_cell::<Left>(&mut app);
+        let right = spawn_cell::<Right>(&mut app);
+        let target = TargetTerminal::<Left>::default();
+        app.world_mut().spawn((Gravity::default(), EffectRegion::all(), target));
+        app.update();
+
+        let world = app.world();
+        assert!(world.get::<Transform>(left).unwrap().translation.y < 0.0);
+        assert!(world.get::<CellVelocity>(left).unwrap().velocity.y < 0.0);
+        assert_eq!(world.get::<Transform>(right).unwrap().translation, Vec3::ZERO);
+        assert_eq!(world.get::<CellVelocity>(right).unwrap().velocity, Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_fixed_step_gravity_matches_across_frame_rates() {
+        use super::gravity::{gravity_system, CellVelocity, Gravity};
+        use crate::grid::{BaseTransform, GridPosition};
+        use crate::{TerminalConfig, TerminalLayout};
+
+        struct Term;
+
+        // Offset after `frames` frames of `frame_time`, on 0.01s steps
+        fn fall(frames: usize, frame_time: f32) -> Vec2 {
+            let mut app = App::new();
+            let layout = TerminalLayout::<Term>::from_config(&TerminalConfig::default());
+            let clock = EffectClock::<Term> {
+                fixed_step: Some(0.01),
+                ..default()
+            };
+            app.insert_resource(clock)
+                .insert_resource(layout)
+                .add_systems(Update, gravity_system::<Term>);
+            let world = app.world_mut();
+            let cell = world
+                .spawn((
+                    TerminalCell::<Term>::default(),
+                    GridPosition { col: 0, row: 0 },
+                    CellStyle {
+                        symbol: "#".into(),
+                        ..default()
+                    },
+                    BaseTransform {
+                        translation: Vec3::ZERO,
+                        rotation: Quat::IDENTITY,
+                        scale: Vec3::ONE,
+                    },
+                    Transform::default(),
+                    CellVelocity::default(),
+                ))
+                .id();
+            let target = TargetTerminal::<Term>::default();
+            world.spawn((Gravity::default(), EffectRegion::all(), target));
+
+            for _ in 0..frames {
+                let mut clock = app.world_mut().resource_mut::<EffectClock<Term>>();
+                clock.advance(frame_time);
+                app.update();
+            }
+            app.world().get::<CellVelocity>(cell).unwrap().offset
+        }
+
+        // 0.04s as four single steps or two double ones
+        let offset = fall(4, 0.01);
+        assert!(offset.y < 0.0);
+        assert_eq!(offset, fall(2, 0.02));
+    }
+
+    /// Marker terminal for the `Gravity` floor tests.
+    struct Pile;
+
+    /// Cells in column 0, one per symbol from row 0 down, with `gravity` on
+    /// a default layout.
+    fn gravity_column(gravity: super::gravity::Gravity, symbols: &str) -> (App, Vec<Entity>) {
+        use super::gravity::{gravity_system, CellVelocity};
+        use crate::grid::{BaseTransform, GridPosition};
+
+        let layout = TerminalLayout::<Pile>::from_config(&TerminalConfig::default());
+        let mut app = App::new();
+        let world = app.world_mut();
+        let mut cells = Vec::new();
+        for (row, ch) in (0..).zip(symbols.chars()) {
+            let translation = layout.grid_to_world(0, row).extend(0.0);
+            let style = CellStyle {
+                symbol: ch.to_string(),
+                ..default()
+            };
+            let base = BaseTransform {
+                translation,
+                rotation: Quat::IDENTITY,
+                scale: Vec3::ONE,
+            };
+            let cell = world.spawn((
+                TerminalCell::<Pile>::default(),
+                GridPosition { col: 0, row },
+                style,
+                base,
+                Transform::from_translation(translation),
+                CellVelocity::default(),
+            ));
+            cells.push(cell.id());
+        }
+        let target = TargetTerminal::<Pile>::default();
+        world.spawn((gravity, EffectRegion::all(), target));
+        app.insert_resource(EffectClock::<Pile>::default())
+            .insert_resource(layout)
+            .add_systems(Update, gravity_system::<Pile>);
+        (app, cells)
+    }
+
+    /// Runs `frames` frames of 0.02s, returning each frame's velocity of `cell`.
+    fn run_gravity(app: &mut App, cell: Entity, frames: usize) -> Vec<Vec2> {
+        use super::gravity::CellVelocity;
+
+        let mut velocities = Vec::new();
+        for _ in 0..frames {
+            let mut clock = app.world_mut().resource_mut::<EffectClock<Pile>>();
+            clock.advance(0.02);
+            app.update();
+            velocities.push(app.world().get::<CellVelocity>(cell).unwrap().velocity);
+        }
+        velocities
+    }
+
+    /// Pixels from row `from` down to row `to` in the `Pile` layout.
+    fn rows_down(app: &App, from: u16, to: u16) -> f32 {
+        let layout = app.world().resource::<TerminalLayout<Pile>>();
+        layout.grid_to_world(0, to).y - layout.grid_to_world(0, from).y
+    }
+
+    #[test]
+    fn test_gravity_floor_stacks_cells_over_blanks() {
+        use super::gravity::{CellVelocity, Gravity};
+
+        let gravity = Gravity {
+            floor_row: Some(4),
+            restitution: 0.0,
+            ..default()
+        };
+        // Two glyphs over three blanks, as in a full-screen region
+        let (mut app, cells) = gravity_column(gravity, "ab   ");
+        run_gravity(&mut app, cells[0], 100);
+
+        let world = app.world();
+        let vel = |cell: Entity| *world.get::<CellVelocity>(cell).unwrap();
+        // The lower glyph lands on the floor and the upper one on top of it
+        assert!(vel(cells[1]).settled);
+        assert_eq!(vel(cells[1]).offset.y, rows_down(&app, 1, 4));
+        assert!(vel(cells[0]).settled);
+        assert_eq!(vel(cells[0]).offset.y, rows_down(&app, 0, 3));
+        for &blank in &cells[2..] {
+            assert_eq!(vel(blank).offset, Vec2::ZERO);
+            assert!(!vel(blank).settled);
+        }
+    }
+
+    #[test]
+    fn test_gravity_floor_restitution_bounces() {
+        use super::gravity::{CellVelocity, Gravity};
+
+        let bounces = |restitution: f32| {
+            let gravity = Gravity {
+                floor_row: Some(4),
+                restitution,
+                ..default()
+            };
+            let (mut app, cells) = gravity_column(gravity, "a");
+            let velocities = run_gravity(&mut app, cells[0], 200);
+            let rest = *app.world().get::<CellVelocity>(cells[0]).unwrap();
+            assert!(rest.settled);
+            assert_eq!(rest.offset.y, rows_down(&app, 0, 4));
+            velocities.iter().any(|v| v.y > 0.0)
+        };
+        assert!(bounces(0.5));
+        assert!(!bounces(0.0));
+    }
+
+    #[test]
+    fn test_gravity_settled_cells_stay_put() {
+        use super::gravity::{CellVelocity, Gravity};
+
+        let gravity = Gravity {
+            floor_row: Some(4),
+            restitution: 0.0,
+            ..default()
+        };
+        let (mut app, cells) = gravity_column(gravity, "a");
+        let resting = CellVelocity {
+            velocity: Vec2::ZERO,
+            offset: Vec2::new(0.0, -5.0),
+            settled: true,
+        };
+        app.world_mut().entity_mut(cells[0]).insert(resting);
+        run_gravity(&mut app, cells[0], 10);
+
+        let vel = app.world().get::<CellVelocity>(cells[0]).unwrap();
+        assert_eq!(vel.velocity, Vec2::ZERO);
+        assert_eq!(vel.offset, resting.offset);
+    }
+
+    #[test]
+    fn test_effect_origin_follows_moving_entity() {
+        use super::ripple::Ripple;
+        use crate::{TerminalConfig, TerminalLayout};
+
+        struct Term;
+        let layout = TerminalLayout::<Term>::from_config(&TerminalConfig::default());
+        let [start, moved] = [(3, 2), (5, 4)].map(|(col, row)| {
+            let world = layout.grid_to_world(col, row);
+            GlobalTransform::from_translation(world.extend(0.0))
+        });
+        let mut app = App::new();
+        app.insert_resource(layout)
+            .add_systems(Update, follow_effect_origin::<Term>);
+        let sprite = app.world_mut().spawn(start).id();
+        let origin = EffectOrigin::Entity(sprite);
+        let target = TargetTerminal::<Term>::default();
+        let ripple = (Ripple::at(0, 0), origin, target);
+        let effect = app.world_mut().spawn(ripple).id();
+
+        let follows = |app: &App, col: f32, row: f32| {
+            let ripple = app.world().get::<Ripple>(effect).unwrap();
+            ripple.origin().abs_diff_eq(Vec2::new(col, row), 1e-4)
+        };
+        app.update();
+        assert!(follows(&app, 3.0, 2.0));
+
+        *app.world_mut().get_mut::<GlobalTransform>(sprite).unwrap() = moved;
+        app.update();
+        assert!(follows(&app, 5.0, 4.0));
+
+        // A despawned target leaves the last origin in place
+        app.world_mut().despawn(sprite);
+        app.update();
+        assert!(follows(&app, 5.0, 4.0));
+    }
+
+    #[test]
+    fn test_color_override_wins_until_removed() {
+        use crate::grid::GridPosition;
+
+        struct Term;
+        let fg = Color::srgb(0.2, 0.4, 0.6);
+        let mut app = App::new();
+        let cell = app
+            .world_mut()
+            .spawn((
+                TerminalCell::<Term>::default(),
+                GridPosition { col: 0, row: 0 },
+                CellStyle { fg, ..default() },
+                ColorOverride {
+                    fg: Some(Color::WHITE),
+                    bg: None,
+                },
+                Sprite::from_color(Color::BLACK, Vec2::ONE),
+            ))
+            .id();
+        let fg_entity = app
+            .world_mut()
+            .spawn((ForegroundSprite::<Term>::default(), Sprite::from_color(fg, Vec2::ONE)))
+            .id();
+        app.insert_resource(CellEntityIndex::<Term>::new(1, 1, vec![cell], vec![fg_entity]))
+            .insert_resource(TerminalConfig::<Term>::default())
+            .add_systems(Update, reset_colors::<Term>);
+
+        let fg_color = |app: &App| app.world().get::<Sprite>(fg_entity).unwrap().color;
+        app.update();
+        assert_eq!(fg_color(&app), Color::WHITE);
+        assert_eq!(app.world().get::<Sprite>(cell).unwrap().color, CellStyle::default().bg);
+
+        app.world_mut().entity_mut(cell).remove::<ColorOverride>();
+        app.update();
+        assert_eq!(fg_color(&app), fg);
+    }
+
+    #[test]
+    fn test_removed_override_repaints_idle_frame() {
+        use crate::testing::TerminalTestHarness;
+        use crate::TerminalEmuPlugin;
+
+        struct Term;
+        // Under the full plugin an unchanged frame skips the color pass
+        let plugin = TerminalEmuPlugin::<Term>::new().columns(1).rows(1);
+        let mut harness = TerminalTestHarness::new(plugin);
+        harness.update();
+        let index = harness.app.world().resource::<CellEntityIndex<Term>>();
+        let (cell, fg_entity) = (index.get(0, 0).unwrap(), index.get_fg(0, 0).unwrap());
+        let shown = |harness: &TerminalTestHarness<Term>| {
+            harness.app.world().get::<Sprite>(fg_entity).unwrap().color
+        };
+        let buffer_fg = shown(&harness);
+
+        let white = ColorOverride {
+            fg: Some(Color::WHITE),
+            bg: None,
+        };
+        let idle = |harness: &TerminalTestHarness<Term>| {
+            let world = harness.app.world();
+            world.resource::<ColorFrameCache<Term>>().is_idle()
+        };
+        harness.app.world_mut().entity_mut(cell).insert(white);
+        harness.update().update();
+        assert_eq!(shown(&harness), Color::WHITE);
+        assert!(idle(&harness));
+
+        let world = harness.app.world_mut();
+        world.entity_mut(cell).remove::<ColorOverride>();
+        harness.update();
+        assert_eq!(shown(&harness), buffer_fg);
+    }
+
+    #[test]
+    fn test_flash_on_change_tints_only_redrawn_cell() {
+        use bevy::color::EuclideanDistance;
+        use ratatui::style::Style;
+
+        use super::flash::{FlashOnChange, FlashTimers};
+        use crate::testing::TerminalTestHarness;
+        use crate::TerminalEmuPlugin;
+
+        struct Term;
+        type Harness = TerminalTestHarness<Term>;
+        let plugin = TerminalEmuPlugin::<Term>::new().columns(3).rows(1);
+        let mut harness = Harness::new(plugin);
+        let draw = |harness: &mut Harness, text: &str| {
+            harness.draw(|frame| frame.buffer_mut().set_string(0, 0, text, Style::default()));
+        };
+        let bg = |harness: &Harness, col: u16| {
+            let world = harness.app.world();
+            let cell = world.resource::<CellEntityIndex<Term>>().get(col, 0);
+            world.get::<Sprite>(cell.unwrap()).unwrap().color
+        };
+        let synced = |harness: &Harness, col: u16| harness.style(col, 0).unwrap().bg;
+        let timers = |harness: &Harness| {
+            let world = harness.app.world();
+            world.resource::<FlashTimers<Term>>().timers.len()
+        };
+
+        draw(&mut harness, "abc");
+        let flash = FlashOnChange {
+            flash_color: Color::srgb(1.0, 0.0, 0.0),
+            decay: 0.1,
+            strength: 1.0,
+        };
+        let target = TargetTerminal::<Term>::default();
+        let world = harness.app.world_mut();
+        world.spawn((flash.clone(), EffectRegion::all(), target));
+        harness.update();
+        assert_eq!(timers(&harness), 0);
+
+        draw(&mut harness, "aXc");
+        assert!(bg(&harness, 1).distance(&flash.flash_color) < 1e-3);
+        assert_ne!(synced(&harness, 1), bg(&harness, 1));
+        assert_eq!(bg(&harness, 0), synced(&harness, 0));
+        assert_eq!(bg(&harness, 2), synced(&harness, 2));
+        assert_eq!(timers(&harness), 1);
+
+        // `decay` is six frames at 60 Hz
+        for _ in 0..8 {
+            harness.update();
+        }
+        assert_eq!(bg(&harness, 1), synced(&harness, 1));
+        assert_eq!(timers(&harness), 0);
+    }
+
+    #[test]
+    fn test_cell_opacity_combines_without_compounding() {
+        use crate::grid::GridPosition;
+        use fade::{fade_system, Fade};
+
+        struct Term;
+        let mut app = App::new();
+        let cell = app
+            .world_mut()
+            .spawn((
+                TerminalCell::<Term>::default(),
+                GridPosition { col: 0, row: 0 },
+                CellStyle::default(),
+                CellOpacity::default(),
+                Sprite::from_color(Color::BLACK, Vec2::ONE),
+            ))
+            .id();
+        let fg_entity = app
+            .world_mut()
+            .spawn((
+                ForegroundSprite::<Term>::default(),
+                Sprite::from_color(Color::WHITE, Vec2::ONE),
+            ))
+            .id();
+        app.insert_resource(CellEntityIndex::<Term>::new(1, 1, vec![cell], vec![fg_entity]))
+            .insert_resource(TerminalConfig::<Term>::default())
+            .insert_resource(EffectClock::<Term>::default())
+            .add_message::<EffectFinished>()
+            .add_systems(
+                Update,
+                (reset_colors::<Term>, fade_system::<Term>, apply_cell_opacity::<Term>).chain(),
+            );
+        let half = Fade {
+            from: 0.5,
+            to: 0.5,
+            duration: 0.0,
+            elapsed: 0.0,
+            active: true,
+        };
+        for _ in 0..2 {
+            app.world_mut()
+                .spawn((half.clone(), EffectRegion::all(), TargetTerminal::<Term>::default()));
+        }
+
+        for _ in 0..3 {
+            app.update();
+            let alpha = app.world().get::<Sprite>(fg_entity).unwrap().color.alpha();
+            assert!((alpha - 0.25).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_flip_progress_and_squash() {
+        use flip::Flip;
+
+        let mut flip = Flip {
+            duration: 1.0,
+            per_cell_stagger: 0.5,
+            elapsed: 1.0,
+            ..default()
+        };
+        assert_eq!(flip.progress(0, 0), 1.0);
+        assert_eq!(flip.progress(1, 0), 0.5);
+        assert_eq!(flip.progress(1, 1), 0.0);
+        flip.elapsed = 0.0;
+        assert_eq!(flip.progress(0, 0), 0.0);
+
+        assert_eq!(Flip::squash(0.0), 1.0);
+        assert!(Flip::squash(0.5) < 1e-6);
+        assert!((Flip::squash(1.0) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_flip_back_glyph_until_removed() {
+        use ratatui::style::Style;
+
+        use crate::atlas::FontAtlasResource;
+        use crate::testing::TerminalTestHarness;
+        use crate::TerminalEmuPlugin;
+        use flip::Flip;
+
+        struct Term;
+        type Harness = TerminalTestHarness<Term>;
+        let plugin = TerminalEmuPlugin::<Term>::new().columns(2).rows(1);
+        let mut harness = Harness::new(plugin);
+        harness.draw(|frame| frame.buffer_mut().set_string(0, 0, "ab", Style::default()));
+        let shown = |harness: &Harness, col: u16| {
+            let world = harness.app.world();
+            let fg = world.resource::<CellEntityIndex<Term>>().get_fg(col, 0);
+            let sprite = world.get::<Sprite>(fg.unwrap()).unwrap();
+            sprite.texture_atlas.as_ref().unwrap().index
+        };
+        let glyph = |harness: &Harness, ch: char| {
+            let atlas = harness.app.world().resource::<FontAtlasResource<Term>>();
+            atlas.glyph_map.get(&ch).copied()
+        };
+
+        // Past halfway right away; the back glyph is added to the atlas first
+        let flip = Flip {
+            duration: 0.0,
+            back_glyph: Some('@'),
+            ..default()
+        };
+        let region = EffectRegion::builder().include(0, 0, 1, 1).build();
+        let target = TargetTerminal::<Term>::default();
+        let effect = harness.app.world_mut().spawn((flip, region, target)).id();
+        for _ in 0..3 {
+            harness.update();
+        }
+        assert_eq!(Some(shown(&harness, 0)), glyph(&harness, '@'));
+        assert_eq!(Some(shown(&harness, 1)), glyph(&harness, 'b'));
+
+        harness.app.world_mut().despawn(effect);
+        harness.update();
+        assert_eq!(Some(shown(&harness, 0)), glyph(&harness, 'a'));
+    }
+
+    #[test]
+    fn test_collapse_direction_and_stagger_axis() {
+        use collapse::{Collapse, CollapseDir, StaggerAxis};
+
+        let collapse = Collapse {
+            gravity: 2.0,
+            elapsed: 1.0,
+            stagger_per_row: 0.5,
+            ..default()
+        };
+        assert_eq!(CollapseDir::default().vector(), Vec2::NEG_Y);
+        assert_eq!(collapse.fall(9, 0), 1.0);
+        assert_eq!(collapse.fall(0, 1), 0.25);
+
+        let sideways = Collapse {
+            direction: CollapseDir::Left,
+            stagger_axis: StaggerAxis::Column,
+            ..collapse
+        };
+        assert_eq!(sideways.direction.vector(), Vec2::NEG_X);
+        assert_eq!(sideways.fall(0, 9), 1.0);
+        assert_eq!(sideways.fall(1, 0), 0.25);
+        assert_eq!(sideways.fall(2, 0), 0.0);
+    }
+
+    #[test]
+    fn test_slash_crisscross_runs_until_last_blade() {
+        use slash::Slash;
+
+        let slash = Slash::crisscross(0.2);
+        assert_eq!(slash.blades.len(), 1);
+        assert_eq!(slash.blades[0].angle, -slash.angle);
+        assert_eq!(slash.total_duration(), slash.duration + 0.2);
+        assert_eq!(Slash::default().total_duration(), Slash::default().duration);
+    }
+
+    #[test]
+    fn test_rotate_about_keeps_pivot_in_place() {
+        let cell = Vec2::new(10.0, 20.0);
+        let quarter = Quat::from_rotation_z(std::f32::consts::FRAC_PI_2);
+
+        // The default pivot spins the cell in place
+        let mut centered = Transform::default();
+        rotate_about(&mut centered, quarter, Vec2::ZERO, cell);
+        assert_eq!(centered.translation, Vec3::ZERO);
+        assert_eq!(centered.rotation, quarter);
+
+        // Hinged on the bottom edge, that edge's midpoint doesn't move
+        let bottom = Vec2::new(0.0, -0.5);
+        let mut hinged = Transform::default();
+        rotate_about(&mut hinged, quarter, bottom, cell);
+        let edge = hinged.transform_point(Vec3::new(0.0, -10.0, 0.0));
+        assert!(edge.distance(Vec3::new(0.0, -10.0, 0.0)) < 1e-4);
+
+        // A second turn composes with the first around the same point
+        rotate_about(&mut hinged, quarter, bottom, cell);
+        let edge = hinged.transform_point(Vec3::new(0.0, -10.0, 0.0));
+        assert!(edge.distance(Vec3::new(0.0, -10.0, 0.0)) < 1e-4);
+        assert!(hinged.translation.distance(Vec3::new(0.0, -20.0, 0.0)) < 1e-4);
+    }
+
+    #[test]
+    fn test_knock_springiness_swings_past_rest() {
+        use knock::Knock;
+
+        let curve = |springiness| {
+            (1..100)
+                .map(|step| {
+                    let knock = Knock {
+                        elapsed: step as f32 / 100.0 * 0.6,
+                        springiness,
+                        ..default()
+                    };
+                    knock.strength()
+                })
+                .collect::<Vec<_>>()
+        };
+        let (plain, springy) = (curve(0.0), curve(2.0));
+
+        // Same jolt, but only the springy one overshoots
+        assert!((plain[24] - 1.0).abs() < 1e-3 && plain[24] == springy[24]);
+        assert!(plain.iter().all(|&s| s > 0.0));
+        let crossings = springy.windows(2).filter(|w| (w[0] > 0.0) != (w[1] > 0.0));
+        assert_eq!(crossings.count(), 4);
+    }
+
+    #[test]
+    fn test_explode_and_scatter_fall_under_gravity() {
+        use explode::Explode;
+        use scatter::Scatter;
+
+        let explode = Explode {
+            elapsed: 2.0,
+            ..default()
+        };
+        assert_eq!(explode.fall(), 0.0);
+        let falling = Explode {
+            gravity: 10.0,
+            ..explode
+        };
+        assert_eq!(falling.fall(), 20.0);
+
+        let scatter = Scatter {
+            elapsed: 1.0,
+            gravity: 300.0,
+            ..default()
+        };
+        assert_eq!(scatter.fall(), 150.0);
+    }
+
+    #[test]
+    fn test_scatter_composes_with_other_geometry_effects() {
+        use super::scatter::{scatter_system, Scatter};
+        use crate::grid::GridPosition;
+        use crate::{TerminalConfig, TerminalLayout};
+
+        struct Term;
+
+        // Cell transform after half a second under `count` identical scatters
+        fn scattered(count: usize) -> Transform {
+            let mut app = App::new();
+            let mut clock = EffectClock::<Term>::default();
+            clock.advance(0.5);
+            let layout = TerminalLayout::<Term>::from_config(&TerminalConfig::default());
+            app.insert_resource(clock)
+                .insert_resource(layout)
+                .add_systems(Update, scatter_system::<Term>);
+            let world = app.world_mut();
+            let cell = world
+                .spawn((
+                    TerminalCell::<Term>::default(),
+                    GridPosition { col: 0, row: 0 },
+                    CellStyle::default(),
+                    Transform::default(),
+                ))
+                .id();
+            for _ in 0..count {
+                let scatter = Scatter {
+                    origin_col: 4.0,
+                    origin_row: 0.0,
+                    ..default()
+                };
+                let target = TargetTerminal::<Term>::default();
+                world.spawn((scatter, EffectRegion::all(), target));
+            }
+            app.update();
+            *app.world().get::<Transform>(cell).unwrap()
+        }
+
+        let (once, twice) = (scattered(1), scattered(2));
+        assert!(once.scale.x < 1.0);
+        assert!((twice.scale - once.scale * once.scale).length() < 1e-5);
+        let doubled = once.rotation * once.rotation;
+        assert!(twice.rotation.angle_between(doubled) < 1e-5);
+    }
+
+    #[test]
+    fn test_swirl_angle_falloff() {
+        use swirl::Swirl;
+
+        let swirl = Swirl {
+            strength: 2.0,
+            radius: 4.0,
+            speed: 0.0,
+            ..default()
+        };
+        assert_eq!(swirl.angle(0.0, 0.0), 2.0);
+        assert_eq!(swirl.angle(2.0, 0.0), 0.5);
+        assert_eq!(swirl.angle(4.0, 0.0), 0.0);
+        assert_eq!(swirl.angle(10.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_magnet_progress_eases_and_retriggers() {
+        use magnet::Magnet;
+
+        let mut magnet = Magnet {
+            duration: 2.0,
+            ..default()
+        };
+        assert_eq!(magnet.progress(), 0.0);
+        magnet.elapsed = 1.0;
+        assert_eq!(magnet.progress(), 0.5);
+        magnet.elapsed = 0.2;
+        assert!(magnet.progress() < 0.1);
+        magnet.elapsed = 3.0;
+        assert!(magnet.is_finished());
+        assert_eq!(magnet.progress(), 1.0);
+
+        magnet.retrigger();
+        assert!(magnet.active);
+        assert!(!magnet.is_finished());
+        assert_eq!(magnet.progress(), 0.0);
+    }
+
+    #[test]
+    fn test_rainbow_spread_per_axis() {
+        use rainbow::Rainbow;
+
+        let horizontal = Rainbow {
+            speed: 0.0,
+            spread: 0.25,
+            row_spread: Some(0.0),
+            ..default()
+        };
+        assert_eq!(horizontal.hue(1, 0, 0.0), 90.0);
+        assert_eq!(horizontal.hue(1, 7, 0.0), 90.0);
+        assert_eq!(horizontal.hue(5, 0, 0.0), 90.0);
+        let vertical = Rainbow {
+            col_spread: Some(0.0),
+            row_spread: None,
+            ..horizontal
+        };
+        assert_eq!(vertical.hue(7, 1, 0.0), 90.0);
+
+        // Defaults keep the old diagonal bands
+        let diagonal = Rainbow::default();
+        assert_eq!(diagonal.hue(2, 0, 0.5), diagonal.hue(0, 2, 0.5));
+    }
+
+    #[test]
+    fn test_shiny_direction_snaps_unless_diagonal() {
+        use shiny::Shiny;
+
+        let shiny = Shiny::default();
+        assert_eq!(shiny.direction(), shiny.angle.sin_cos());
+
+        let straight = Shiny {
+            diagonal: false,
+            ..shiny
+        };
+        assert_eq!(straight.direction(), (0.0, 1.0));
+        let down = Shiny {
+            angle: 1.4,
+            ..straight
+        };
+        assert_eq!(down.direction(), (1.0, 0.0));
+    }
+
+    #[test]
+    fn test_reveal_units_by_word_and_line() {
+        use line_reveal::{reveal_units, RevealUnit};
+
+        // 4 columns x 3 rows: "ab c" / "    " / "d" then a cell outside
+        let (b, t, out) = (Some(true), Some(false), None);
+        let cells = [t, t, b, t, b, b, b, b, t, b, b, out];
+
+        let (units, count) = reveal_units(RevealUnit::Word, 4, &cells);
+        assert_eq!(count, 3);
+        assert_eq!(&units[..4], [Some(0), Some(0), None, Some(1)]);
+        assert_eq!(units[8], Some(2));
+
+        let (units, count) = reveal_units(RevealUnit::Line, 4, &cells);
+        assert_eq!(count, 2);
+        assert_eq!(&units[..4], [Some(0); 4]);
+        assert_eq!(units[4], None);
+        assert_eq!(&units[8..], [Some(1), Some(1), Some(1), None]);
+    }
+
+    #[test]
+    fn test_line_reveal_unit_alpha_staggers() {
+        use line_reveal::LineReveal;
+
+        let reveal = LineReveal {
+            interval: 1.0,
+            fade_duration: 0.5,
+            elapsed: 1.25,
+            ..default()
+        };
+        assert_eq!(reveal.unit_alpha(0), 1.0);
+        assert_eq!(reveal.unit_alpha(1), 0.5);
+        assert_eq!(reveal.unit_alpha(2), 0.0);
+    }
+
+    #[test]
+    fn test_line_reveal_splits_redrawn_text_again() {
+        use ratatui::style::Style;
+
+        use crate::testing::TerminalTestHarness;
+        use crate::TerminalEmuPlugin;
+        use line_reveal::LineReveal;
+
+        struct Term;
+        type Harness = TerminalTestHarness<Term>;
+        let plugin = TerminalEmuPlugin::<Term>::new().columns(2).rows(2);
+        let mut harness = Harness::new(plugin);
+        let alpha = |harness: &Harness, row: u16| {
+            let world = harness.app.world();
+            let fg = world.resource::<CellEntityIndex<Term>>().get_fg(0, row);
+            world.get::<Sprite>(fg.unwrap()).unwrap().color.alpha()
+        };
+
+        harness.draw(|frame| frame.buffer_mut().set_string(0, 0, "ab", Style::default()));
+        let reveal = LineReveal {
+            interval: 10.0,
+            fade_duration: 10.0,
+            ..default()
+        };
+        let target = TargetTerminal::<Term>::default();
+        let world = harness.app.world_mut();
+        let effect = world.spawn((reveal, EffectRegion::all(), target)).id();
+        harness.update();
+        assert!(alpha(&harness, 0) < 0.1);
+
+        // The new line becomes the second unit, still waiting its turn
+        harness.draw(|frame| frame.buffer_mut().set_string(0, 1, "cd", Style::default()));
+        assert_eq!(alpha(&harness, 1), 0.0);
+        let reveal = harness.app.world().get::<LineReveal>(effect).unwrap();
+        assert!(reveal.active);
+    }
+
+    #[test]
+    fn test_shiny_band_reaches_far_corner() {
+        use shiny::Shiny;
+
+        let shiny = Shiny::default();
+        let (columns, rows) = (160, 48);
+        let (sin_a, cos_a) = shiny.direction();
+        let far_corner = 159.0 * cos_a + 47.0 * sin_a;
+
+        // One full cycle, sampled every frame at 60 Hz
+        let cycle = far_corner + 2.0 * shiny.width;
+        let frames = (cycle / shiny.speed * 60.0).ceil() as usize;
+        let closest = (0..=frames)
+            .map(|frame| shiny.band_position(frame as f32 / 60.0, columns, rows))
+            .map(|pos| (pos - far_corner).abs())
+            .fold(f32::INFINITY, f32::min);
+        assert!(closest < 0.5, "band came no closer than {closest}");
+
+        // ...and starts over from the near corner afterwards
+        let restart = shiny.band_position(cycle / shiny.speed + 0.001, columns, rows);
+        assert!(restart < 0.0);
+    }
+
+    #[test]
+    fn test_redraw_mid_fade_keeps_effect_on_new_colors() {
+        use ratatui::style::{Color as RatColor, Style};
+
+        use crate::testing::TerminalTestHarness;
+        use crate::TerminalEmuPlugin;
+        use fade::Fade;
+
+        struct Term;
+        let plugin = TerminalEmuPlugin::<Term>::new().columns(2).rows(1);
+        let mut harness = TerminalTestHarness::new(plugin);
+        let half = Fade {
+            from: 0.5,
+            to: 0.5,
+            duration: 10.0,
+            elapsed: 0.0,
+            active: true,
+        };
+        let target = TargetTerminal::<Term>::default();
+        let world = harness.app.world_mut();
+        world.spawn((half, EffectRegion::all(), target));
+
+        // Each draw must come out faded on the frame it lands, in its own colors
+        for (symbol, fg, bg) in [
+            ("a", RatColor::Red, RatColor::Blue),
+            ("b", RatColor::Green, RatColor::Yellow),
+        ] {
+            let drawn = Style::default().fg(fg).bg(bg);
+            harness.draw(|frame| {
+                frame.buffer_mut().set_string(0, 0, symbol, drawn);
+            });
+            let style = harness.style(0, 0).unwrap();
+            assert_eq!(style.symbol, symbol);
+            let world = harness.app.world();
+            let index = world.resource::<CellEntityIndex<Term>>();
+            let sprite_color = |entity| world.get::<Sprite>(entity).unwrap().color;
+            let shown_fg = sprite_color(index.get_fg(0, 0).unwrap());
+            let shown_bg = sprite_color(index.get(0, 0).unwrap());
+            assert_eq!(shown_fg, style.fg.with_alpha(0.5));
+            assert_eq!(shown_bg, style.bg.with_alpha(0.5));
+        }
+    }
+
+    #[test]
+    fn test_fade_reports_finished_once() {
+        use crate::testing::TerminalTestHarness;
+        use crate::TerminalEmuPlugin;
+        use fade::Fade;
+
+        struct Term;
+        let plugin = TerminalEmuPlugin::<Term>::new().columns(2).rows(1);
+        let mut harness = TerminalTestHarness::new(plugin);
+        // Ticks once and returns the effects that reported finishing
+        let finished = |harness: &mut TerminalTestHarness<Term>| -> Vec<Entity> {
+            harness.update();
+            let world = harness.app.world_mut();
+            let mut messages = world.resource_mut::<Messages<EffectFinished>>();
+            messages.drain().map(|message| message.effect).collect()
+        };
+
+        // A zero-length fade completes on its first frame
+        let target = TargetTerminal::<Term>::default();
+        let world = harness.app.world_mut();
+        let fade = Fade::fade_out(0.0);
+        let instant = world.spawn((fade, EffectRegion::all(), target)).id();
+        assert_eq!(finished(&mut harness), vec![instant]);
+        assert!(finished(&mut harness).is_empty());
+
+        // Three frames cover 0.04s
+        let target = TargetTerminal::<Term>::default();
+        let world = harness.app.world_mut();
+        world.despawn(instant);
+        let fade = Fade::fade_in(0.04);
+        let timed = world.spawn((fade, EffectRegion::all(), target)).id();
+        assert!(finished(&mut harness).is_empty());
+        assert!(finished(&mut harness).is_empty());
+        assert_eq!(finished(&mut harness), vec![timed]);
+        assert!(finished(&mut harness).is_empty());
+
+        let fade = harness.app.world().get::<Fade>(timed).unwrap();
+        assert!(!fade.active);
+        assert_eq!(fade.alpha(), 1.0);
+    }
+
+    #[test]
+    fn test_zero_length_magnet_reports_finished() {
+        use crate::testing::TerminalTestHarness;
+        use crate::TerminalEmuPlugin;
+        use magnet::Magnet;
+
+        struct Term;
+        let plugin = TerminalEmuPlugin::<Term>::new().columns(2).rows(1);
+        let mut harness = TerminalTestHarness::new(plugin);
+        let finished = |harness: &mut TerminalTestHarness<Term>| -> Vec<Entity> {
+            harness.update();
+            let world = harness.app.world_mut();
+            let mut messages = world.resource_mut::<Messages<EffectFinished>>();
+            messages.drain().map(|message| message.effect).collect()
+        };
+
+        let magnet = Magnet {
+            duration: 0.0,
+            ..default()
+        };
+        let target = TargetTerminal::<Term>::default();
+        let world = harness.app.world_mut();
+        let effect = world.spawn((magnet, EffectRegion::all(), target)).id();
+        assert_eq!(finished(&mut harness), vec![effect]);
+        assert!(finished(&mut harness).is_empty());
+
+        // Retriggering reports again
+        let world = harness.app.world_mut();
+        world.get_mut::<Magnet>(effect).unwrap().retrigger();
+        assert_eq!(finished(&mut harness), vec![effect]);
+    }
+
+    #[test]
+    fn test_still_rainbow_goes_idle_until_cells_change() {
+        use ratatui::style::{Color as RatColor, Style};
+
+        use crate::testing::TerminalTestHarness;
+        use crate::TerminalEmuPlugin;
+        use rainbow::Rainbow;
+
+        struct Term;
+        let plugin = TerminalEmuPlugin::<Term>::new().columns(2).rows(1);
+        let mut harness = TerminalTestHarness::new(plugin);
+        let still = Rainbow {
+            speed: 0.0,
+            ..default()
+        };
+        let rainbow_fg =
+            |col| Color::hsl(still.hue(col, 0, 0.0), still.saturation, still.lightness);
+        let target = TargetTerminal::<Term>::default();
+        let world = harness.app.world_mut();
+        world.spawn((still.clone(), EffectRegion::all(), target));
+        harness.draw(|frame| {
+            frame.buffer_mut().set_string(0, 0, "ab", Style::default());
+        });
+
+        let shown_fg = |harness: &TerminalTestHarness<Term>, col| {
+            let world = harness.app.world();
+            let index = world.resource::<CellEntityIndex<Term>>();
+            let fg = index.get_fg(col, 0).unwrap();
+            world.get::<Sprite>(fg).unwrap().color
+        };
+        let idle = |harness: &TerminalTestHarness<Term>| {
+            let world = harness.app.world();
+            world.resource::<ColorFrameCache<Term>>().is_idle()
+        };
+
+        // One frame to fingerprint, then nothing changes
+        harness.update().update();
+        assert!(idle(&harness));
+        assert_eq!(shown_fg(&harness, 1), rainbow_fg(1));
+
+        // A redraw runs the pass again, and the new text gets the effect too
+        harness.draw(|frame| {
+            let red = Style::default().fg(RatColor::Red);
+            frame.buffer_mut().set_string(0, 0, "c", red);
+        });
+        assert!(!idle(&harness));
+        assert_eq!(shown_fg(&harness, 0), rainbow_fg(0));
+        assert_eq!(shown_fg(&harness, 1), rainbow_fg(1));
+    }
+
+    #[test]
+    fn test_still_glow_keeps_scaling_cells() {
+        use ratatui::style::Style;
+
+        use crate::testing::TerminalTestHarness;
+        use crate::TerminalEmuPlugin;
+        use glow::Glow;
+
+        struct Term;
+        let plugin = TerminalEmuPlugin::<Term>::new().columns(2).rows(1);
+        let mut harness = TerminalTestHarness::new(plugin);
+        let still = Glow {
+            speed: 0.0,
+            ..default()
+        };
+        let target = TargetTerminal::<Term>::default();
+        let world = harness.app.world_mut();
+        world.spawn((still, EffectRegion::all(), target));
+        harness.draw(|frame| {
+            frame.buffer_mut().set_string(0, 0, "ab", Style::default());
+        });
+
+        // The scale is reset every frame, so Glow has to reapply it
+        harness.update().update().update();
+        let world = harness.app.world();
+        assert!(!world.resource::<ColorFrameCache<Term>>().is_idle());
+        let cell = world.resource::<CellEntityIndex<Term>>().get(1, 0).unwrap();
+        assert_ne!(world.get::<Transform>(cell).unwrap().scale, Vec3::ONE);
+    }
+
+    #[test]
+    fn test_envelope_ramps_and_stops_from_current_gain() {
+        let mut envelope = EffectEnvelope::new(1.0, 2.0);
+        assert_eq!(envelope.gain(), 0.0);
+        envelope.elapsed = 0.5;
+        assert_eq!(envelope.gain(), 0.5);
+
+        // Stopping mid-attack ramps down from where it was
+        envelope.stop();
+        assert_eq!(envelope.gain(), 0.5);
+        envelope.elapsed += 0.5;
+        assert_eq!(envelope.gain(), 0.25);
+        assert!(!envelope.is_finished());
+        envelope.elapsed += 0.5;
+        assert!(envelope.is_finished());
+        assert_eq!(envelope_gain(None), 1.0);
+    }
+
+    #[test]
+    fn test_enveloped_wave_fades_in_and_despawns_after_release() {
+        use super::wave::{wave_system, Wave};
+        use crate::grid::GridPosition;
+
+        struct Term;
+        let mut app = App::new();
+        let systems = (advance_effect_envelopes::<Term>, wave_system::<Term>).chain();
+        app.insert_resource(EffectClock::<Term>::default())
+            .add_systems(Update, systems);
+        let cell = app
+            .world_mut()
+            .spawn((
+                TerminalCell::<Term>::default(),
+                // A quarter wavelength in, so the wave lifts it by its full
+                // amplitude at t = 0
+                GridPosition { col: 2, row: 0 },
+                CellStyle::default(),
+                Transform::default(),
+            ))
+            .id();
+        let wave = Wave {
+            speed: 0.0,
+            ..default()
+        };
+        let envelope = EffectEnvelope::new(1.0, 1.0);
+        let target = TargetTerminal::<Term>::default();
+        let effect = app
+            .world_mut()
+            .spawn((wave, EffectRegion::all(), target, envelope))
+            .id();
+        let step = |app: &mut App, secs: f32| {
+            let world = app.world_mut();
+            world.resource_mut::<EffectClock<Term>>().advance(secs);
+            world.entity_mut(cell).insert(Transform::default());
+            app.update();
+            app.world().get::<Transform>(cell).map(|t| t.translation.y)
+        };
+
+        let amplitude = Wave::default().amplitude;
+        assert!((step(&mut app, 0.5).unwrap() - amplitude / 2.0).abs() < 1e-4);
+        assert!((step(&mut app, 0.5).unwrap() - amplitude).abs() < 1e-4);
+
+        let world = app.world_mut();
+        world.get_mut::<EffectEnvelope>(effect).unwrap().stop();
+        assert!((step(&mut app, 0.25).unwrap() - amplitude * 0.75).abs() < 1e-4);
+        step(&mut app, 0.75);
+        assert!(app.world().get_entity(effect).is_err());
+    }
+
     #[test]
     fn test_effect_region_empty_include() {
         let region = EffectRegion::all();
@@ -1,7 +1,11 @@
+use bevy::color::Mix;
 use bevy::prelude::*;
 
-use super::{EffectRegion, TargetTerminal};
-use crate::grid::{CellEntityIndex, ForegroundSprite};
+use super::{
+    cell_in_effect, envelope_gain, CellMask, EffectClock, EffectEnvelope, EffectRegion,
+    RunningEffect,
+};
+use crate::grid::{CellEntityIndex, CellStyle, ForegroundSprite, TerminalCell};
 
 /// Rainbow color cycling effect.
 ///
@@ -14,8 +18,15 @@ pub struct Rainbow {
     pub saturation: f32,
     /// Color lightness (0.0 to 1.0).
     pub lightness: f32,
-    /// Spatial spread — how much hue varies across the grid.
+    /// Spatial spread — how much hue varies across the grid, as the shift
+    /// per column and per row in revolutions.
     pub spread: f32,
+    /// Hue shift per column, if it should differ from `spread` (0 = every
+    /// column alike).
+    pub col_spread: Option<f32>,
+    /// Hue shift per row, if it should differ from `spread` (0 = every row
+    /// alike).
+    pub row_spread: Option<f32>,
 }
 
 impl Default for Rainbow {
@@ -25,37 +36,65 @@ impl Default for Rainbow {
             saturation: 1.0,
             lightness: 0.6,
             spread: 0.3,
+            col_spread: None,
+            row_spread: None,
         }
     }
 }
 
+impl Rainbow {
+    /// Hue in degrees (0.0..360.0) of the cell at (col, row) at time `t`.
+    pub fn hue(&self, col: u16, row: u16, t: f32) -> f32 {
+        let col_spread = self.col_spread.unwrap_or(self.spread);
+        let row_spread = self.row_spread.unwrap_or(self.spread);
+        let offset = col as f32 * col_spread + row as f32 * row_spread;
+        ((offset + t * self.speed) * 360.0).rem_euclid(360.0)
+    }
+}
+
 /// System that applies the rainbow effect to foreground sprite colors.
 pub fn rainbow_system<T: 'static + Send + Sync>(
-    time: Res<Time>,
-    effects: Query<(&Rainbow, &EffectRegion), With<TargetTerminal<T>>>,
+    clock: Res<EffectClock<T>>,
+    effects: Query<
+        (
+            &Rainbow,
+            &EffectRegion,
+            Option<&CellMask>,
+            Option<&EffectEnvelope>,
+        ),
+        RunningEffect<T>,
+    >,
     cell_index: Res<CellEntityIndex<T>>,
+    styles: Query<&CellStyle, With<TerminalCell<T>>>,
     mut sprites: Query<&mut Sprite, With<ForegroundSprite<T>>>,
 ) {
-    let t = time.elapsed_secs();
+    let t = clock.elapsed_secs();
     let columns = cell_index.columns as usize;
 
-    for (rainbow, region) in effects.iter() {
+    for (rainbow, region, mask, envelope) in effects.iter() {
+        let gain = envelope_gain(envelope);
         for (idx, &fg_entity) in cell_index.fg_entities.iter().enumerate() {
             let col = (idx % columns) as u16;
             let row = (idx / columns) as u16;
 
-            if !region.contains(col, row) {
+            let Ok(style) = styles.get(cell_index.entities[idx]) else {
+                continue;
+            };
+            if !cell_in_effect(region, mask, col, row, style) {
                 continue;
             }
 
-            let hue = ((col as f32 + row as f32) * rainbow.spread + t * rainbow.speed)
-                * 360.0
-                % 360.0;
-
+            let hue = rainbow.hue(col, row, t);
             let color = Color::hsl(hue, rainbow.saturation, rainbow.lightness);
 
             if let Ok(mut sprite) = sprites.get_mut(fg_entity) {
                 let alpha = sprite.color.alpha();
+                // Partway through an envelope, blend from the cell's own color
+                let color = if gain < 1.0 {
+                    sprite.color.mix(&color, gain)
+                } else {
+                    color
+                };
                 sprite.color = color.with_alpha(alpha);
             }
         }
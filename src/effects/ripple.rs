@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 
-use super::{EffectRegion, TargetTerminal};
-use crate::grid::{GridPosition, TerminalCell};
+use super::{cell_in_effect, CellMask, EffectClock, EffectFinished, EffectRegion, RunningEffect};
+use crate::grid::{CellStyle, GridPosition, TerminalCell};
 
 /// A ripple effect that displaces cells in a wave pattern from an origin point.
 #[derive(Component, Clone, Debug)]
@@ -20,6 +20,12 @@ pub struct Ripple {
     pub phase: f32,
     /// Exponential damping factor (higher = faster falloff).
     pub damping: f32,
+    /// One-shot duration in seconds. Amplitude decays to zero over this
+    /// time, then the ripple deactivates. `None` ripples forever.
+    pub envelope: Option<f32>,
+    /// Seconds since the ripple started (only advanced with an envelope).
+    pub elapsed: f32,
+    pub active: bool,
 }
 
 impl Default for Ripple {
@@ -32,23 +38,66 @@ impl Default for Ripple {
             speed: 10.0,
             phase: 0.0,
             damping: 0.1,
+            envelope: None,
+            elapsed: 0.0,
+            active: true,
+        }
+    }
+}
+
+impl Ripple {
+    /// A one-shot ripple spreading from the cell at (col, row), e.g. where
+    /// the user clicked.
+    pub fn at(col: u16, row: u16) -> Self {
+        Self {
+            origin_col: col as f32,
+            origin_row: row as f32,
+            envelope: Some(1.5),
+            ..default()
+        }
+    }
+
+    /// Amplitude multiplier from the envelope: eases from 1 down to 0.
+    pub fn envelope_gain(&self) -> f32 {
+        match self.envelope {
+            Some(duration) if duration > 0.0 => {
+                let remaining = (1.0 - self.elapsed / duration).clamp(0.0, 1.0);
+                remaining * remaining
+            }
+            Some(_) => 0.0,
+            None => 1.0,
         }
     }
 }
 
 /// System that applies the ripple effect to cell transforms.
 pub fn ripple_system<T: 'static + Send + Sync>(
-    time: Res<Time>,
-    mut effects: Query<(&mut Ripple, &EffectRegion), With<TargetTerminal<T>>>,
-    mut cells: Query<(&GridPosition, &mut Transform), With<TerminalCell<T>>>,
+    clock: Res<EffectClock<T>>,
+    mut effects: Query<(Entity, &mut Ripple, &EffectRegion, Option<&CellMask>), RunningEffect<T>>,
+    mut cells: Query<(&GridPosition, &CellStyle, &mut Transform), With<TerminalCell<T>>>,
+    mut finished: MessageWriter<EffectFinished>,
 ) {
-    for (mut ripple, region) in effects.iter_mut() {
-        ripple.phase += ripple.speed * time.delta_secs();
+    for (entity, mut ripple, region, mask) in effects.iter_mut() {
+        if !ripple.active {
+            continue;
+        }
+
+        ripple.phase += ripple.speed * clock.delta_secs();
+
+        if let Some(duration) = ripple.envelope {
+            ripple.elapsed += clock.delta_secs();
+            if ripple.elapsed >= duration {
+                ripple.active = false;
+                finished.write(EffectFinished { effect: entity });
+                continue;
+            }
+        }
+        let amplitude = ripple.amplitude * ripple.envelope_gain();
 
         let two_pi = std::f32::consts::TAU;
 
-        for (pos, mut transform) in cells.iter_mut() {
-            if !region.contains(pos.col, pos.row) {
+        for (pos, style, mut transform) in cells.iter_mut() {
+            if !cell_in_effect(region, mask, pos.col, pos.row, style) {
                 continue;
             }
 
@@ -58,7 +107,7 @@ pub fn ripple_system<T: 'static + Send + Sync>(
 
             let wave = (two_pi * (distance / ripple.wavelength - ripple.phase)).sin();
             let decay = (-ripple.damping * distance).exp();
-            let displacement = ripple.amplitude * wave * decay;
+            let displacement = amplitude * wave * decay;
 
             transform.translation.y += displacement;
         }
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 
-use super::{EffectRegion, TargetTerminal};
-use crate::grid::{GridPosition, TerminalCell};
+use super::{cell_in_effect, CellMask, EffectClock, EffectRegion, RunningEffect};
+use crate::grid::{CellStyle, GridPosition, TerminalCell};
 use crate::TerminalLayout;
 
 /// Explosion effect that scatters cells outward from a center point.
@@ -21,6 +21,9 @@ pub struct Scatter {
     pub duration: f32,
     /// Spin speed in radians per second.
     pub spin: f32,
+    /// Downward acceleration in pixels per second squared, so cells arc and
+    /// fall (0 = straight lines).
+    pub gravity: f32,
     /// Whether the effect is currently active.
     pub active: bool,
 }
@@ -34,24 +37,32 @@ impl Default for Scatter {
             elapsed: 0.0,
             duration: 3.0,
             spin: 3.0,
+            gravity: 0.0,
             active: true,
         }
     }
 }
 
+impl Scatter {
+    /// Distance in pixels gravity has pulled every cell down so far.
+    pub fn fall(&self) -> f32 {
+        0.5 * self.gravity * self.elapsed * self.elapsed
+    }
+}
+
 /// System that applies the scatter effect to cell transforms.
 pub fn scatter_system<T: 'static + Send + Sync>(
-    time: Res<Time>,
+    clock: Res<EffectClock<T>>,
     layout: Res<TerminalLayout<T>>,
-    mut effects: Query<(&mut Scatter, &EffectRegion), With<TargetTerminal<T>>>,
-    mut cells: Query<(&GridPosition, &mut Transform), With<TerminalCell<T>>>,
+    mut effects: Query<(&mut Scatter, &EffectRegion, Option<&CellMask>), RunningEffect<T>>,
+    mut cells: Query<(&GridPosition, &CellStyle, &mut Transform), With<TerminalCell<T>>>,
 ) {
-    for (mut scatter, region) in effects.iter_mut() {
+    for (mut scatter, region, mask) in effects.iter_mut() {
         if !scatter.active {
             continue;
         }
 
-        scatter.elapsed += time.delta_secs();
+        scatter.elapsed += clock.delta_secs();
 
         if scatter.elapsed > scatter.duration {
             scatter.active = false;
@@ -60,9 +71,10 @@ pub fn scatter_system<T: 'static + Send + Sync>(
 
         let t = scatter.elapsed;
         let progress = t / scatter.duration; // 0.0 -> 1.0
+        let fall = scatter.fall();
 
-        for (pos, mut transform) in cells.iter_mut() {
-            if !region.contains(pos.col, pos.row) {
+        for (pos, style, mut transform) in cells.iter_mut() {
+            if !cell_in_effect(region, mask, pos.col, pos.row, style) {
                 continue;
             }
 
@@ -78,15 +90,15 @@ pub fn scatter_system<T: 'static + Send + Sync>(
             // Radial displacement grows over time
             let displacement = scatter.speed * t;
             transform.translation.x += nx * displacement;
-            transform.translation.y += ny * displacement;
+            transform.translation.y += ny * displacement - fall;
 
             // Spin increases over time
             let angle = scatter.spin * t * (1.0 + dist * 0.001);
-            transform.rotation = Quat::from_rotation_z(angle);
+            transform.rotation = Quat::from_rotation_z(angle) * transform.rotation;
 
             // Scale shrinks as effect progresses
             let scale = 1.0 - progress * 0.8; // shrink to 0.2
-            transform.scale = Vec3::splat(scale.max(0.0));
+            transform.scale *= Vec3::splat(scale.max(0.0));
         }
     }
 }
@@ -1,11 +1,12 @@
 use bevy::prelude::*;
 
-use super::{EffectRegion, TargetTerminal};
-use crate::grid::{CellEntityIndex, ForegroundSprite};
+use super::{cell_in_effect, CellMask, EffectClock, EffectRegion, RunningEffect};
+use crate::grid::{CellEntityIndex, CellStyle, ForegroundSprite, TerminalCell};
 
 /// Sweeping highlight band effect.
 ///
 /// A bright band sweeps diagonally across the grid, boosting foreground RGB.
+/// Each pass crosses the whole grid, corner to corner, whatever its size.
 #[derive(Component, Clone, Debug)]
 pub struct Shiny {
     /// Speed of the sweep (grid units per second).
@@ -14,6 +15,9 @@ pub struct Shiny {
     pub width: f32,
     /// Angle of the sweep in radians (0 = horizontal, PI/2 = vertical).
     pub angle: f32,
+    /// Sweep at `angle` (default: true). When false the band runs straight
+    /// across columns or down rows, whichever `angle` is closer to.
+    pub diagonal: bool,
     /// Maximum brightness multiplier at the center of the band.
     pub brightness: f32,
 }
@@ -24,11 +28,46 @@ impl Default for Shiny {
             speed: 8.0,
             width: 6.0,
             angle: 0.5,
+            diagonal: true,
             brightness: 2.0,
         }
     }
 }
 
+impl Shiny {
+    /// (sin, cos) of the direction the band travels in.
+    pub fn direction(&self) -> (f32, f32) {
+        if self.diagonal {
+            return self.angle.sin_cos();
+        }
+        // Snap to whichever axis the angle is closer to
+        let (sin_a, cos_a) = self.angle.sin_cos();
+        if cos_a.abs() >= sin_a.abs() {
+            (0.0, cos_a.signum())
+        } else {
+            (sin_a.signum(), 0.0)
+        }
+    }
+
+    /// Center of the band at time `t`, measured along the sweep direction,
+    /// on a `columns` x `rows` grid. The band enters just before the
+    /// nearest corner and leaves just past the farthest, then starts over.
+    pub fn band_position(&self, t: f32, columns: u16, rows: u16) -> f32 {
+        let (sin_a, cos_a) = self.direction();
+        let last_col = columns.saturating_sub(1) as f32 * cos_a;
+        let last_row = rows.saturating_sub(1) as f32 * sin_a;
+        let corners = [0.0, last_col, last_row, last_col + last_row];
+        let near = corners.into_iter().fold(f32::INFINITY, f32::min);
+        let far = corners.into_iter().fold(f32::NEG_INFINITY, f32::max);
+
+        let cycle = far - near + 2.0 * self.width;
+        if cycle <= 0.0 {
+            return near;
+        }
+        near - self.width + (t * self.speed).rem_euclid(cycle)
+    }
+}
+
 /// Smoothstep interpolation.
 fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
     let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
@@ -37,27 +76,28 @@ fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
 
 /// System that applies the shiny sweep effect to foreground sprites.
 pub fn shiny_system<T: 'static + Send + Sync>(
-    time: Res<Time>,
-    effects: Query<(&Shiny, &EffectRegion), With<TargetTerminal<T>>>,
+    clock: Res<EffectClock<T>>,
+    effects: Query<(&Shiny, &EffectRegion, Option<&CellMask>), RunningEffect<T>>,
     cell_index: Res<CellEntityIndex<T>>,
+    styles: Query<&CellStyle, With<TerminalCell<T>>>,
     mut sprites: Query<&mut Sprite, With<ForegroundSprite<T>>>,
 ) {
-    let t = time.elapsed_secs();
+    let t = clock.elapsed_secs();
     let columns = cell_index.columns as usize;
 
-    for (shiny, region) in effects.iter() {
-        let cos_a = shiny.angle.cos();
-        let sin_a = shiny.angle.sin();
-        // Diagonal length of the grid (generous upper bound)
-        let diagonal = 200.0_f32;
-        let band_pos = (t * shiny.speed) % diagonal - shiny.width;
+    for (shiny, region, mask) in effects.iter() {
+        let (sin_a, cos_a) = shiny.direction();
+        let band_pos = shiny.band_position(t, cell_index.columns, cell_index.rows);
         let half_width = shiny.width / 2.0;
 
         for (idx, &fg_entity) in cell_index.fg_entities.iter().enumerate() {
             let col = (idx % columns) as u16;
             let row = (idx / columns) as u16;
 
-            if !region.contains(col, row) {
+            let Ok(style) = styles.get(cell_index.entities[idx]) else {
+                continue;
+            };
+            if !cell_in_effect(region, mask, col, row, style) {
                 continue;
             }
 
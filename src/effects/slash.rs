@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 
-use super::{EffectRegion, TargetTerminal};
-use crate::grid::{GridPosition, TerminalCell};
+use super::{cell_in_effect, rotate_about, CellMask, EffectClock, EffectRegion, RunningEffect};
+use crate::grid::{CellStyle, GridPosition, TerminalCell};
 use crate::TerminalLayout;
 
 /// Slash effect — a blade cuts across the region along a line, splitting cells
@@ -11,6 +11,12 @@ use crate::TerminalLayout;
 /// blade's wavefront get displaced outward (perpendicular to the cut).
 /// Displacement is strongest at the center of the line and fades toward edges.
 /// After the blade finishes its pass, the split eases closed.
+///
+/// `blades` adds more cuts to the same effect, e.g. `Slash::crisscross` for
+/// an X. Blades, like separate `Slash` components on one cell, compose the
+/// same way as every geometry effect: offsets and rotations add, scales
+/// multiply. Each frame starts again from the cell's base transform, so the
+/// result stays bounded by the number of cuts overlapping a cell.
 #[derive(Component, Clone, Debug)]
 pub struct Slash {
     /// How long the slash has been running.
@@ -23,6 +29,11 @@ pub struct Slash {
     pub width: f32,
     /// Angle of the slash line in radians (0 = horizontal, PI/4 = diagonal).
     pub angle: f32,
+    /// Extra cuts, each making its own pass of `duration` (default: none).
+    pub blades: Vec<Blade>,
+    /// Point each cell rotates around, in cell sizes from its center
+    /// (default: the center). See `rotate_about`.
+    pub pivot: Vec2,
     /// Whether the effect is currently active.
     pub active: bool,
 }
@@ -35,32 +46,62 @@ impl Default for Slash {
             amplitude: 8.0,
             width: 4.0,
             angle: std::f32::consts::FRAC_PI_4,
+            blades: Vec::new(),
+            pivot: Vec2::ZERO,
             active: true,
         }
     }
 }
 
-/// System that applies the slash effect to cell transforms.
-pub fn slash_system<T: 'static + Send + Sync>(
-    time: Res<Time>,
-    layout: Res<TerminalLayout<T>>,
-    mut effects: Query<(&mut Slash, &EffectRegion), With<TargetTerminal<T>>>,
-    mut cells: Query<(&GridPosition, &mut Transform), With<TerminalCell<T>>>,
-) {
-    for (mut slash, region) in effects.iter_mut() {
-        if !slash.active {
-            continue;
-        }
-
-        slash.elapsed += time.delta_secs();
+/// An extra cut of a `Slash`, on top of the one at `Slash::angle`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Blade {
+    /// Angle of the cut in radians, like `Slash::angle`.
+    pub angle: f32,
+    /// Seconds after the slash starts that this blade begins its pass.
+    pub delay: f32,
+}
 
-        if slash.elapsed > slash.duration {
-            slash.active = false;
-            continue;
+impl Slash {
+    /// An X-shaped slash: the default diagonal cut, crossed by a second one
+    /// that starts `delay` seconds later.
+    pub fn crisscross(delay: f32) -> Self {
+        Self {
+            blades: vec![Blade {
+                angle: -std::f32::consts::FRAC_PI_4,
+                delay,
+            }],
+            ..default()
         }
+    }
 
-        let progress = slash.elapsed / slash.duration; // 0.0 -> 1.0
+    /// Seconds from the start until the last blade has finished its pass.
+    pub fn total_duration(&self) -> f32 {
+        let last_delay = self
+            .blades
+            .iter()
+            .map(|blade| blade.delay)
+            .fold(0.0, f32::max);
+        self.duration + last_delay
+    }
+}
 
+/// One blade's pass, projected onto the grid for the current frame.
+struct Cut {
+    /// Unit direction along the cut line.
+    along: Vec2,
+    /// Unit direction perpendicular to the cut (displacement direction).
+    perp: Vec2,
+    center_along: f32,
+    center_perp: f32,
+    half_along: f32,
+    /// Position of the blade's wavefront along the line.
+    blade_pos: f32,
+    close_phase: f32,
+}
+
+impl Cut {
+    fn new(angle: f32, progress: f32, region: &EffectRegion) -> Self {
         // Phase 1 (0.0–0.5): blade travels along the line, opening the cut
         // Phase 2 (0.5–1.0): cut eases closed
         let cut_phase = progress.min(0.5) / 0.5; // 0→1 during phase 1, stays 1 in phase 2
@@ -71,94 +112,128 @@ pub fn slash_system<T: 'static + Send + Sync>(
             1.0
         };
 
-        // Direction along the cut line
-        let along_x = slash.angle.cos();
-        let along_y = slash.angle.sin();
-
-        // Perpendicular to the cut (displacement direction)
-        let perp_x = -along_y;
-        let perp_y = along_x;
+        let along = Vec2::new(angle.cos(), angle.sin());
+        let perp = Vec2::new(-along.y, along.x);
 
         // Find region center and half-extent along the cut direction
-        let (center_col, center_row, half_along, _half_perp) =
-            if let Some(rect) = region.include.first() {
-                let cx = rect.col as f32 + rect.width as f32 / 2.0;
-                let cy = rect.row as f32 + rect.height as f32 / 2.0;
-                let ex = rect.width as f32 / 2.0;
-                let ey = rect.height as f32 / 2.0;
-                // Extent of the region projected onto each axis
-                let along_ext = (ex * along_x).abs() + (ey * along_y).abs();
-                let perp_ext = (ex * perp_x).abs() + (ey * perp_y).abs();
-                (cx, cy, along_ext, perp_ext)
-            } else {
-                (75.0, 25.0, 50.0, 25.0)
-            };
-
-        // Project center onto both axes
-        let center_along = center_col * along_x + center_row * along_y;
-        let center_perp = center_col * perp_x + center_row * perp_y;
+        let (center, half_along) = if let Some(rect) = region.include.first() {
+            let extent = Vec2::new(rect.width as f32, rect.height as f32) / 2.0;
+            let center = Vec2::new(rect.col as f32, rect.row as f32) + extent;
+            // Extent of the region projected onto the cut
+            (center, (extent * along).abs().element_sum())
+        } else {
+            (Vec2::new(75.0, 25.0), 50.0)
+        };
+        let center_along = center.dot(along);
 
         // The blade wavefront: starts at one end, travels to the other
-        let blade_start = center_along - half_along;
-        let blade_end = center_along + half_along;
-        let blade_pos = blade_start + cut_phase * (blade_end - blade_start);
+        let blade_pos = center_along - half_along + cut_phase * 2.0 * half_along;
 
-        let half_width = slash.width / 2.0;
+        Self {
+            along,
+            perp,
+            center_along,
+            center_perp: center.dot(perp),
+            half_along,
+            blade_pos,
+            close_phase,
+        }
+    }
 
-        for (pos, mut transform) in cells.iter_mut() {
-            if !region.contains(pos.col, pos.row) {
-                continue;
-            }
+    /// Displacement strength for the cell at `cell` (grid coords) and the
+    /// side of the cut it's on, or None if the blade leaves it alone.
+    fn strength(&self, cell: Vec2, half_width: f32) -> Option<(f32, f32)> {
+        // Project cell onto the along-line and perpendicular axes
+        let cell_along = cell.dot(self.along);
+        let cell_perp = cell.dot(self.perp);
+
+        // Perpendicular distance from the cut line
+        let perp_dist = (cell_perp - self.center_perp).abs();
+        // Skip cells outside the band or that the blade hasn't reached yet
+        if perp_dist >= half_width || cell_along > self.blade_pos {
+            return None;
+        }
 
-            let col = pos.col as f32;
-            let row = pos.row as f32;
+        // Perpendicular falloff: 1.0 at the line, 0.0 at band edges
+        let perp_t = 1.0 - perp_dist / half_width;
+        let perp_strength = perp_t * perp_t * (3.0 - 2.0 * perp_t); // smoothstep
 
-            // Project cell onto the along-line and perpendicular axes
-            let cell_along = col * along_x + row * along_y;
-            let cell_perp = col * perp_x + row * perp_y;
+        // Along-line falloff: strongest at center, fades to edges
+        let along_dist = (cell_along - self.center_along).abs();
+        let along_strength = (1.0 - along_dist / self.half_along.max(1.0)).max(0.0);
 
-            // Perpendicular distance from the cut line
-            let perp_dist = (cell_perp - center_perp).abs();
+        // Wavefront softness: cells very close to the blade tip get partial displacement
+        let tip_blend = ((self.blade_pos - cell_along) / 2.0).min(1.0); // ramp over 2 cells
 
-            if perp_dist >= half_width {
-                continue;
-            }
+        let strength = perp_strength * along_strength * tip_blend * self.close_phase;
+        let side = if cell_perp >= self.center_perp {
+            1.0
+        } else {
+            -1.0
+        };
+        Some((strength, side))
+    }
+}
 
-            // Has the blade passed this cell?
-            if cell_along > blade_pos {
-                continue;
-            }
+/// System that applies the slash effect to cell transforms.
+pub fn slash_system<T: 'static + Send + Sync>(
+    clock: Res<EffectClock<T>>,
+    layout: Res<TerminalLayout<T>>,
+    mut effects: Query<(&mut Slash, &EffectRegion, Option<&CellMask>), RunningEffect<T>>,
+    mut cells: Query<(&GridPosition, &CellStyle, &mut Transform), With<TerminalCell<T>>>,
+) {
+    let mut cuts = Vec::new();
 
-            // Perpendicular falloff: 1.0 at the line, 0.0 at band edges
-            let perp_t = 1.0 - perp_dist / half_width;
-            let perp_strength = perp_t * perp_t * (3.0 - 2.0 * perp_t); // smoothstep
+    for (mut slash, region, mask) in effects.iter_mut() {
+        if !slash.active {
+            continue;
+        }
+
+        slash.elapsed += clock.delta_secs();
+
+        if slash.elapsed > slash.total_duration() {
+            slash.active = false;
+            continue;
+        }
 
-            // Along-line falloff: strongest at center, fades to edges
-            let along_dist = (cell_along - center_along).abs();
-            let along_t = (1.0 - along_dist / half_along.max(1.0)).max(0.0);
-            let along_strength = along_t; // linear falloff along the cut
+        // Blades that are mid-pass this frame
+        cuts.clear();
+        let blades = std::iter::once((slash.angle, 0.0))
+            .chain(slash.blades.iter().map(|blade| (blade.angle, blade.delay)));
+        for (angle, delay) in blades {
+            let t = slash.elapsed - delay;
+            if (0.0..=slash.duration).contains(&t) {
+                cuts.push(Cut::new(angle, t / slash.duration, region));
+            }
+        }
 
-            // Wavefront softness: cells very close to the blade tip get partial displacement
-            let tip_dist = blade_pos - cell_along;
-            let tip_blend = (tip_dist / 2.0).min(1.0); // ramp over 2 cells
+        let half_width = slash.width / 2.0;
+        let cell_size = Vec2::new(layout.cell_width, layout.cell_height);
 
-            let strength = perp_strength * along_strength * tip_blend * close_phase;
+        for (pos, style, mut transform) in cells.iter_mut() {
+            if !cell_in_effect(region, mask, pos.col, pos.row, style) {
+                continue;
+            }
+            let cell = Vec2::new(pos.col as f32, pos.row as f32);
 
-            // Which side of the cut line is this cell on?
-            let side = if cell_perp >= center_perp { 1.0 } else { -1.0 };
+            for cut in &cuts {
+                let Some((strength, side)) = cut.strength(cell, half_width) else {
+                    continue;
+                };
 
-            // Displace perpendicular to the cut (cells split apart)
-            let disp = slash.amplitude * strength * side;
-            transform.translation.x += perp_x * disp * layout.cell_width;
-            transform.translation.y += perp_y * disp * -layout.cell_height;
+                // Displace perpendicular to the cut (cells split apart)
+                let disp = slash.amplitude * strength * side;
+                transform.translation.x += cut.perp.x * disp * layout.cell_width;
+                transform.translation.y += cut.perp.y * disp * -layout.cell_height;
 
-            // Slight rotation following the cut
-            let rotation = 0.08 * strength * side;
-            transform.rotation *= Quat::from_rotation_z(rotation);
+                // Slight rotation following the cut
+                let rotation = Quat::from_rotation_z(0.08 * strength * side);
+                rotate_about(&mut transform, rotation, slash.pivot, cell_size);
 
-            // Subtle scale bump near the cut line
-            let scale = 1.0 + 0.1 * strength;
-            transform.scale *= Vec3::splat(scale);
+                // Subtle scale bump near the cut line
+                let scale = 1.0 + 0.1 * strength;
+                transform.scale *= Vec3::splat(scale);
+            }
         }
     }
 }
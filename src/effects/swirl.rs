@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+
+use super::{cell_in_effect, CellMask, EffectClock, EffectRegion, RunningEffect};
+use crate::grid::{CellStyle, GridPosition, TerminalCell};
+use crate::TerminalLayout;
+
+/// Whirlpool effect that rotates cells around an origin, twisting most at
+/// the center and fading out to nothing at `radius`.
+#[derive(Component, Clone, Debug)]
+pub struct Swirl {
+    /// Origin column (grid coords).
+    pub origin_col: f32,
+    /// Origin row (grid coords).
+    pub origin_row: f32,
+    /// Peak rotation at the origin, in radians.
+    pub strength: f32,
+    /// Distance in cells at which the twist reaches zero.
+    pub radius: f32,
+    /// How fast the twist winds and unwinds, in radians per second
+    /// (0 = hold a static twist at full `strength`).
+    pub speed: f32,
+    /// Also turn each glyph by its cell's twist angle.
+    pub spin_glyphs: bool,
+    pub active: bool,
+}
+
+impl Default for Swirl {
+    fn default() -> Self {
+        Self {
+            origin_col: 40.0,
+            origin_row: 12.0,
+            strength: std::f32::consts::PI,
+            radius: 12.0,
+            speed: 1.5,
+            spin_glyphs: true,
+            active: true,
+        }
+    }
+}
+
+impl Swirl {
+    /// Twist angle for a cell `distance` cells from the origin at time `t`.
+    pub fn angle(&self, distance: f32, t: f32) -> f32 {
+        if self.radius <= 0.0 || distance >= self.radius {
+            return 0.0;
+        }
+        let falloff = 1.0 - distance / self.radius;
+        let wind = if self.speed == 0.0 {
+            1.0
+        } else {
+            (self.speed * t).sin()
+        };
+        self.strength * falloff * falloff * wind
+    }
+}
+
+pub fn swirl_system<T: 'static + Send + Sync>(
+    clock: Res<EffectClock<T>>,
+    layout: Res<TerminalLayout<T>>,
+    effects: Query<(&Swirl, &EffectRegion, Option<&CellMask>), RunningEffect<T>>,
+    mut cells: Query<(&GridPosition, &CellStyle, &mut Transform), With<TerminalCell<T>>>,
+) {
+    let t = clock.elapsed_secs();
+
+    for (swirl, region, mask) in effects.iter() {
+        if !swirl.active {
+            continue;
+        }
+
+        for (pos, style, mut transform) in cells.iter_mut() {
+            if !cell_in_effect(region, mask, pos.col, pos.row, style) {
+                continue;
+            }
+
+            let dc = pos.col as f32 - swirl.origin_col;
+            let dr = pos.row as f32 - swirl.origin_row;
+            let angle = swirl.angle((dc * dc + dr * dr).sqrt(), t);
+            if angle == 0.0 {
+                continue;
+            }
+
+            // Rotate the cell's pixel offset from the origin
+            let offset = Vec2::new(dc * layout.cell_width, -dr * layout.cell_height);
+            let rotated = Vec2::from_angle(angle).rotate(offset);
+            transform.translation.x += rotated.x - offset.x;
+            transform.translation.y += rotated.y - offset.y;
+
+            if swirl.spin_glyphs {
+                transform.rotation *= Quat::from_rotation_z(angle);
+            }
+        }
+    }
+}
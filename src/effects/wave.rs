@@ -1,9 +1,28 @@
 use bevy::prelude::*;
 
-use super::{EffectRegion, TargetTerminal};
-use crate::grid::{GridPosition, TerminalCell};
+use super::{
+    cell_in_effect, envelope_gain, CellMask, EffectClock, EffectEnvelope, EffectRegion,
+    RunningEffect,
+};
+use crate::grid::{CellStyle, GridPosition, TerminalCell};
 
-/// A simple sine wave effect that oscillates cells vertically.
+/// Screen axis a `Wave` pushes cells along.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WaveAxis {
+    X,
+    #[default]
+    Y,
+}
+
+/// A simple sine wave effect that oscillates cells.
+///
+/// `horizontal` picks which grid position the phase is sampled from and
+/// `displace_axis` which way cells move:
+///
+/// - `horizontal: true`, `Y` (default): ripples travel across columns, cells bob up and down.
+/// - `horizontal: true`, `X`: each column surges sideways in turn, like a compression wave.
+/// - `horizontal: false`, `X`: rows sway sideways, like a flag waving.
+/// - `horizontal: false`, `Y`: rows bob up and down in turn.
 #[derive(Component, Clone, Debug)]
 pub struct Wave {
     /// Maximum displacement in pixels.
@@ -14,6 +33,8 @@ pub struct Wave {
     pub speed: f32,
     /// Axis of wave propagation: if true, wave travels along rows; if false, along columns.
     pub horizontal: bool,
+    /// Direction cells are displaced in.
+    pub displace_axis: WaveAxis,
 }
 
 impl Default for Wave {
@@ -23,23 +44,33 @@ impl Default for Wave {
             wavelength: 8.0,
             speed: 4.0,
             horizontal: true,
+            displace_axis: WaveAxis::Y,
         }
     }
 }
 
 /// System that applies the wave effect to cell transforms.
 pub fn wave_system<T: 'static + Send + Sync>(
-    time: Res<Time>,
-    effects: Query<(&Wave, &EffectRegion), With<TargetTerminal<T>>>,
-    mut cells: Query<(&GridPosition, &mut Transform), With<TerminalCell<T>>>,
+    clock: Res<EffectClock<T>>,
+    effects: Query<
+        (
+            &Wave,
+            &EffectRegion,
+            Option<&CellMask>,
+            Option<&EffectEnvelope>,
+        ),
+        RunningEffect<T>,
+    >,
+    mut cells: Query<(&GridPosition, &CellStyle, &mut Transform), With<TerminalCell<T>>>,
 ) {
-    let t = time.elapsed_secs();
+    let t = clock.elapsed_secs();
 
-    for (wave, region) in effects.iter() {
+    for (wave, region, mask, envelope) in effects.iter() {
         let two_pi = std::f32::consts::TAU;
+        let amplitude = wave.amplitude * envelope_gain(envelope);
 
-        for (pos, mut transform) in cells.iter_mut() {
-            if !region.contains(pos.col, pos.row) {
+        for (pos, style, mut transform) in cells.iter_mut() {
+            if !cell_in_effect(region, mask, pos.col, pos.row, style) {
                 continue;
             }
 
@@ -50,9 +81,12 @@ pub fn wave_system<T: 'static + Send + Sync>(
             };
 
             let displacement =
-                wave.amplitude * (two_pi * (position_along / wave.wavelength - wave.speed * t)).sin();
+                amplitude * (two_pi * (position_along / wave.wavelength - wave.speed * t)).sin();
 
-            transform.translation.y += displacement;
+            match wave.displace_axis {
+                WaveAxis::X => transform.translation.x += displacement,
+                WaveAxis::Y => transform.translation.y += displacement,
+            }
         }
     }
 }
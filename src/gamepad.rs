@@ -0,0 +1,156 @@
+use bevy::input::gamepad::{Gamepad, GamepadButton};
+use bevy::prelude::*;
+
+use crate::input::{FocusedTerminal, RepeatMode, TerminalInputQueue};
+use crate::TerminalConfig;
+
+/// Which gamepad inputs become which terminal keys, shared by all terminals.
+///
+/// The default maps the D-pad and left stick to the arrow keys, South to
+/// Enter, East to Esc, West to Backspace, North to Space and Start to Tab,
+/// so arrow-key driven apps work on a controller unchanged.
+#[derive(Resource, Clone, Debug)]
+pub struct GamepadMap {
+    pub buttons: Vec<(GamepadButton, terminput::KeyCode)>,
+    /// Map the left stick to the arrow keys.
+    pub left_stick: bool,
+    /// Stick deflection (0.0..1.0) below which the stick counts as centered.
+    pub deadzone: f32,
+    /// Seconds a stick direction is held before it starts repeating.
+    pub repeat_delay: f32,
+    /// Seconds between repeats while the stick stays pushed.
+    pub repeat_interval: f32,
+}
+
+impl Default for GamepadMap {
+    fn default() -> Self {
+        use terminput::KeyCode as K;
+        Self {
+            buttons: vec![
+                (GamepadButton::DPadUp, K::Up),
+                (GamepadButton::DPadDown, K::Down),
+                (GamepadButton::DPadLeft, K::Left),
+                (GamepadButton::DPadRight, K::Right),
+                (GamepadButton::South, K::Enter),
+                (GamepadButton::East, K::Esc),
+                (GamepadButton::West, K::Backspace),
+                (GamepadButton::North, K::Char(' ')),
+                (GamepadButton::Start, K::Tab),
+            ],
+            left_stick: true,
+            deadzone: 0.5,
+            repeat_delay: 0.4,
+            repeat_interval: 0.1,
+        }
+    }
+}
+
+/// Arrow key for a stick position, along its dominant axis, or `None`
+/// inside the deadzone.
+fn stick_direction(stick: Vec2, deadzone: f32) -> Option<terminput::KeyCode> {
+    if stick.length() < deadzone {
+        return None;
+    }
+    Some(if stick.x.abs() > stick.y.abs() {
+        if stick.x > 0.0 {
+            terminput::KeyCode::Right
+        } else {
+            terminput::KeyCode::Left
+        }
+    } else if stick.y > 0.0 {
+        terminput::KeyCode::Up
+    } else {
+        terminput::KeyCode::Down
+    })
+}
+
+/// Held stick direction and seconds until it next repeats.
+#[derive(Default)]
+pub struct StickRepeat {
+    direction: Option<terminput::KeyCode>,
+    countdown: f32,
+}
+
+fn key(code: terminput::KeyCode, kind: terminput::KeyEventKind) -> terminput::Event {
+    terminput::Event::Key(terminput::KeyEvent::new(code).kind(kind))
+}
+
+/// System that forwards gamepad buttons and left-stick directions to the
+/// terminal input queue according to `GamepadMap`.
+///
+/// Stick repeats follow `TerminalConfig::repeat_mode`; `RepeatMode::FirstOnly`
+/// behaves like `Forward` since the stick repeats at most once per frame.
+pub fn forward_gamepad_input<T: 'static + Send + Sync>(
+    time: Res<Time>,
+    map: Res<GamepadMap>,
+    gamepads: Query<&Gamepad>,
+    focused: Res<FocusedTerminal>,
+    config: Res<TerminalConfig<T>>,
+    mut queue: ResMut<TerminalInputQueue<T>>,
+    mut stick: Local<StickRepeat>,
+) {
+    if !focused.accepts::<T>() {
+        stick.direction = None;
+        return;
+    }
+
+    let mut direction = None;
+    for gamepad in gamepads.iter() {
+        for &(button, code) in &map.buttons {
+            if gamepad.just_pressed(button) {
+                queue.push(key(code, terminput::KeyEventKind::Press), &config);
+            }
+            if config.forward_releases && gamepad.just_released(button) {
+                queue.push(key(code, terminput::KeyEventKind::Release), &config);
+            }
+        }
+        if map.left_stick && direction.is_none() {
+            direction = stick_direction(gamepad.left_stick(), map.deadzone);
+        }
+    }
+
+    if direction != stick.direction {
+        if let Some(old) = stick.direction {
+            if config.forward_releases {
+                queue.push(key(old, terminput::KeyEventKind::Release), &config);
+            }
+        }
+        if let Some(new) = direction {
+            queue.push(key(new, terminput::KeyEventKind::Press), &config);
+        }
+        stick.direction = direction;
+        stick.countdown = map.repeat_delay;
+        return;
+    }
+
+    let Some(held) = direction else {
+        return;
+    };
+    stick.countdown -= time.delta_secs();
+    if stick.countdown <= 0.0 {
+        stick.countdown = map.repeat_interval.max(0.0);
+        if config.repeat_mode != RepeatMode::Ignore {
+            queue.push(key(held, terminput::KeyEventKind::Repeat), &config);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stick_direction_deadzone() {
+        assert_eq!(stick_direction(Vec2::new(0.3, 0.3), 0.5), None);
+        assert_eq!(stick_direction(Vec2::new(0.0, 0.6), 0.5), Some(terminput::KeyCode::Up));
+        assert_eq!(stick_direction(Vec2::new(0.0, -0.6), 0.5), Some(terminput::KeyCode::Down));
+    }
+
+    #[test]
+    fn test_stick_direction_dominant_axis() {
+        let dir = |x, y| stick_direction(Vec2::new(x, y), 0.2);
+        assert_eq!(dir(0.9, 0.4), Some(terminput::KeyCode::Right));
+        assert_eq!(dir(-0.9, -0.4), Some(terminput::KeyCode::Left));
+        assert_eq!(dir(0.4, -0.9), Some(terminput::KeyCode::Down));
+    }
+}
@@ -0,0 +1,193 @@
+use ab_glyph::{Font as _, FontRef, ScaleFont};
+
+use crate::FontSource;
+
+/// Texture filtering used when sampling the glyph atlas.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AtlasFilter {
+    /// Smooth, anti-aliased glyphs (default).
+    #[default]
+    Linear,
+    /// Crisp texels, for pixel fonts and integer-scaled cells.
+    Nearest,
+}
+
+impl AtlasFilter {
+    #[cfg(feature = "render")]
+    pub(crate) fn sampler(self) -> bevy::image::ImageSampler {
+        match self {
+            Self::Linear => bevy::image::ImageSampler::linear(),
+            Self::Nearest => bevy::image::ImageSampler::nearest(),
+        }
+    }
+}
+
+/// Where glyphs sit vertically in their cell, before `glyph_offset`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GlyphAlign {
+    /// As the font places them, with the baseline at the font's ascent
+    /// (default).
+    #[default]
+    Baseline,
+    /// Shifted so capital letters are centered in the cell, measured from the
+    /// font's `H`. Fixes fonts whose text sits visibly high or low. Bitmap
+    /// fonts are left as drawn.
+    CapCenter,
+}
+
+/// How glyphs of a proportional (non-monospace) font are fit into the
+/// uniform terminal cells.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MonospacePolicy {
+    /// Draw every glyph at its natural width, left-aligned in the cell
+    /// (default). Narrow glyphs leave a gap and wide ones are clipped.
+    #[default]
+    ForceCellWidth,
+    /// Stretch or squeeze each glyph horizontally so its advance fills the
+    /// cell exactly. Columns stay aligned, at the cost of distorted shapes.
+    FitGlyph,
+}
+
+/// Empty space left between atlas tiles. Glyph overflow past the cell lands
+/// in it instead of a neighbor's tile; anything beyond it is clipped.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum AtlasPadding {
+    /// Half the cell width, between 4 and 16 texels (default).
+    #[default]
+    Auto,
+    /// A fixed number of texels.
+    Pixels(u32),
+    /// A fraction of the cell width.
+    Fraction(f32),
+}
+
+/// Largest padding `AtlasPadding::Auto` picks.
+const MAX_AUTO_PADDING: u32 = 16;
+
+impl AtlasPadding {
+    /// Padding in texels for tiles `cell_w` texels wide. Always at least one
+    /// texel so linear filtering never samples the neighboring tile, and at
+    /// most one cell width.
+    pub fn texels(self, cell_w: u32) -> u32 {
+        let pad = match self {
+            Self::Auto => (cell_w / 2).clamp(4, MAX_AUTO_PADDING),
+            Self::Pixels(px) => px,
+            Self::Fraction(f) => (cell_w as f32 * f.max(0.0)).round() as u32,
+        };
+        pad.clamp(1, cell_w.max(1))
+    }
+}
+
+/// Typographic punctuation common in European text but outside Latin-1.
+const COMMON_PUNCTUATION: &[char] = &[
+    '‘', '’', '‚', '“', '”', '„', '–', '—', '•', '…', '‹', '›', '†', '‡', '‰', '€', '™',
+];
+
+/// Characters rasterized into the atlas at startup on top of printable
+/// ASCII, which is always included. Anything else is added the first time
+/// it's drawn, at the cost of an atlas rebuild.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum CharSet {
+    /// Printable ASCII only (default).
+    #[default]
+    Ascii,
+    /// Latin-1 (U+00A0..=U+00FF) plus common punctuation: curly quotes,
+    /// dashes, bullet, ellipsis and the euro sign. Covers most Western
+    /// European languages.
+    Latin1,
+    /// These characters.
+    Custom(Vec<char>),
+}
+
+impl CharSet {
+    /// The characters this set adds to printable ASCII.
+    pub fn extra_chars(&self) -> Vec<char> {
+        match self {
+            Self::Ascii => Vec::new(),
+            Self::Latin1 => ('\u{A0}'..='\u{FF}')
+                .chain(COMMON_PUNCTUATION.iter().copied())
+                .collect(),
+            Self::Custom(chars) => chars.clone(),
+        }
+    }
+}
+
+/// Return the printable ASCII characters (0x20..=0x7E).
+pub(crate) fn ascii_chars() -> Vec<char> {
+    (0x20u8..=0x7E).map(|b| b as char).collect()
+}
+
+/// Compute the cell (width, height) in pixels for a given font and size.
+///
+/// Uses exact font metrics (no rounding) so adjacent cells tile seamlessly.
+/// Height excludes line_gap so vertical borders connect without gaps.
+pub fn compute_cell_size(font_bytes: &[u8], font_size: f32) -> (f32, f32) {
+    let font = FontRef::try_from_slice(font_bytes)
+        .or_else(|_| FontRef::try_from_slice(FontSource::Default.bytes()))
+        .expect("Failed to parse default font");
+    let scale = ab_glyph::PxScale::from(font_size);
+    let scaled_font = font.as_scaled(scale);
+    let glyph_id = font.glyph_id('M');
+    let cell_width = scaled_font.h_advance(glyph_id);
+    let cell_height = scaled_font.ascent() - scaled_font.descent();
+    (cell_width, cell_height)
+}
+
+/// Shift that centers capital letters in the cell, as a fraction of the cell
+/// height (+ up). Zero if the font has no `H` outline.
+pub fn cap_center_offset(font_bytes: &[u8]) -> f32 {
+    let Ok(font) = FontRef::try_from_slice(font_bytes) else {
+        return 0.0;
+    };
+    let Some(outline) = font.outline(font.glyph_id('H')) else {
+        return 0.0;
+    };
+    let ascent = font.ascent_unscaled();
+    let height = ascent - font.descent_unscaled();
+    // Unscaled bounds are y-up from the baseline; measure down from the cell top
+    let cap_middle = ascent - (outline.bounds.min.y + outline.bounds.max.y) / 2.0;
+    (cap_middle - height / 2.0) / height
+}
+
+/// Whether every printable ASCII glyph has the same advance as `M`.
+pub fn is_monospace(font_bytes: &[u8]) -> bool {
+    let Ok(font) = FontRef::try_from_slice(font_bytes) else {
+        return true;
+    };
+    let reference = font.h_advance_unscaled(font.glyph_id('M'));
+    ascii_chars().into_iter().all(|ch| {
+        let id = font.glyph_id(ch);
+        // Missing glyphs fall back to .notdef, which doesn't count
+        id.0 == 0 || (font.h_advance_unscaled(id) - reference).abs() <= reference * 0.01
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_atlas_padding_is_clamped() {
+        assert_eq!(AtlasPadding::Auto.texels(10), 5);
+        assert_eq!(AtlasPadding::Auto.texels(4), 4);
+        assert_eq!(AtlasPadding::Auto.texels(100), MAX_AUTO_PADDING);
+        assert_eq!(AtlasPadding::Pixels(0).texels(10), 1);
+        assert_eq!(AtlasPadding::Pixels(50).texels(10), 10);
+        assert_eq!(AtlasPadding::Fraction(0.25).texels(40), 10);
+    }
+
+    #[test]
+    fn test_latin1_charset_adds_accents_and_punctuation() {
+        let extra = CharSet::Latin1.extra_chars();
+        for ch in ['é', 'ß', '£', '“', '—', '•'] {
+            assert!(extra.contains(&ch), "{ch} missing");
+        }
+        assert!(!extra.iter().any(char::is_ascii));
+        assert!(CharSet::Ascii.extra_chars().is_empty());
+    }
+
+    #[test]
+    fn test_default_font_is_monospace() {
+        assert!(is_monospace(FontSource::Default.bytes()));
+    }
+}
@@ -1,10 +1,19 @@
+use std::collections::HashSet;
 use std::marker::PhantomData;
 
+use bevy::camera::visibility::RenderLayers;
 use bevy::color::Color;
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
+use ratatui::buffer::Cell;
+use ratatui::style::{Modifier, Style};
 
-use crate::atlas::FontAtlasResource;
-use crate::{TerminalConfig, TerminalLayout};
+use crate::atlas::{align_layout_to_atlas, FontAtlasResource};
+use crate::color::bevy_to_ratatui;
+use crate::glyph::GlyphAlign;
+use crate::{TerminalConfig, TerminalLayout, TerminalResource};
+
+pub use crate::GridPosition;
 
 /// Marker component for terminal cell entities, scoped by terminal instance.
 #[derive(Component)]
@@ -16,13 +25,6 @@ impl<T: 'static + Send + Sync> Default for TerminalCell<T> {
     }
 }
 
-/// Logical grid position of a cell.
-#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
-pub struct GridPosition {
-    pub col: u16,
-    pub row: u16,
-}
-
 /// Style information for a cell, mirroring ratatui cell data.
 #[derive(Component, Clone, Debug)]
 pub struct CellStyle {
@@ -49,6 +51,30 @@ impl Default for CellStyle {
     }
 }
 
+impl CellStyle {
+    /// Rebuild a ratatui `Cell` from this style, e.g. to diff, serialize or
+    /// re-render the synced grid elsewhere.
+    ///
+    /// Lossy: colors go through `color::bevy_to_ratatui`, so `Reset` and
+    /// indexed colors come back as the concrete colors they were drawn
+    /// with, and reverse video as already-swapped colors.
+    pub fn to_ratatui_cell(&self) -> Cell {
+        let mut modifier = Modifier::empty();
+        modifier.set(Modifier::BOLD, self.bold);
+        modifier.set(Modifier::ITALIC, self.italic);
+        modifier.set(Modifier::UNDERLINED, self.underlined);
+        modifier.set(Modifier::DIM, self.dim);
+        let style = Style::default()
+            .fg(bevy_to_ratatui(self.fg))
+            .bg(bevy_to_ratatui(self.bg))
+            .add_modifier(modifier);
+
+        let mut cell = Cell::default();
+        cell.set_symbol(&self.symbol).set_style(style);
+        cell
+    }
+}
+
 /// Stores the "home" transform for a cell. Effects offset from this.
 #[derive(Component, Clone, Copy, Debug)]
 pub struct BaseTransform {
@@ -57,6 +83,32 @@ pub struct BaseTransform {
     pub scale: Vec3,
 }
 
+/// Foreground opacity multiplier for a cell, reset to 1.0 each frame.
+///
+/// Effects that dim or brighten glyphs should multiply this rather than
+/// touch `Sprite::color` alpha; it is applied once, after all color effects,
+/// so several alpha effects combine predictably.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct CellOpacity(pub f32);
+
+impl Default for CellOpacity {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// Forces a cell's colors from game code regardless of what ratatui drew,
+/// e.g. to highlight the player's position. Insert it on a cell entity
+/// (see `CellEntityIndex::get`); removing it restores the buffer's colors
+/// on the next frame. Effects still apply on top.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq)]
+pub struct ColorOverride {
+    /// Glyph color (None = keep the buffer's).
+    pub fg: Option<Color>,
+    /// Background color (None = keep the buffer's).
+    pub bg: Option<Color>,
+}
+
 /// Marker for the background sprite child entity, scoped by terminal instance.
 #[derive(Component)]
 pub struct BackgroundSprite<T: 'static + Send + Sync>(PhantomData<T>);
@@ -77,6 +129,17 @@ impl<T: 'static + Send + Sync> Default for ForegroundSprite<T> {
     }
 }
 
+/// The panel behind the whole grid drawn for
+/// `TerminalConfig::frame_background`, one per terminal instance.
+#[derive(Component)]
+pub struct FrameBackground<T: 'static + Send + Sync>(PhantomData<T>);
+
+impl<T: 'static + Send + Sync> Default for FrameBackground<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
 /// O(1) lookup of cell entities by grid position, scoped by terminal instance.
 #[derive(Resource)]
 pub struct CellEntityIndex<T: 'static + Send + Sync> {
@@ -88,6 +151,22 @@ pub struct CellEntityIndex<T: 'static + Send + Sync> {
 }
 
 impl<T: 'static + Send + Sync> CellEntityIndex<T> {
+    /// Build an index from row-major parent and foreground entity lists.
+    pub(crate) fn new(
+        columns: u16,
+        rows: u16,
+        entities: Vec<Entity>,
+        fg_entities: Vec<Entity>,
+    ) -> Self {
+        Self {
+            entities,
+            fg_entities,
+            columns,
+            rows,
+            _marker: PhantomData,
+        }
+    }
+
     /// Get the parent entity at (col, row).
     pub fn get(&self, col: u16, row: u16) -> Option<Entity> {
         if col < self.columns && row < self.rows {
@@ -105,6 +184,81 @@ impl<T: 'static + Send + Sync> CellEntityIndex<T> {
             None
         }
     }
+
+    /// Grid position of the cell at `index` in `entities`.
+    pub fn position(&self, index: usize) -> GridPosition {
+        let columns = self.columns.max(1) as usize;
+        GridPosition::new((index % columns) as u16, (index / columns) as u16)
+    }
+
+    /// Parent entities of the cells sharing an edge with `pos`.
+    pub fn neighbors4(&self, pos: GridPosition) -> impl Iterator<Item = Entity> + '_ {
+        pos.neighbors4(self.columns, self.rows)
+            .filter_map(|p| self.get(p.col, p.row))
+    }
+
+    /// Parent entities of the cells touching `pos`, corners included.
+    pub fn neighbors8(&self, pos: GridPosition) -> impl Iterator<Item = Entity> + '_ {
+        pos.neighbors8(self.columns, self.rows)
+            .filter_map(|p| self.get(p.col, p.row))
+    }
+
+    /// Parent entities of row `row`, left to right. Empty past the last row.
+    pub fn row(&self, row: u16) -> impl Iterator<Item = Entity> + '_ {
+        let columns = self.columns as usize;
+        let start = row as usize * columns;
+        let cells = if row < self.rows {
+            &self.entities[start..start + columns]
+        } else {
+            &[]
+        };
+        cells.iter().copied()
+    }
+
+    /// Parent entities of column `col`, top to bottom. Empty past the last
+    /// column.
+    pub fn column(&self, col: u16) -> impl Iterator<Item = Entity> + '_ {
+        let rows = if col < self.columns { self.rows } else { 0 };
+        self.entities
+            .iter()
+            .skip(col as usize)
+            .step_by(self.columns.max(1) as usize)
+            .take(rows as usize)
+            .copied()
+    }
+}
+
+/// Read-only view of a terminal's cells, joining `CellEntityIndex<T>` with
+/// each cell's `GridPosition` and `CellStyle`.
+///
+/// Holds a shared borrow of `CellStyle`, so it can't be used alongside a
+/// `&mut CellStyle` query in the same system.
+#[derive(SystemParam)]
+pub struct TerminalView<'w, 's, T: 'static + Send + Sync> {
+    index: Res<'w, CellEntityIndex<T>>,
+    cells: Query<'w, 's, (&'static GridPosition, &'static CellStyle), With<TerminalCell<T>>>,
+}
+
+impl<'w, 's, T: 'static + Send + Sync> TerminalView<'w, 's, T> {
+    /// Grid size as (columns, rows).
+    pub fn size(&self) -> (u16, u16) {
+        (self.index.columns, self.index.rows)
+    }
+
+    /// Style and entity of the cell at (col, row).
+    pub fn get(&self, col: u16, row: u16) -> Option<(&CellStyle, Entity)> {
+        let entity = self.index.get(col, row)?;
+        let (_, style) = self.cells.get(entity).ok()?;
+        Some((style, entity))
+    }
+
+    /// Every cell in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (GridPosition, &CellStyle, Entity)> + '_ {
+        self.index.entities.iter().filter_map(|&entity| {
+            let (pos, style) = self.cells.get(entity).ok()?;
+            Some((*pos, style, entity))
+        })
+    }
 }
 
 /// Startup system that spawns the grid of cell entities.
@@ -114,6 +268,221 @@ pub fn spawn_grid<T: 'static + Send + Sync>(
     layout: Res<TerminalLayout<T>>,
     atlas: Res<FontAtlasResource<T>>,
 ) {
+    let index = spawn_cells(&mut commands, &config, &layout, &atlas);
+    commands.insert_resource(index);
+
+    let (color, size, translation) = frame_placement(&config, &layout).unwrap_or_default();
+    let visibility = if config.frame_background.is_some() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    let mut frame = commands.spawn((
+        FrameBackground::<T>::default(),
+        Sprite::from_color(color, size),
+        Transform::from_translation(translation),
+        visibility,
+    ));
+    if let Some(layer) = config.render_layer {
+        frame.insert(RenderLayers::layer(layer as usize));
+    }
+}
+
+/// Color, size and translation of the frame panel, if the config wants one:
+/// the grid plus `margin` pixels on each side, just behind the cells.
+fn frame_placement<T: 'static + Send + Sync>(
+    config: &TerminalConfig<T>,
+    layout: &TerminalLayout<T>,
+) -> Option<(Color, Vec2, Vec3)> {
+    let (color, margin) = config.frame_background?;
+    let bounds = layout.bounds();
+    let size = bounds.size() + Vec2::splat(2.0 * margin.max(0.0));
+    let z = config.z_layer - config.fg_z_offset;
+    Some((color, size, bounds.center().extend(z)))
+}
+
+/// Keeps the frame panel around the grid as it's resized or its font
+/// changes, and shows or hides it when `frame_background` is toggled.
+pub fn update_frame_background<T: 'static + Send + Sync>(
+    config: Res<TerminalConfig<T>>,
+    layout: Res<TerminalLayout<T>>,
+    mut frames: Query<(&mut Sprite, &mut Transform, &mut Visibility), With<FrameBackground<T>>>,
+) {
+    let placement = frame_placement(&config, &layout);
+    for (mut sprite, mut transform, mut visibility) in frames.iter_mut() {
+        let target = if placement.is_some() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        if *visibility != target {
+            *visibility = target;
+        }
+        let Some((color, size, translation)) = placement else {
+            continue;
+        };
+        if sprite.color != color {
+            sprite.color = color;
+        }
+        if sprite.custom_size != Some(size) {
+            sprite.custom_size = Some(size);
+        }
+        if transform.translation != translation {
+            transform.translation = translation;
+        }
+    }
+}
+
+/// Detects when `TerminalConfig` columns/rows no longer match the spawned grid
+/// and rebuilds it: the backend is resized, the layout recomputed, and all
+/// cell entities respawned. Runs at the start of the sync chain so the new
+/// `CellEntityIndex` is in place before sync.
+pub fn resize_grid<T: 'static + Send + Sync>(
+    mut commands: Commands,
+    config: Res<TerminalConfig<T>>,
+    mut layout: ResMut<TerminalLayout<T>>,
+    atlas: Res<FontAtlasResource<T>>,
+    cell_index: Res<CellEntityIndex<T>>,
+    terminal_res: Res<TerminalResource<T>>,
+) -> Result {
+    if cell_index.columns == config.columns && cell_index.rows == config.rows {
+        return Ok(());
+    }
+    if config.columns == 0 || config.rows == 0 {
+        if config.is_changed() {
+            warn!(
+                "Ignoring terminal resize to {}x{}: the grid needs at least one cell",
+                config.columns, config.rows
+            );
+        }
+        return Ok(());
+    }
+
+    // Retried next frame while a `TerminalDriver` holds the terminal
+    let Some(mut terminal) = terminal_res.try_lock()? else {
+        return Ok(());
+    };
+    terminal.backend_mut().resize(config.columns, config.rows);
+    for &entity in &cell_index.entities {
+        commands.entity(entity).despawn();
+    }
+
+    align_layout_to_atlas(&mut layout, &config, atlas.cell_size, atlas.scale_factor);
+
+    let index = spawn_cells(&mut commands, &config, &layout, &atlas);
+    commands.insert_resource(index);
+    Ok(())
+}
+
+/// Where each glyph sprite sits in its cell: `glyph_offset` plus the shift
+/// `glyph_align` asks for.
+pub(crate) fn glyph_translation<T: 'static + Send + Sync>(
+    config: &TerminalConfig<T>,
+    layout: &TerminalLayout<T>,
+    atlas: &FontAtlasResource<T>,
+) -> Vec2 {
+    let align = match config.glyph_align {
+        GlyphAlign::Baseline => 0.0,
+        GlyphAlign::CapCenter => atlas.cap_center * layout.cell_height,
+    };
+    config.glyph_offset + Vec2::new(0.0, align)
+}
+
+/// Moves existing glyph sprites when `TerminalConfig::glyph_offset`,
+/// `glyph_align` or `fg_z_offset` changes at runtime, or when a font swap or
+/// cell resize moves the `GlyphAlign::CapCenter` shift.
+pub fn apply_glyph_offset<T: 'static + Send + Sync>(
+    config: Res<TerminalConfig<T>>,
+    layout: Res<TerminalLayout<T>>,
+    atlas: Res<FontAtlasResource<T>>,
+    mut fg_query: Query<&mut Transform, With<ForegroundSprite<T>>>,
+    mut applied: Local<Option<Vec3>>,
+) {
+    let offset = glyph_translation(&config, &layout, &atlas);
+    let translation = offset.extend(config.fg_z_offset);
+    if *applied == Some(translation) {
+        return;
+    }
+    *applied = Some(translation);
+    for mut transform in fg_query.iter_mut() {
+        if transform.translation != translation {
+            transform.translation = translation;
+        }
+    }
+}
+
+/// Slants italic glyphs by `TerminalConfig::faux_italic` radians, sheared
+/// about the glyph's center so the top leans right.
+///
+/// `Transform` can't hold a shear, so this runs after transform propagation
+/// and writes each italic glyph's `GlobalTransform` directly, rebuilt from
+/// its cell's every frame so nothing accumulates. Glyphs that stop being
+/// italic, or all of them once `faux_italic` is unset, get their upright
+/// transform back.
+pub fn shear_italic_glyphs<T: 'static + Send + Sync>(
+    config: Res<TerminalConfig<T>>,
+    cell_index: Res<CellEntityIndex<T>>,
+    cells: Query<(&CellStyle, &GlobalTransform), With<TerminalCell<T>>>,
+    mut glyphs: Query<
+        (&Transform, &mut GlobalTransform),
+        (With<ForegroundSprite<T>>, Without<TerminalCell<T>>),
+    >,
+    mut sheared: Local<Vec<(Entity, Entity)>>,
+) {
+    if config.faux_italic.is_none() && sheared.is_empty() {
+        return;
+    }
+
+    let mut slant = |cell: Entity, fg: Entity, shear: Option<Affine3A>| {
+        let Ok((_, cell_global)) = cells.get(cell) else {
+            return;
+        };
+        let Ok((local, mut global)) = glyphs.get_mut(fg) else {
+            return;
+        };
+        let upright = cell_global.mul_transform(*local);
+        let target = match shear {
+            Some(shear) => GlobalTransform::from(upright.affine() * shear),
+            None => upright,
+        };
+        if *global != target {
+            *global = target;
+        }
+    };
+
+    let mut now = Vec::new();
+    if let Some(angle) = config.faux_italic {
+        // x += tan(angle) * y
+        let shear = Affine3A::from_mat3(Mat3::from_cols(
+            Vec3::X,
+            Vec3::new(angle.tan(), 1.0, 0.0),
+            Vec3::Z,
+        ));
+        let pairs = cell_index.entities.iter().zip(&cell_index.fg_entities);
+        for (&cell, &fg) in pairs {
+            if cells.get(cell).is_ok_and(|(style, _)| style.italic) {
+                slant(cell, fg, Some(shear));
+                now.push((cell, fg));
+            }
+        }
+    }
+
+    let still: HashSet<Entity> = now.iter().map(|&(_, fg)| fg).collect();
+    for &(cell, fg) in sheared.iter() {
+        if !still.contains(&fg) {
+            slant(cell, fg, None);
+        }
+    }
+    *sheared = now;
+}
+
+/// Spawn one parent + foreground child per cell and return the index.
+fn spawn_cells<T: 'static + Send + Sync>(
+    commands: &mut Commands,
+    config: &TerminalConfig<T>,
+    layout: &TerminalLayout<T>,
+    atlas: &FontAtlasResource<T>,
+) -> CellEntityIndex<T> {
     let total = config.columns as usize * config.rows as usize;
     let mut entities = Vec::with_capacity(total);
     let mut fg_entities = Vec::with_capacity(total);
@@ -122,14 +491,11 @@ pub fn spawn_grid<T: 'static + Send + Sync>(
     let space_index = atlas.glyph_map.get(&' ').copied().unwrap_or(0);
 
     let bg_size = layout.bg_sprite_size();
+    let glyph_offset = glyph_translation(config, layout, atlas);
 
     for row in 0..config.rows {
         for col in 0..config.columns {
-            let world_x =
-                layout.origin.x + (col as f32) * layout.cell_width + layout.cell_width / 2.0;
-            let world_y =
-                layout.origin.y - (row as f32) * layout.cell_height - layout.cell_height / 2.0;
-            let translation = Vec3::new(world_x, world_y, config.z_layer);
+            let translation = layout.grid_to_world(col, row).extend(config.z_layer);
 
             // Spawn foreground sprite as a standalone entity first
             let fg_entity = commands
@@ -145,7 +511,7 @@ pub fn spawn_grid<T: 'static + Send + Sync>(
                         custom_size: Some(Vec2::new(layout.cell_width, layout.cell_height)),
                         ..default()
                     },
-                    Transform::from_translation(Vec3::new(0.0, 0.0, 0.1)),
+                    Transform::from_translation(glyph_offset.extend(config.fg_z_offset)),
                 ))
                 .id();
 
@@ -155,6 +521,7 @@ pub fn spawn_grid<T: 'static + Send + Sync>(
                     TerminalCell::<T>::default(),
                     GridPosition { col, row },
                     CellStyle::default(),
+                    CellOpacity::default(),
                     BackgroundSprite::<T>::default(),
                     Sprite::from_color(Color::srgb(0.0, 0.0, 0.0), bg_size),
                     BaseTransform {
@@ -168,16 +535,207 @@ pub fn spawn_grid<T: 'static + Send + Sync>(
                 .add_child(fg_entity)
                 .id();
 
+            if let Some(layer) = config.render_layer {
+                let layers = RenderLayers::layer(layer as usize);
+                commands.entity(cell_entity).insert(layers.clone());
+                commands.entity(fg_entity).insert(layers);
+            }
+
             entities.push(cell_entity);
             fg_entities.push(fg_entity);
         }
     }
 
-    commands.insert_resource(CellEntityIndex::<T> {
-        entities,
-        fg_entities,
-        columns: config.columns,
-        rows: config.rows,
-        _marker: PhantomData,
-    });
+    CellEntityIndex::new(config.columns, config.rows, entities, fg_entities)
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::Color as RatColor;
+
+    use super::*;
+    use crate::color::ratatui_color_to_bevy;
+
+    #[test]
+    fn test_cell_style_to_ratatui_cell() {
+        let style = CellStyle {
+            fg: ratatui_color_to_bevy(RatColor::LightCyan),
+            bg: ratatui_color_to_bevy(RatColor::Rgb(10, 20, 30)),
+            bold: true,
+            dim: true,
+            symbol: "λ".to_string(),
+            ..default()
+        };
+        let cell = style.to_ratatui_cell();
+        assert_eq!(cell.symbol(), "λ");
+        assert_eq!(cell.fg, RatColor::LightCyan);
+        assert_eq!(cell.bg, RatColor::Rgb(10, 20, 30));
+        assert_eq!(cell.modifier, Modifier::BOLD | Modifier::DIM);
+    }
+
+    #[test]
+    fn test_faux_italic_shears_italic_glyphs_only() {
+        use crate::testing::TerminalTestHarness;
+        use crate::TerminalEmuPlugin;
+
+        struct Term;
+
+        let plugin = TerminalEmuPlugin::<Term>::new()
+            .columns(2)
+            .rows(1)
+            .faux_italic(0.2);
+        let mut harness = TerminalTestHarness::new(plugin);
+        let draw = |harness: &mut TerminalTestHarness<Term>, modifier| {
+            harness.draw(|frame| {
+                let buffer = frame.buffer_mut();
+                buffer.set_string(0, 0, "a", Style::default().add_modifier(modifier));
+                buffer.set_string(1, 0, "b", Style::default());
+            });
+        };
+        // Horizontal shift per unit of height of the glyph at (col, 0)
+        let slant = |harness: &TerminalTestHarness<Term>, col| {
+            let world = harness.app.world();
+            let index = world.resource::<CellEntityIndex<Term>>();
+            let global = world.get::<GlobalTransform>(index.get_fg(col, 0).unwrap());
+            global.unwrap().affine().matrix3.y_axis.x
+        };
+
+        draw(&mut harness, Modifier::ITALIC);
+        assert!((slant(&harness, 0) - 0.2_f32.tan()).abs() < 1e-6);
+        assert_eq!(slant(&harness, 1), 0.0);
+
+        draw(&mut harness, Modifier::empty());
+        assert_eq!(slant(&harness, 0), 0.0);
+    }
+
+    #[test]
+    fn test_frame_background_wraps_grid_and_toggles() {
+        use crate::testing::TerminalTestHarness;
+        use crate::TerminalEmuPlugin;
+
+        struct Term;
+        type Frame = (&'static Sprite, &'static Transform, &'static Visibility);
+
+        /// The frame sprite's overhang past the grid, and its visibility.
+        fn frame(harness: &mut TerminalTestHarness<Term>) -> (Vec2, f32, Visibility) {
+            let world = harness.app.world_mut();
+            let layout = world.resource::<TerminalLayout<Term>>().clone();
+            let grid = layout.bounds().size();
+            let mut query = world.query_filtered::<Frame, With<FrameBackground<Term>>>();
+            let (sprite, transform, visibility) = query.single(world).unwrap();
+            let overhang = sprite.custom_size.unwrap() - grid;
+            (overhang, transform.translation.z, *visibility)
+        }
+
+        fn config(harness: &mut TerminalTestHarness<Term>) -> Mut<'_, TerminalConfig<Term>> {
+            harness.app.world_mut().resource_mut()
+        }
+
+        let plugin = TerminalEmuPlugin::<Term>::new()
+            .columns(10)
+            .rows(4)
+            .frame_background(Color::BLACK, 8.0);
+        let mut harness = TerminalTestHarness::new(plugin);
+        let (overhang, z, visibility) = frame(&mut harness);
+        assert_eq!(overhang, Vec2::splat(16.0));
+        assert!(z < 0.0);
+        assert_eq!(visibility, Visibility::Inherited);
+
+        // Follows a resize
+        config(&mut harness).columns = 20;
+        harness.update();
+        assert_eq!(frame(&mut harness).0, Vec2::splat(16.0));
+
+        config(&mut harness).frame_background = None;
+        harness.update();
+        assert_eq!(frame(&mut harness).2, Visibility::Hidden);
+    }
+
+    #[test]
+    fn test_glyph_align_cap_center_moves_glyphs_only() {
+        use crate::testing::TerminalTestHarness;
+        use crate::TerminalEmuPlugin;
+
+        struct Term;
+
+        let plugin = TerminalEmuPlugin::<Term>::new()
+            .columns(2)
+            .rows(1)
+            .glyph_offset(Vec2::new(1.0, 0.0));
+        let mut harness = TerminalTestHarness::new(plugin);
+        let translation = |harness: &TerminalTestHarness<Term>, fg: bool| {
+            let world = harness.app.world();
+            let index = world.resource::<CellEntityIndex<Term>>();
+            let entity = if fg {
+                index.get_fg(1, 0)
+            } else {
+                index.get(1, 0)
+            };
+            world.get::<Transform>(entity.unwrap()).unwrap().translation
+        };
+        let cell = translation(&harness, false);
+        assert_eq!(translation(&harness, true).truncate(), Vec2::new(1.0, 0.0));
+
+        let world = harness.app.world_mut();
+        world.resource_mut::<TerminalConfig<Term>>().glyph_align = GlyphAlign::CapCenter;
+        harness.update();
+        let world = harness.app.world();
+        let cell_height = world.resource::<TerminalLayout<Term>>().cell_height;
+        let shift = world.resource::<FontAtlasResource<Term>>().cap_center * cell_height;
+        assert!(shift != 0.0 && shift.abs() < cell_height * 0.25);
+        let glyph = translation(&harness, true);
+        assert_eq!(glyph.truncate(), Vec2::new(1.0, shift));
+        assert_eq!(translation(&harness, false), cell);
+    }
+
+    #[test]
+    fn test_terminal_view_iter_and_get() {
+        struct Term;
+        let mut app = App::new();
+        let mut entities = Vec::new();
+        for row in 0..2 {
+            for col in 0..2 {
+                let symbol = format!("{}", row * 2 + col);
+                let entity = app
+                    .world_mut()
+                    .spawn((
+                        TerminalCell::<Term>::default(),
+                        GridPosition { col, row },
+                        CellStyle { symbol, ..default() },
+                    ))
+                    .id();
+                entities.push(entity);
+            }
+        }
+        let fg = entities.clone();
+        app.insert_resource(CellEntityIndex::<Term>::new(2, 2, entities.clone(), fg));
+
+        app.add_systems(Update, move |view: TerminalView<Term>| {
+            let symbols: Vec<&str> = view.iter().map(|(_, s, _)| s.symbol.as_str()).collect();
+            assert_eq!(symbols, ["0", "1", "2", "3"]);
+            let (style, entity) = view.get(1, 1).unwrap();
+            assert_eq!(style.symbol, "3");
+            assert_eq!(entity, entities[3]);
+            assert!(view.get(2, 0).is_none());
+        });
+        app.update();
+    }
+
+    #[test]
+    fn test_index_neighbors_rows_and_columns() {
+        struct Term;
+        let mut world = World::new();
+        let cells: Vec<Entity> = (0..6).map(|_| world.spawn_empty().id()).collect();
+        let index = CellEntityIndex::<Term>::new(3, 2, cells.clone(), cells.clone());
+
+        assert_eq!(index.row(1).collect::<Vec<_>>(), cells[3..6]);
+        assert_eq!(index.column(2).collect::<Vec<_>>(), [cells[2], cells[5]]);
+        assert_eq!(index.row(2).count() + index.column(3).count(), 0);
+
+        let pos = index.position(4);
+        assert_eq!(pos, GridPosition::new(1, 1));
+        let sides: Vec<_> = index.neighbors4(pos).collect();
+        assert_eq!(sides, [cells[1], cells[3], cells[5]]);
+        assert_eq!(index.neighbors8(pos).count(), 5);
+    }
 }
@@ -0,0 +1,173 @@
+use std::marker::PhantomData;
+
+use bevy::camera::visibility::RenderLayers;
+use bevy::prelude::*;
+
+use crate::{TerminalConfig, TerminalLayout};
+
+/// Draws thin lines on terminal `T`'s cell boundaries, to check glyph
+/// alignment and box-drawing seams, or as a visible board for grid-based
+/// games. Toggle `enabled` at runtime; changes show up the same frame.
+///
+/// Lines sit between the cell backgrounds and the glyphs and follow the
+/// grid itself, not cells moved by geometry effects.
+#[derive(Resource, Clone, Debug)]
+pub struct ShowGridLines<T: 'static + Send + Sync> {
+    /// Toggle the lines (default: false).
+    pub enabled: bool,
+    /// Line color (default: translucent gray).
+    pub color: Color,
+    /// Line width in pixels (default: 1.0).
+    pub thickness: f32,
+    _marker: PhantomData<T>,
+}
+
+impl<T: 'static + Send + Sync> Default for ShowGridLines<T> {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            color: Color::srgba(0.5, 0.5, 0.5, 0.4),
+            thickness: 1.0,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Root entity of the lines spawned by `draw_grid_lines`.
+#[derive(Component)]
+pub struct GridLines<T: 'static + Send + Sync>(PhantomData<T>);
+
+/// What the lines were last built from, to rebuild only when it changes.
+#[derive(Clone, PartialEq)]
+pub struct GridLinesKey {
+    origin: Vec2,
+    cell: Vec2,
+    columns: u16,
+    rows: u16,
+    z: f32,
+    color: Color,
+    thickness: f32,
+}
+
+/// World-space x of each vertical line and y of each horizontal line, one
+/// per cell edge including the grid's outline.
+pub fn grid_line_positions<T: 'static + Send + Sync>(
+    layout: &TerminalLayout<T>,
+) -> (Vec<f32>, Vec<f32>) {
+    let xs = (0..=layout.columns)
+        .map(|col| layout.origin.x + col as f32 * layout.cell_width)
+        .collect();
+    let ys = (0..=layout.rows)
+        .map(|row| layout.origin.y - row as f32 * layout.cell_height)
+        .collect();
+    (xs, ys)
+}
+
+/// Spawns, rebuilds and removes the grid lines to match `ShowGridLines<T>`
+/// and the current layout.
+pub fn draw_grid_lines<T: 'static + Send + Sync>(
+    mut commands: Commands,
+    lines: Res<ShowGridLines<T>>,
+    config: Res<TerminalConfig<T>>,
+    layout: Res<TerminalLayout<T>>,
+    existing: Query<Entity, With<GridLines<T>>>,
+    mut shown: Local<Option<GridLinesKey>>,
+) {
+    let key = lines.enabled.then(|| GridLinesKey {
+        origin: layout.origin,
+        cell: Vec2::new(layout.cell_width, layout.cell_height),
+        columns: layout.columns,
+        rows: layout.rows,
+        // Over the backgrounds, under the glyphs
+        z: config.z_layer + config.fg_z_offset * 0.5,
+        color: lines.color,
+        thickness: lines.thickness,
+    });
+    if *shown == key {
+        return;
+    }
+
+    // Despawning the root takes its line children with it
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+    *shown = key.clone();
+    let Some(key) = key else {
+        return;
+    };
+
+    let size = key.cell * Vec2::new(key.columns as f32, key.rows as f32);
+    let center = key.origin + Vec2::new(size.x, -size.y) / 2.0;
+    let (xs, ys) = grid_line_positions(&layout);
+    // Horizontal lines overhang by half a line each side to square the corners
+    let vertical = Vec2::new(key.thickness, size.y);
+    let horizontal = Vec2::new(size.x + key.thickness, key.thickness);
+    let placed = xs
+        .into_iter()
+        .map(|x| (Vec2::new(x, center.y), vertical))
+        .chain(ys.into_iter().map(|y| (Vec2::new(center.x, y), horizontal)));
+
+    let layer = config.render_layer.map(|l| RenderLayers::layer(l as usize));
+    let mut children = Vec::new();
+    for (position, line_size) in placed {
+        let mut line = commands.spawn((
+            Sprite::from_color(key.color, line_size),
+            Transform::from_translation((position - center).extend(0.0)),
+        ));
+        if let Some(layer) = &layer {
+            line.insert(layer.clone());
+        }
+        children.push(line.id());
+    }
+
+    let mut root = commands.spawn((
+        GridLines::<T>(PhantomData),
+        Transform::from_translation(center.extend(key.z)),
+        Visibility::default(),
+    ));
+    if let Some(layer) = layer {
+        root.insert(layer);
+    }
+    root.add_children(&children);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TerminalTestHarness;
+    use crate::TerminalEmuPlugin;
+
+    struct Term;
+
+    fn line_count(harness: &mut TerminalTestHarness<Term>) -> usize {
+        let world = harness.app.world_mut();
+        let mut roots = world.query_filtered::<&Children, With<GridLines<Term>>>();
+        roots.iter(world).map(|children| children.len()).sum()
+    }
+
+    fn show(harness: &mut TerminalTestHarness<Term>, enabled: bool) {
+        let world = harness.app.world_mut();
+        world.resource_mut::<ShowGridLines<Term>>().enabled = enabled;
+        harness.update();
+    }
+
+    #[test]
+    fn test_lines_land_on_cell_edges_and_toggle() {
+        let plugin = TerminalEmuPlugin::<Term>::new().columns(4).rows(2);
+        let mut harness = TerminalTestHarness::new(plugin);
+        assert_eq!(line_count(&mut harness), 0);
+
+        show(&mut harness, true);
+        assert_eq!(line_count(&mut harness), 5 + 3);
+
+        let layout = harness.app.world().resource::<TerminalLayout<Term>>();
+        let (xs, ys) = grid_line_positions(layout);
+        let right = layout.grid_to_world(3, 1) + Vec2::new(layout.cell_width, 0.0) / 2.0;
+        let bottom = layout.grid_to_world(3, 1) - Vec2::new(0.0, layout.cell_height) / 2.0;
+        assert_eq!((xs[0], ys[0]), (layout.origin.x, layout.origin.y));
+        assert_eq!((xs[4], ys[2]), (right.x, bottom.y));
+
+        show(&mut harness, false);
+        assert_eq!(line_count(&mut harness), 0);
+    }
+}
@@ -1,10 +1,13 @@
-use std::collections::VecDeque;
+use std::any::TypeId;
+use std::collections::{HashSet, VecDeque};
 use std::marker::PhantomData;
 
-use bevy::input::keyboard::KeyboardInput;
+use bevy::input::keyboard::{Key, KeyboardInput};
 use bevy::input::ButtonState;
 use bevy::prelude::*;
 
+use crate::TerminalConfig;
+
 /// Queue of terminal input events for the ratatui app to consume.
 #[derive(Resource)]
 pub struct TerminalInputQueue<T: 'static + Send + Sync> {
@@ -21,47 +24,163 @@ impl<T: 'static + Send + Sync> Default for TerminalInputQueue<T> {
     }
 }
 
+impl<T: 'static + Send + Sync> TerminalInputQueue<T> {
+    /// Queue `event`, keeping at most `config.max_queued_events` by
+    /// `config.input_overflow`. Returns whether `event` was queued.
+    pub fn push(&mut self, event: terminput::Event, config: &TerminalConfig<T>) -> bool {
+        let max = config.max_queued_events;
+        let overflow = config.input_overflow;
+        if overflow != InputOverflow::Unbounded && self.events.len() >= max {
+            if overflow == InputOverflow::DropNewest || max == 0 {
+                return false;
+            }
+            let excess = self.events.len() + 1 - max;
+            self.events.drain(..excess);
+        }
+        self.events.push_back(event);
+        true
+    }
+
+    /// Number of events waiting to be read.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Whether no events are waiting.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+/// What `TerminalInputQueue` does with new events once it holds
+/// `TerminalConfig::max_queued_events`, e.g. while the app isn't reading it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InputOverflow {
+    /// Discard the oldest queued events to make room (default), so the app
+    /// sees the latest input when it resumes reading.
+    #[default]
+    DropOldest,
+    /// Discard the new events, keeping what was typed first.
+    DropNewest,
+    /// Never discard anything; the queue grows until it's read.
+    Unbounded,
+}
+
+/// How OS key auto-repeat reaches the input queue.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RepeatMode {
+    /// Queue every repeat as `KeyEventKind::Repeat` (default).
+    #[default]
+    Forward,
+    /// Drop repeats; holding a key sends a single press.
+    Ignore,
+    /// Queue at most one repeat per key per frame, so a low frame rate
+    /// doesn't build up a backlog of repeats.
+    FirstOnly,
+}
+
+/// Which terminal keyboard input goes to, shared by all terminal instances.
+///
+/// With no terminal focused (the default), every terminal with
+/// `receive_input` set gets every key, which suits single-terminal apps.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FocusedTerminal(Option<TypeId>);
+
+impl FocusedTerminal {
+    /// Route keyboard input to terminal `T` only.
+    pub fn focus<T: 'static + Send + Sync>(&mut self) {
+        self.0 = Some(TypeId::of::<T>());
+    }
+
+    /// Go back to sending input to every terminal.
+    pub fn clear(&mut self) {
+        self.0 = None;
+    }
+
+    /// Whether terminal `T` has focus.
+    pub fn is_focused<T: 'static + Send + Sync>(&self) -> bool {
+        self.0 == Some(TypeId::of::<T>())
+    }
+
+    /// Whether terminal `T` should receive input: it has focus, or nothing does.
+    pub fn accepts<T: 'static + Send + Sync>(&self) -> bool {
+        self.0.is_none_or(|id| id == TypeId::of::<T>())
+    }
+}
+
+/// System that focuses terminal `T`, e.g.
+/// `focus_terminal::<Chat>.run_if(input_just_pressed(KeyCode::F2))`.
+pub fn focus_terminal<T: 'static + Send + Sync>(mut focused: ResMut<FocusedTerminal>) {
+    if !focused.is_focused::<T>() {
+        focused.focus::<T>();
+    }
+}
+
 /// System that forwards Bevy keyboard events to the terminal input queue.
 pub fn forward_input<T: 'static + Send + Sync>(
     mut keyboard_events: MessageReader<KeyboardInput>,
     keys: Res<ButtonInput<KeyCode>>,
+    focused: Res<FocusedTerminal>,
+    config: Res<TerminalConfig<T>>,
     mut queue: ResMut<TerminalInputQueue<T>>,
 ) {
+    // Drop events while unfocused so they don't arrive late on refocus
+    if !focused.accepts::<T>() {
+        keyboard_events.clear();
+        return;
+    }
+
+    let mut repeated = HashSet::new();
     for event in keyboard_events.read() {
-        // Only process key presses (not releases)
-        if event.state != ButtonState::Pressed {
+        if event.state == ButtonState::Released && !config.forward_releases {
             continue;
         }
+        if event.repeat && event.state == ButtonState::Pressed {
+            let keep = match config.repeat_mode {
+                RepeatMode::Forward => true,
+                RepeatMode::Ignore => false,
+                RepeatMode::FirstOnly => repeated.insert(event.key_code),
+            };
+            if !keep {
+                continue;
+            }
+        }
 
         if let Some(terminal_event) = bevy_key_to_terminal_event(event, &keys) {
-            queue.events.push_back(terminal_event);
+            queue.push(terminal_event, &config);
         }
     }
 }
 
+/// Left/right key pairs and the modifier flag they set.
+const MODIFIER_KEYS: [(KeyCode, KeyCode, terminput::KeyModifiers); 4] = [
+    (KeyCode::ControlLeft, KeyCode::ControlRight, terminput::KeyModifiers::CTRL),
+    (KeyCode::ShiftLeft, KeyCode::ShiftRight, terminput::KeyModifiers::SHIFT),
+    (KeyCode::AltLeft, KeyCode::AltRight, terminput::KeyModifiers::ALT),
+    (KeyCode::SuperLeft, KeyCode::SuperRight, terminput::KeyModifiers::SUPER),
+];
+
 /// Convert a Bevy KeyboardInput into a terminput Event.
 fn bevy_key_to_terminal_event(
     event: &KeyboardInput,
     keys: &ButtonInput<KeyCode>,
 ) -> Option<terminput::Event> {
     let mut modifiers = terminput::KeyModifiers::NONE;
-
-    if keys.pressed(KeyCode::ControlLeft) || keys.pressed(KeyCode::ControlRight) {
-        modifiers |= terminput::KeyModifiers::CTRL;
-    }
-    if keys.pressed(KeyCode::ShiftLeft) || keys.pressed(KeyCode::ShiftRight) {
-        modifiers |= terminput::KeyModifiers::SHIFT;
-    }
-    if keys.pressed(KeyCode::AltLeft) || keys.pressed(KeyCode::AltRight) {
-        modifiers |= terminput::KeyModifiers::ALT;
-    }
-    if keys.pressed(KeyCode::SuperLeft) || keys.pressed(KeyCode::SuperRight) {
-        modifiers |= terminput::KeyModifiers::SUPER;
+    for (left, right, flag) in MODIFIER_KEYS {
+        // A released modifier no longer applies, even to its own release
+        // event, whatever order the input state was updated in.
+        let released = event.state == ButtonState::Released
+            && (event.key_code == left || event.key_code == right);
+        if !released && (keys.pressed(left) || keys.pressed(right)) {
+            modifiers |= flag;
+        }
     }
 
     let code = bevy_keycode_to_terminput(event)?;
 
-    let kind = if event.repeat {
+    let kind = if event.state == ButtonState::Released {
+        terminput::KeyEventKind::Release
+    } else if event.repeat {
         terminput::KeyEventKind::Repeat
     } else {
         terminput::KeyEventKind::Press
@@ -85,6 +204,19 @@ fn bevy_keycode_to_terminput(event: &KeyboardInput) -> Option<terminput::KeyCode
         }
     }
 
+    // Releases carry no text; the logical key still names the character
+    if event.state == ButtonState::Released {
+        if let Key::Character(ref chars) = event.logical_key {
+            if let Some(ch) = chars.chars().next() {
+                if !ch.is_control() {
+                    return Some(terminput::KeyCode::Char(ch));
+                }
+            }
+        }
+    }
+
+    let media = terminput::KeyCode::Media;
+
     // Fall back to physical key mapping
     match event.key_code {
         KeyCode::Space => Some(terminput::KeyCode::Char(' ')),
@@ -114,6 +246,50 @@ fn bevy_keycode_to_terminput(event: &KeyboardInput) -> Option<terminput::KeyCode
         KeyCode::F10 => Some(terminput::KeyCode::F(10)),
         KeyCode::F11 => Some(terminput::KeyCode::F(11)),
         KeyCode::F12 => Some(terminput::KeyCode::F(12)),
+        KeyCode::F13 => Some(terminput::KeyCode::F(13)),
+        KeyCode::F14 => Some(terminput::KeyCode::F(14)),
+        KeyCode::F15 => Some(terminput::KeyCode::F(15)),
+        KeyCode::F16 => Some(terminput::KeyCode::F(16)),
+        KeyCode::F17 => Some(terminput::KeyCode::F(17)),
+        KeyCode::F18 => Some(terminput::KeyCode::F(18)),
+        KeyCode::F19 => Some(terminput::KeyCode::F(19)),
+        KeyCode::F20 => Some(terminput::KeyCode::F(20)),
+        KeyCode::F21 => Some(terminput::KeyCode::F(21)),
+        KeyCode::F22 => Some(terminput::KeyCode::F(22)),
+        KeyCode::F23 => Some(terminput::KeyCode::F(23)),
+        KeyCode::F24 => Some(terminput::KeyCode::F(24)),
+        // Numpad keys, for when the platform reports no text (e.g. NumLock
+        // handling or modifier combos)
+        KeyCode::Numpad0 => Some(terminput::KeyCode::Char('0')),
+        KeyCode::Numpad1 => Some(terminput::KeyCode::Char('1')),
+        KeyCode::Numpad2 => Some(terminput::KeyCode::Char('2')),
+        KeyCode::Numpad3 => Some(terminput::KeyCode::Char('3')),
+        KeyCode::Numpad4 => Some(terminput::KeyCode::Char('4')),
+        KeyCode::Numpad5 => Some(terminput::KeyCode::Char('5')),
+        KeyCode::Numpad6 => Some(terminput::KeyCode::Char('6')),
+        KeyCode::Numpad7 => Some(terminput::KeyCode::Char('7')),
+        KeyCode::Numpad8 => Some(terminput::KeyCode::Char('8')),
+        KeyCode::Numpad9 => Some(terminput::KeyCode::Char('9')),
+        KeyCode::NumpadAdd => Some(terminput::KeyCode::Char('+')),
+        KeyCode::NumpadSubtract => Some(terminput::KeyCode::Char('-')),
+        KeyCode::NumpadMultiply | KeyCode::NumpadStar => Some(terminput::KeyCode::Char('*')),
+        KeyCode::NumpadDivide => Some(terminput::KeyCode::Char('/')),
+        KeyCode::NumpadDecimal => Some(terminput::KeyCode::Char('.')),
+        KeyCode::NumpadComma => Some(terminput::KeyCode::Char(',')),
+        KeyCode::NumpadEqual => Some(terminput::KeyCode::Char('=')),
+        KeyCode::NumpadHash => Some(terminput::KeyCode::Char('#')),
+        KeyCode::NumpadParenLeft => Some(terminput::KeyCode::Char('(')),
+        KeyCode::NumpadParenRight => Some(terminput::KeyCode::Char(')')),
+        KeyCode::NumpadBackspace => Some(terminput::KeyCode::Backspace),
+        KeyCode::ContextMenu => Some(terminput::KeyCode::Menu),
+        KeyCode::PrintScreen => Some(terminput::KeyCode::PrintScreen),
+        KeyCode::MediaPlayPause => Some(media(terminput::MediaKeyCode::PlayPause)),
+        KeyCode::MediaStop => Some(media(terminput::MediaKeyCode::Stop)),
+        KeyCode::MediaTrackNext => Some(media(terminput::MediaKeyCode::TrackNext)),
+        KeyCode::MediaTrackPrevious => Some(media(terminput::MediaKeyCode::TrackPrevious)),
+        KeyCode::AudioVolumeDown => Some(media(terminput::MediaKeyCode::LowerVolume)),
+        KeyCode::AudioVolumeUp => Some(media(terminput::MediaKeyCode::RaiseVolume)),
+        KeyCode::AudioVolumeMute => Some(media(terminput::MediaKeyCode::MuteVolume)),
         KeyCode::CapsLock => Some(terminput::KeyCode::CapsLock),
         KeyCode::ScrollLock => Some(terminput::KeyCode::ScrollLock),
         KeyCode::NumLock => Some(terminput::KeyCode::NumLock),
@@ -130,3 +306,171 @@ fn bevy_keycode_to_terminput(event: &KeyboardInput) -> Option<terminput::KeyCode
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::input::keyboard::NativeKey;
+
+    use super::*;
+
+    fn key_event(key_code: KeyCode, text: Option<&str>) -> KeyboardInput {
+        KeyboardInput {
+            key_code,
+            logical_key: Key::Unidentified(NativeKey::Unidentified),
+            state: ButtonState::Pressed,
+            text: text.map(Into::into),
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        }
+    }
+
+    /// Marker terminal for the `forward_input` tests.
+    struct Keys;
+
+    /// Runs `forward_input` once per frame of `frames`, returning the queued
+    /// chars and their kinds.
+    fn forward(
+        config: TerminalConfig<Keys>,
+        frames: Vec<Vec<KeyboardInput>>,
+    ) -> Vec<(char, terminput::KeyEventKind)> {
+        let mut app = App::new();
+        app.add_message::<KeyboardInput>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<FocusedTerminal>()
+            .init_resource::<TerminalInputQueue<Keys>>()
+            .insert_resource(config)
+            .add_systems(Update, forward_input::<Keys>);
+        for events in frames {
+            for event in events {
+                app.world_mut().write_message(event);
+            }
+            app.update();
+        }
+        let mut queue = app.world_mut().resource_mut::<TerminalInputQueue<Keys>>();
+        let keys = queue.events.drain(..).filter_map(|event| match event {
+            terminput::Event::Key(key) => match key.code {
+                terminput::KeyCode::Char(ch) => Some((ch, key.kind)),
+                _ => None,
+            },
+            _ => None,
+        });
+        keys.collect()
+    }
+
+    #[test]
+    fn test_releases_forwarded_only_when_enabled() {
+        use terminput::KeyEventKind::{Press, Release};
+
+        let events = || {
+            let press = key_event(KeyCode::KeyA, Some("a"));
+            let release = KeyboardInput {
+                state: ButtonState::Released,
+                ..press.clone()
+            };
+            vec![vec![press, release]]
+        };
+        assert_eq!(forward(default(), events()), [('a', Press)]);
+
+        let config = TerminalConfig {
+            forward_releases: true,
+            ..default()
+        };
+        assert_eq!(forward(config, events()), [('a', Press), ('a', Release)]);
+    }
+
+    #[test]
+    fn test_repeat_modes() {
+        use terminput::KeyEventKind::{Press, Repeat};
+
+        let repeat = |key_code, text| KeyboardInput {
+            repeat: true,
+            ..key_event(key_code, Some(text))
+        };
+        // Two frames of 'a' held down, and 'b' held down in the first
+        let frames = || {
+            let first = vec![
+                key_event(KeyCode::KeyA, Some("a")),
+                repeat(KeyCode::KeyA, "a"),
+                repeat(KeyCode::KeyB, "b"),
+                repeat(KeyCode::KeyA, "a"),
+                repeat(KeyCode::KeyB, "b"),
+            ];
+            let second = vec![repeat(KeyCode::KeyA, "a"), repeat(KeyCode::KeyA, "a")];
+            vec![first, second]
+        };
+        let forward_with = |repeat_mode| {
+            let config = TerminalConfig {
+                repeat_mode,
+                ..default()
+            };
+            forward(config, frames())
+        };
+
+        assert_eq!(forward_with(RepeatMode::Forward).len(), 7);
+        assert_eq!(forward_with(RepeatMode::Ignore), [('a', Press)]);
+        assert_eq!(
+            forward_with(RepeatMode::FirstOnly),
+            [('a', Press), ('a', Repeat), ('b', Repeat), ('a', Repeat)]
+        );
+    }
+
+    #[test]
+    fn test_queue_overflow_policies() {
+        struct Term;
+
+        let push_all = |overflow| {
+            let config = TerminalConfig::<Term> {
+                max_queued_events: 2,
+                input_overflow: overflow,
+                ..default()
+            };
+            let mut queue = TerminalInputQueue::<Term>::default();
+            let queued = ['a', 'b', 'c'].map(|ch| {
+                let event = terminput::KeyEvent::new(terminput::KeyCode::Char(ch));
+                queue.push(terminput::Event::Key(event), &config)
+            });
+            let chars = queue.events.iter().filter_map(|event| match event {
+                terminput::Event::Key(key) => match key.code {
+                    terminput::KeyCode::Char(ch) => Some(ch),
+                    _ => None,
+                },
+                _ => None,
+            });
+            (queued, chars.collect::<String>())
+        };
+
+        let (all, refused) = ([true; 3], [true, true, false]);
+        assert_eq!(push_all(InputOverflow::DropOldest), (all, "bc".into()));
+        assert_eq!(push_all(InputOverflow::DropNewest), (refused, "ab".into()));
+        assert_eq!(push_all(InputOverflow::Unbounded), (all, "abc".into()));
+    }
+
+    #[test]
+    fn test_numpad_fallback() {
+        let map = |key| bevy_keycode_to_terminput(&key_event(key, None));
+        assert_eq!(map(KeyCode::Numpad7), Some(terminput::KeyCode::Char('7')));
+        assert_eq!(map(KeyCode::NumpadAdd), Some(terminput::KeyCode::Char('+')));
+        assert_eq!(map(KeyCode::NumpadDivide), Some(terminput::KeyCode::Char('/')));
+        assert_eq!(map(KeyCode::NumpadDecimal), Some(terminput::KeyCode::Char('.')));
+        assert_eq!(map(KeyCode::NumpadEnter), Some(terminput::KeyCode::Enter));
+    }
+
+    #[test]
+    fn test_other_keys_fallback() {
+        let map = |key| bevy_keycode_to_terminput(&key_event(key, None));
+        assert_eq!(map(KeyCode::ContextMenu), Some(terminput::KeyCode::Menu));
+        assert_eq!(map(KeyCode::F20), Some(terminput::KeyCode::F(20)));
+        assert_eq!(
+            map(KeyCode::MediaPlayPause),
+            Some(terminput::KeyCode::Media(terminput::MediaKeyCode::PlayPause))
+        );
+        assert_eq!(map(KeyCode::ShiftLeft), None);
+    }
+
+    #[test]
+    fn test_text_wins_over_physical_key() {
+        // A numpad key under a layout that produces different text
+        let event = key_event(KeyCode::Numpad1, Some("!"));
+        assert_eq!(bevy_keycode_to_terminput(&event), Some(terminput::KeyCode::Char('!')));
+    }
+}
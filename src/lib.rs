@@ -1,23 +1,67 @@
+#[cfg(feature = "render")]
 pub mod atlas;
+#[cfg(feature = "render")]
+mod atlas_cache;
 pub mod backend;
+pub mod bitmap_font;
 pub mod color;
+#[cfg(feature = "crt")]
+pub mod crt;
+#[cfg(feature = "render")]
+pub mod culling;
+pub mod cursor;
+#[cfg(feature = "debug")]
+pub mod debug;
+#[cfg(feature = "render")]
 pub mod effects;
+#[cfg(feature = "gamepad")]
+pub mod gamepad;
+pub mod glyph;
+#[cfg(feature = "render")]
 pub mod grid;
+#[cfg(feature = "render")]
+pub mod grid_lines;
 pub mod input;
+#[cfg(feature = "render")]
+pub mod render_target;
+pub mod scroll;
+#[cfg(feature = "render")]
+pub mod selection;
+#[cfg(feature = "render")]
 pub mod sync;
+pub mod testing;
+pub mod throttle;
+#[cfg(feature = "ui")]
+pub mod ui;
 
 use std::marker::PhantomData;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use bevy::color::Color;
 use bevy::prelude::*;
 
 use backend::BevyBackend;
-use input::TerminalInputQueue;
-use sync::SyncGeneration;
+use bitmap_font::BitmapFont;
+use color::DimMode;
+#[cfg(feature = "render")]
+use culling::TerminalVisible;
+#[cfg(feature = "render")]
+use cursor::CursorState;
+use cursor::CursorStyle;
+#[cfg(feature = "render")]
+use effects::EffectClock;
+use glyph::{AtlasFilter, AtlasPadding, CharSet, GlyphAlign, MonospacePolicy};
+use input::{InputOverflow, RepeatMode, TerminalInputQueue};
+use scroll::ScrollOffset;
+#[cfg(feature = "render")]
+use selection::Selection;
+#[cfg(feature = "render")]
+use sync::{SyncGeneration, SyncStats};
+use throttle::UpdateThrottle;
 
 /// The embedded default font (JetBrains Mono Regular).
-const DEFAULT_FONT_BYTES: &[u8] = include_bytes!("../assets/JetBrainsMono-Regular.ttf");
+pub(crate) const DEFAULT_FONT_BYTES: &[u8] = include_bytes!("../assets/JetBrainsMono-Regular.ttf");
 
 /// Source of font data for the terminal.
 #[derive(Clone, Debug)]
@@ -26,53 +70,125 @@ pub enum FontSource {
     Default,
     /// Use custom font bytes loaded from a file or other source.
     Custom(Vec<u8>),
+    /// Load the font asynchronously through Bevy's `AssetServer` from an asset
+    /// path. The grid spawns empty and fills in once the font has loaded.
+    Asset(String),
+    /// Use a pre-rasterized bitmap glyph sheet. See `BitmapFont` for the
+    /// sheet format.
+    Bitmap(Arc<BitmapFont>),
 }
 
 impl FontSource {
-    /// Load a font from a file path.
+    /// Load a font from a file path (blocking).
+    /// Falls back to the default font with a warning if the file can't be read.
     pub fn from_file(path: impl AsRef<std::path::Path>) -> Self {
-        let bytes = std::fs::read(path.as_ref())
-            .unwrap_or_else(|e| panic!("Failed to read font file {:?}: {}", path.as_ref(), e));
-        FontSource::Custom(bytes)
+        match std::fs::read(path.as_ref()) {
+            Ok(bytes) => FontSource::Custom(bytes),
+            Err(e) => {
+                warn!(
+                    "Failed to read font file {:?}: {}, falling back to the default font",
+                    path.as_ref(),
+                    e
+                );
+                FontSource::Default
+            }
+        }
+    }
+
+    /// Load a font through the `AssetServer` without blocking startup.
+    pub fn asset(path: impl Into<String>) -> Self {
+        FontSource::Asset(path.into())
+    }
+
+    /// Use a bitmap glyph sheet.
+    pub fn bitmap(font: BitmapFont) -> Self {
+        FontSource::Bitmap(Arc::new(font))
     }
 
     /// Get the font bytes.
+    ///
+    /// `Asset` sources return the default font until the asset has loaded;
+    /// the atlas keeps the real bytes once they arrive. `Bitmap` sources have
+    /// no outline data and also return the default font.
     pub fn bytes(&self) -> &[u8] {
         match self {
-            FontSource::Default => DEFAULT_FONT_BYTES,
+            FontSource::Default | FontSource::Asset(_) | FontSource::Bitmap(_) => {
+                DEFAULT_FONT_BYTES
+            }
             FontSource::Custom(bytes) => bytes,
         }
     }
 }
 
 pub mod prelude {
-    pub use crate::atlas::FontAtlasResource;
     pub use crate::backend::BevyBackend;
-    pub use crate::effects::breathe::Breathe;
-    pub use crate::effects::bubbly::Bubbly;
-    pub use crate::effects::collapse::Collapse;
-    pub use crate::effects::explode::Explode;
-    pub use crate::effects::glitch::Glitch;
-    pub use crate::effects::glow::Glow;
-    pub use crate::effects::knock::Knock;
-    pub use crate::effects::gravity::{CellVelocity, Gravity};
-    pub use crate::effects::jitter::Jitter;
-    pub use crate::effects::rainbow::Rainbow;
-    pub use crate::effects::ripple::Ripple;
-    pub use crate::effects::scatter::Scatter;
-    pub use crate::effects::shiny::Shiny;
-    pub use crate::effects::slash::Slash;
-    pub use crate::effects::wave::Wave;
-    pub use crate::effects::{EffectRegion, GridRect, TargetTerminal};
-    pub use crate::grid::{
-        BackgroundSprite, BaseTransform, CellEntityIndex, CellStyle, ForegroundSprite,
-        GridPosition, TerminalCell,
+    pub use crate::bitmap_font::BitmapFont;
+    pub use crate::color::{DimMode, TintMode};
+    pub use crate::cursor::CursorStyle;
+    pub use crate::glyph::{AtlasFilter, AtlasPadding, CharSet, GlyphAlign, MonospacePolicy};
+    pub use crate::input::{
+        focus_terminal, FocusedTerminal, InputOverflow, RepeatMode, TerminalInputQueue,
     };
-    pub use crate::input::TerminalInputQueue;
+    pub use crate::scroll::ScrollOffset;
+    pub use crate::testing::TerminalTestHarness;
+    pub use crate::throttle::{terminal_update_due, UpdateThrottle};
     pub use crate::{
-        FontSource, TerminalConfig, TerminalEmuPlugin, TerminalLayout, TerminalResource,
-        TerminalSet,
+        ConfigError, FontSource, GridPosition, TerminalConfig, TerminalDriver, TerminalEmuPlugin,
+        TerminalLayout, TerminalPoisoned, TerminalResource, TerminalSet,
     };
+
+    #[cfg(feature = "render")]
+    pub use crate::{
+        atlas::{AtlasKey, FontAtlasResource, MissingGlyphLog, SharedAtlases},
+        culling::TerminalVisible,
+        cursor::{CursorSprite, CursorState},
+        effects::breathe::Breathe,
+        effects::bubbly::Bubbly,
+        effects::collapse::{Collapse, CollapseDir, StaggerAxis},
+        effects::explode::Explode,
+        effects::fade::Fade,
+        effects::flash::{FlashOnChange, FlashTimers},
+        effects::flip::{Flip, FlipAxis},
+        effects::glitch::Glitch,
+        effects::glow::Glow,
+        effects::gradient_tint::GradientTint,
+        effects::gravity::{CellVelocity, Gravity},
+        effects::jitter::Jitter,
+        effects::knock::Knock,
+        effects::line_reveal::{LineReveal, RevealUnit},
+        effects::magnet::Magnet,
+        effects::rainbow::Rainbow,
+        effects::ripple::Ripple,
+        effects::scatter::Scatter,
+        effects::shiny::Shiny,
+        effects::slash::{Blade, Slash},
+        effects::swirl::Swirl,
+        effects::wave::{Wave, WaveAxis},
+        effects::{
+            envelope_gain, seeded_hash, simple_hash, spawn_effect, CellMask, ColorFrameCache,
+            EffectClock, EffectEnvelope, EffectFinished, EffectOrigin, EffectPaused, EffectRegion,
+            EffectRegionBuilder, GridRect, RunningEffect, SpawnEffectExt, TargetTerminal,
+        },
+        grid::{
+            BackgroundSprite, BaseTransform, CellEntityIndex, CellOpacity, CellStyle,
+            ColorOverride, ForegroundSprite, FrameBackground, TerminalCell, TerminalView,
+        },
+        grid_lines::ShowGridLines,
+        render_target::TerminalRenderTarget,
+        selection::{LineHighlight, Selection, SelectionMode},
+        sync::SyncStats,
+    };
+
+    #[cfg(feature = "clipboard")]
+    pub use crate::selection::copy_selection;
+    #[cfg(feature = "crt")]
+    pub use crate::crt::CrtPostProcess;
+    #[cfg(feature = "debug")]
+    pub use crate::debug::AtlasDebugOverlay;
+    #[cfg(feature = "gamepad")]
+    pub use crate::gamepad::GamepadMap;
+    #[cfg(feature = "ui")]
+    pub use crate::ui::TerminalUiNode;
 }
 
 /// Configuration for the terminal grid.
@@ -94,12 +210,157 @@ pub struct TerminalConfig<T: 'static + Send + Sync> {
     pub origin_override: Option<Vec2>,
     /// Z depth for cell entities (default: 0.0).
     pub z_layer: f32,
-    /// Whether this terminal receives keyboard input (default: true).
+    /// Depth of each foreground glyph above its cell background (default: 0.1).
+    ///
+    /// Resulting world depths are: background at `z_layer`, glyph at
+    /// `z_layer + fg_z_offset`. Per-cell decorations such as underlines or a
+    /// cursor should sit between the two (under glyphs) or just above the
+    /// glyph; other terminals or game sprites interleave cleanly as long as
+    /// they avoid that range.
+    pub fg_z_offset: f32,
+    /// Shift of each glyph within its cell in pixels, +y up (default: zero).
+    /// Use it to re-center fonts whose text sits visibly high or low. Only
+    /// moves glyph sprites; grid spacing and backgrounds are unaffected.
+    pub glyph_offset: Vec2,
+    /// Vertical placement of glyphs in their cell, before `glyph_offset`
+    /// (default: `GlyphAlign::Baseline`). `GlyphAlign::CapCenter` centers
+    /// them from the font's metrics instead of by hand.
+    pub glyph_align: GlyphAlign,
+    /// Whether this terminal receives keyboard input (default: true). With
+    /// several terminals, `FocusedTerminal` picks which one gets it.
     pub receive_input: bool,
+    /// Also queue key releases, as `KeyEventKind::Release` events
+    /// (default: false).
+    pub forward_releases: bool,
+    /// What to do with OS key auto-repeat (default: `RepeatMode::Forward`).
+    pub repeat_mode: RepeatMode,
+    /// Most events `TerminalInputQueue<T>` holds before `input_overflow`
+    /// kicks in (default: 1024), so an app that stops reading input doesn't
+    /// pile up memory and a flood of stale keys.
+    pub max_queued_events: usize,
+    /// What happens to events past `max_queued_events` (default:
+    /// `InputOverflow::DropOldest`).
+    pub input_overflow: InputOverflow,
     /// Override cell dimensions instead of deriving from font metrics.
     /// When set, `TerminalLayout` uses these exact values (no ceil rounding).
     /// The atlas is still rasterized at `font_size` — this only affects grid spacing.
+    ///
+    /// For pixel-art scaling, rasterize at a small `font_size`, set this to a
+    /// larger size and use `AtlasFilter::Nearest`: the override is then snapped
+    /// to the nearest whole multiple of the rasterized cell so glyphs stay crisp.
     pub cell_size_override: Option<Vec2>,
+    /// Texture filtering for the glyph atlas (default: `AtlasFilter::Linear`).
+    pub atlas_filter: AtlasFilter,
+    /// Empty space between glyph tiles in the atlas (default:
+    /// `AtlasPadding::Auto`). Smaller padding saves texture memory at large
+    /// font sizes; glyph parts that overflow it are clipped.
+    pub atlas_padding: AtlasPadding,
+    /// Stretch box-drawing and block-element glyphs so strokes that end near
+    /// a cell edge reach it exactly, closing seams between neighboring cells
+    /// (default: false). Alters glyph shapes, so it is opt-in.
+    pub snap_box_drawing: bool,
+    /// Draw block elements (`█▀▄▌▐░▒▓`, eighth blocks, quadrants) as exact
+    /// filled rectangles instead of using the font's glyphs (default: false).
+    /// Fills are gapless and shades are uniform, even if the font lacks them.
+    pub solid_block_elements: bool,
+    /// Keep the font's own colors for color emoji instead of tinting them
+    /// with the cell's foreground (default: false). Needs embedded color
+    /// bitmaps (CBDT/CBLC or sbix, as in Noto Color Emoji); COLR/CPAL and SVG
+    /// glyphs still draw as tinted outlines. Color glyphs are scaled down to
+    /// fit one cell.
+    pub color_glyphs: bool,
+    /// How glyphs of a proportional font are fit into the fixed-width cells
+    /// (default: `MonospacePolicy::ForceCellWidth`). A warning is logged when
+    /// a custom font isn't monospace and this is left at the default.
+    pub monospace_policy: MonospacePolicy,
+    /// Skip sync and effects while the grid is outside every active camera's
+    /// view (default: false). Leave disabled when compositing the terminal
+    /// into a render target that no on-screen camera looks at directly.
+    pub cull_offscreen: bool,
+    /// Hide glyph sprites of blank cells, and whole cells that are blank on
+    /// the default background, to cut draw calls for sparse layouts
+    /// (default: false). Cells reappear as soon as they get content.
+    pub hide_blank_cells: bool,
+    /// Maximum number of glyphs kept in the font atlas. Once reached, new
+    /// glyphs evict the least-recently-used ones (default: None = unbounded).
+    pub max_glyphs: Option<usize>,
+    /// Largest width or height of the atlas texture, in texels (default:
+    /// 8192). Keep this at or below the GPU's max texture size; glyphs that
+    /// don't fit replace least-recently-used ones like `max_glyphs`.
+    pub max_atlas_dimension: u32,
+    /// Characters rasterized into the atlas at startup on top of printable
+    /// ASCII (default: `CharSet::Ascii`, nothing more). `CharSet::Latin1`
+    /// saves European-language apps a rebuild on their first accented
+    /// letter or curly quote. Chars the font lacks are skipped.
+    pub initial_charset: CharSet,
+    /// Extra characters rasterized into the atlas at startup alongside
+    /// `initial_charset`, so scripts the app is known to show appear without
+    /// a first-use rebuild (default: empty). Chars the font lacks are skipped.
+    pub preload_chars: Vec<char>,
+    /// Directory to cache the startup atlas in (default: None = no cache).
+    /// Entries are keyed by the font bytes, size and glyph options, so a
+    /// changed font or size just writes a new file. Only the 8 most recently
+    /// used files are kept. Needs a filesystem, so leave unset on the web.
+    pub atlas_cache_dir: Option<std::path::PathBuf>,
+    /// Draw from one atlas texture shared with other terminals that set this
+    /// and use the same font, size and glyph options (default: false).
+    /// Saves a texture per extra terminal in multi-terminal apps.
+    pub share_atlas: bool,
+    /// Draw the terminal on this `RenderLayers` layer and render it offscreen
+    /// into a `TerminalRenderTarget<T>` image (default: None = draw directly).
+    pub render_layer: Option<u8>,
+    /// Rows kept after they scroll off the top of the screen, viewable via
+    /// `ScrollOffset<T>` (default: 0 = no scrollback). A fullscreen ratatui
+    /// draw never scrolls, so history is filled with
+    /// `TerminalResource::push_lines`.
+    pub scrollback_lines: usize,
+    /// Columns between tab stops when text with `\t` goes through
+    /// `BevyBackend::print` (default: 8; 0 writes tabs as-is). ratatui widgets
+    /// expand tabs themselves. Read when the terminal is created; change it
+    /// later with `BevyBackend::set_tab_width`.
+    pub tab_width: u8,
+    /// Process dirty cells as contiguous per-row spans instead of one index
+    /// at a time (default: false). Helps full-screen redraws.
+    pub coalesce_dirty: bool,
+    /// Give cells whose ratatui background is `Reset` a fully transparent
+    /// background instead of `default_bg`, so the scene shows through between
+    /// glyphs (default: false). Explicit background colors stay opaque.
+    pub transparent_background: bool,
+    /// Advance effects in fixed steps of this many seconds instead of by the
+    /// frame time, so they animate identically at any frame rate (default:
+    /// None). Sets `EffectClock::fixed_step`.
+    pub effect_fixed_step: Option<f32>,
+    /// Sync the buffer to the grid at most once per this interval instead
+    /// of every frame (default: None). Effects still animate every frame.
+    /// Gate draw systems with `throttle::terminal_update_due`.
+    pub update_interval: Option<Duration>,
+    /// Shape of the cursor drawn where ratatui places it (default:
+    /// `CursorStyle::Block`).
+    pub cursor_style: CursorStyle,
+    /// Cursor color (default: None = `default_fg` at half opacity, so the
+    /// glyph under a block cursor stays readable). Unused by
+    /// `CursorStyle::Invert`.
+    pub cursor_color: Option<Color>,
+    /// Blink the cursor this many times per second (default: None = solid).
+    /// It stays solid for a moment after each move, like most terminals.
+    pub cursor_blink_hz: Option<f32>,
+    /// How glyphs with `Modifier::DIM` are drawn (default: `DimMode::Alpha`).
+    pub dim_mode: DimMode,
+    /// How much of a dim glyph's alpha, or brightness with `DimMode::Darken`,
+    /// is kept (default: 0.5).
+    pub dim_factor: f32,
+    /// Draw `Modifier::BOLD` glyphs in a brighter color, as the font has no
+    /// bold face (default: false). The eight base colors switch to their
+    /// light variants; other colors are blended toward white.
+    pub bold_brightens: bool,
+    /// Panel drawn behind the whole grid as (color, margin in pixels), to
+    /// frame the terminal like a bezel (default: None). It follows resizes
+    /// and font changes, and sits `fg_z_offset` below the cell backgrounds.
+    pub frame_background: Option<(Color, f32)>,
+    /// Slant `Modifier::ITALIC` glyphs by this shear angle in radians, as
+    /// the font has no italic face (default: None = draw them upright).
+    /// Around 0.2 reads as italic; the slant reaches slightly past the cell.
+    pub faux_italic: Option<f32>,
     #[doc(hidden)]
     pub _marker: PhantomData<T>,
 }
@@ -115,13 +376,207 @@ impl<T: 'static + Send + Sync> Default for TerminalConfig<T> {
             default_bg: Color::srgb(0.1, 0.1, 0.1),
             origin_override: None,
             z_layer: 0.0,
+            fg_z_offset: 0.1,
+            glyph_offset: Vec2::ZERO,
+            glyph_align: GlyphAlign::Baseline,
             receive_input: true,
+            forward_releases: false,
+            repeat_mode: RepeatMode::Forward,
+            max_queued_events: 1024,
+            input_overflow: InputOverflow::DropOldest,
             cell_size_override: None,
+            atlas_filter: AtlasFilter::Linear,
+            atlas_padding: AtlasPadding::Auto,
+            snap_box_drawing: false,
+            solid_block_elements: false,
+            color_glyphs: false,
+            monospace_policy: MonospacePolicy::ForceCellWidth,
+            cull_offscreen: false,
+            hide_blank_cells: false,
+            max_glyphs: None,
+            max_atlas_dimension: 8192,
+            initial_charset: CharSet::Ascii,
+            preload_chars: Vec::new(),
+            atlas_cache_dir: None,
+            share_atlas: false,
+            render_layer: None,
+            scrollback_lines: 0,
+            tab_width: 8,
+            coalesce_dirty: false,
+            transparent_background: false,
+            effect_fixed_step: None,
+            update_interval: None,
+            cursor_style: CursorStyle::Block,
+            cursor_color: None,
+            cursor_blink_hz: None,
+            dim_mode: DimMode::Alpha,
+            dim_factor: 0.5,
+            bold_brightens: false,
+            frame_background: None,
+            faux_italic: None,
             _marker: PhantomData,
         }
     }
 }
 
+impl<T: 'static + Send + Sync> TerminalConfig<T> {
+    /// Check the config for values the terminal can't work with. The plugin
+    /// runs this on build, logs each problem and falls back to the default
+    /// for the offending field rather than panicking.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.columns == 0 || self.rows == 0 {
+            return Err(ConfigError::ZeroSize {
+                columns: self.columns,
+                rows: self.rows,
+            });
+        }
+        if !(self.font_size.is_finite() && self.font_size > 0.0) {
+            return Err(ConfigError::InvalidFontSize(self.font_size));
+        }
+        if let FontSource::Custom(bytes) = &self.font {
+            if ab_glyph::FontRef::try_from_slice(bytes).is_err() {
+                return Err(ConfigError::InvalidFont);
+            }
+        }
+        Ok(())
+    }
+
+    /// Replace every invalid field with its default, logging why.
+    fn repaired(mut self) -> Self {
+        let defaults = Self::default();
+        while let Err(err) = self.validate() {
+            error!("Invalid terminal config: {err}; using the default instead");
+            match err {
+                ConfigError::ZeroSize { .. } => {
+                    if self.columns == 0 {
+                        self.columns = defaults.columns;
+                    }
+                    if self.rows == 0 {
+                        self.rows = defaults.rows;
+                    }
+                }
+                ConfigError::InvalidFontSize(_) => self.font_size = defaults.font_size,
+                ConfigError::InvalidFont => self.font = FontSource::Default,
+            }
+        }
+        self
+    }
+
+    /// Background color for cells whose ratatui background is `Reset`.
+    pub fn reset_bg(&self) -> Color {
+        if self.transparent_background {
+            self.default_bg.with_alpha(0.0)
+        } else {
+            self.default_bg
+        }
+    }
+
+    /// Color a glyph of color `fg` is drawn in, after its modifiers.
+    pub fn glyph_color(&self, fg: Color, dim: bool) -> Color {
+        if dim {
+            self.dim_mode.apply(fg, self.dim_factor)
+        } else {
+            fg
+        }
+    }
+
+    /// Sprite tint for a glyph of color `fg`. Color glyphs (see
+    /// `color_glyphs`) keep their own colors, so they're tinted white and
+    /// only take `fg`'s alpha and the dim modifier.
+    pub fn glyph_tint(&self, fg: Color, dim: bool, color_glyph: bool) -> Color {
+        let fg = if color_glyph {
+            Color::WHITE.with_alpha(fg.alpha())
+        } else {
+            fg
+        };
+        self.glyph_color(fg, dim)
+    }
+}
+
+/// A problem found by `TerminalConfig::validate`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    /// The grid has no cells to draw into.
+    ZeroSize { columns: u16, rows: u16 },
+    /// `font_size` is zero, negative or not a number.
+    InvalidFontSize(f32),
+    /// `FontSource::Custom` bytes don't parse as a font.
+    InvalidFont,
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ZeroSize { columns, rows } => {
+                write!(f, "terminal size {columns}x{rows} has no cells")
+            }
+            Self::InvalidFontSize(size) => write!(f, "font size {size} is not positive"),
+            Self::InvalidFont => f.write_str("custom font data could not be parsed"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Logical grid position of a cell.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GridPosition {
+    pub col: u16,
+    pub row: u16,
+}
+
+/// Offsets to the orthogonal neighbors, in reading order.
+const NEIGHBORS4: [(i32, i32); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Offsets to the orthogonal and diagonal neighbors, in reading order.
+const NEIGHBORS8: [(i32, i32); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+impl GridPosition {
+    pub const fn new(col: u16, row: u16) -> Self {
+        Self { col, row }
+    }
+
+    /// The position `dcol` columns and `drow` rows away, if it lies inside
+    /// a `columns` x `rows` grid.
+    pub fn offset(self, dcol: i32, drow: i32, columns: u16, rows: u16) -> Option<GridPosition> {
+        let col = u16::try_from(self.col as i32 + dcol).ok()?;
+        let row = u16::try_from(self.row as i32 + drow).ok()?;
+        (col < columns && row < rows).then_some(GridPosition { col, row })
+    }
+
+    /// The up to 4 cells sharing an edge with this one inside a `columns` x
+    /// `rows` grid, in reading order.
+    pub fn neighbors4(self, columns: u16, rows: u16) -> impl Iterator<Item = GridPosition> {
+        self.neighbors_at(&NEIGHBORS4, columns, rows)
+    }
+
+    /// The up to 8 cells touching this one, corners included, inside a
+    /// `columns` x `rows` grid, in reading order.
+    pub fn neighbors8(self, columns: u16, rows: u16) -> impl Iterator<Item = GridPosition> {
+        self.neighbors_at(&NEIGHBORS8, columns, rows)
+    }
+
+    fn neighbors_at(
+        self,
+        offsets: &'static [(i32, i32)],
+        columns: u16,
+        rows: u16,
+    ) -> impl Iterator<Item = GridPosition> {
+        offsets
+            .iter()
+            .filter_map(move |&(dcol, drow)| self.offset(dcol, drow, columns, rows))
+    }
+}
+
 /// Derived layout properties computed from font metrics and terminal dimensions.
 /// Created automatically by the plugin — do not construct manually.
 #[derive(Resource, Clone, Debug)]
@@ -132,6 +587,10 @@ pub struct TerminalLayout<T: 'static + Send + Sync> {
     pub cell_height: f32,
     /// World-space origin (top-left corner of the grid), centered on screen.
     pub origin: Vec2,
+    /// Grid size in cells, used to bound coordinate conversions. Follows
+    /// `TerminalConfig` columns and rows when the grid is resized.
+    pub columns: u16,
+    pub rows: u16,
     #[doc(hidden)]
     pub _marker: PhantomData<T>,
 }
@@ -143,6 +602,27 @@ impl<T: 'static + Send + Sync> TerminalLayout<T> {
         Vec2::new(self.cell_width + 0.5, self.cell_height + 0.5)
     }
 
+    /// Width of the whole grid in pixels.
+    pub fn pixel_width(&self) -> f32 {
+        self.columns as f32 * self.cell_width
+    }
+
+    /// Height of the whole grid in pixels.
+    pub fn pixel_height(&self) -> f32 {
+        self.rows as f32 * self.cell_height
+    }
+
+    /// World-space rect the grid covers, from `origin` at its top-left
+    /// corner to its bottom-right corner.
+    pub fn bounds(&self) -> Rect {
+        Rect::new(
+            self.origin.x,
+            self.origin.y - self.pixel_height(),
+            self.origin.x + self.pixel_width(),
+            self.origin.y,
+        )
+    }
+
     /// Compute layout from config using font metrics.
     ///
     /// Cell dimensions are ceil'd to integer pixels so that foreground sprites
@@ -151,8 +631,11 @@ impl<T: 'static + Send + Sync> TerminalLayout<T> {
     pub fn from_config(config: &TerminalConfig<T>) -> Self {
         let (cell_width, cell_height) = if let Some(override_size) = config.cell_size_override {
             (override_size.x, override_size.y)
+        } else if let FontSource::Bitmap(bitmap) = &config.font {
+            let size = bitmap.cell_size(config.font_size);
+            (size.x as f32, size.y as f32)
         } else {
-            let (cw, ch) = atlas::compute_cell_size(config.font.bytes(), config.font_size);
+            let (cw, ch) = glyph::compute_cell_size(config.font.bytes(), config.font_size);
             (cw.ceil(), ch.ceil())
         };
         let origin = config.origin_override.unwrap_or_else(|| {
@@ -165,9 +648,46 @@ impl<T: 'static + Send + Sync> TerminalLayout<T> {
             cell_width,
             cell_height,
             origin,
+            columns: config.columns,
+            rows: config.rows,
             _marker: PhantomData,
         }
     }
+
+    /// The grid cell containing world-space point `world`, or `None` if it
+    /// falls outside the grid.
+    pub fn world_to_grid(&self, world: Vec2) -> Option<GridPosition> {
+        let local = Vec2::new(world.x - self.origin.x, self.origin.y - world.y);
+        let col = (local.x / self.cell_width).floor();
+        let row = (local.y / self.cell_height).floor();
+        if col < 0.0 || row < 0.0 || col >= self.columns as f32 || row >= self.rows as f32 {
+            return None;
+        }
+        Some(GridPosition {
+            col: col as u16,
+            row: row as u16,
+        })
+    }
+
+    /// Fractional grid coords of world-space point `world`, with cell
+    /// centers at whole numbers. Unlike `world_to_grid` this is not clamped,
+    /// so points off the grid give coords outside it.
+    pub fn world_to_grid_coords(&self, world: Vec2) -> Vec2 {
+        Vec2::new(
+            (world.x - self.origin.x) / self.cell_width - 0.5,
+            (self.origin.y - world.y) / self.cell_height - 0.5,
+        )
+    }
+
+    /// World-space center of the cell at (col, row), clamped to the grid.
+    pub fn grid_to_world(&self, col: u16, row: u16) -> Vec2 {
+        let col = col.min(self.columns.saturating_sub(1));
+        let row = row.min(self.rows.saturating_sub(1));
+        Vec2::new(
+            self.origin.x + (col as f32 + 0.5) * self.cell_width,
+            self.origin.y - (row as f32 + 0.5) * self.cell_height,
+        )
+    }
 }
 
 /// Shared resource wrapping the ratatui Terminal<BevyBackend> in an Arc<Mutex<>>
@@ -182,22 +702,180 @@ impl<T: 'static + Send + Sync> TerminalResource<T> {
     pub fn new(terminal: ratatui::Terminal<BevyBackend>) -> Self {
         Self(Arc::new(Mutex::new(terminal)), PhantomData)
     }
+
+    /// Lock the terminal. Systems can `?` the error to report it through
+    /// Bevy's error handler.
+    pub fn lock(
+        &self,
+    ) -> Result<std::sync::MutexGuard<'_, ratatui::Terminal<BevyBackend>>, TerminalPoisoned> {
+        self.0.lock().map_err(|_| TerminalPoisoned)
+    }
+
+    /// Lock the terminal if no one else holds it, e.g. a `TerminalDriver`
+    /// mid-draw on another thread. `Ok(None)` means it is busy.
+    pub fn try_lock(
+        &self,
+    ) -> Result<Option<std::sync::MutexGuard<'_, ratatui::Terminal<BevyBackend>>>, TerminalPoisoned>
+    {
+        match self.0.try_lock() {
+            Ok(guard) => Ok(Some(guard)),
+            Err(std::sync::TryLockError::WouldBlock) => Ok(None),
+            Err(std::sync::TryLockError::Poisoned(_)) => Err(TerminalPoisoned),
+        }
+    }
+
+    /// A handle for drawing into this terminal from another thread.
+    pub fn driver(&self) -> TerminalDriver<T> {
+        TerminalDriver {
+            terminal: self.0.clone(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Lock the terminal and draw a frame with `f`.
+    ///
+    /// Don't call this (or `with_backend`) from inside `f`: the lock is not
+    /// reentrant.
+    pub fn draw(&self, f: impl FnOnce(&mut ratatui::Frame)) -> Result<(), TerminalPoisoned> {
+        let mut terminal = self.lock()?;
+        // BevyBackend's error type is Infallible
+        let Ok(_) = terminal.draw(f);
+        Ok(())
+    }
+
+    /// Lock the terminal and append `lines` to its scrollback history, e.g.
+    /// log output. See `BevyBackend::push_lines`.
+    pub fn push_lines<'a>(
+        &self,
+        lines: impl IntoIterator<Item = ratatui::text::Line<'a>>,
+    ) -> Result<(), TerminalPoisoned> {
+        self.with_backend(|backend| backend.push_lines(lines))
+    }
+
+    /// Lock the terminal and run `f` on its backend, returning its result.
+    pub fn with_backend<R>(
+        &self,
+        f: impl FnOnce(&mut BevyBackend) -> R,
+    ) -> Result<R, TerminalPoisoned> {
+        let mut terminal = self.lock()?;
+        Ok(f(terminal.backend_mut()))
+    }
+}
+
+/// Returned by `TerminalResource` helpers when a previous holder of the
+/// terminal lock panicked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TerminalPoisoned;
+
+impl std::fmt::Display for TerminalPoisoned {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("terminal mutex poisoned by a panic while it was locked")
+    }
+}
+
+impl std::error::Error for TerminalPoisoned {}
+
+/// Handle for drawing into terminal `T` off the Bevy schedule, e.g. from a
+/// thread that tails a log or builds a large table. Get one from
+/// `TerminalResource::driver`, clone it freely and move it to any thread.
+///
+/// A draw flushes into the backend and bumps its generation, and the next
+/// `TerminalSet::Sync` picks the changes up. Sync only ever `try_lock`s the
+/// terminal, so a driver mid-draw delays the update by a frame instead of
+/// stalling rendering.
+///
+/// Every call holds the terminal lock for its whole closure, which blocks
+/// `TerminalResource::draw` in `AppTick` and puts resizes and atlas updates
+/// off to a later frame. Prepare content before calling `draw` and keep the
+/// closure to rendering it. Never call the driver from inside a `TerminalResource`
+/// closure or another driver call on the same thread: the lock isn't
+/// reentrant and that deadlocks.
+pub struct TerminalDriver<T: 'static + Send + Sync> {
+    terminal: Arc<Mutex<ratatui::Terminal<BevyBackend>>>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: 'static + Send + Sync> Clone for TerminalDriver<T> {
+    fn clone(&self) -> Self {
+        Self {
+            terminal: self.terminal.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: 'static + Send + Sync> TerminalDriver<T> {
+    /// Lock the terminal and draw a frame with `f`, like
+    /// `TerminalResource::draw`.
+    pub fn draw(&self, f: impl FnOnce(&mut ratatui::Frame)) -> Result<(), TerminalPoisoned> {
+        let mut terminal = self.terminal.lock().map_err(|_| TerminalPoisoned)?;
+        // BevyBackend's error type is Infallible
+        let Ok(_) = terminal.draw(f);
+        Ok(())
+    }
+
+    /// Lock the terminal and append `lines` to its scrollback history, like
+    /// `TerminalResource::push_lines`.
+    pub fn push_lines<'a>(
+        &self,
+        lines: impl IntoIterator<Item = ratatui::text::Line<'a>>,
+    ) -> Result<(), TerminalPoisoned> {
+        self.with_backend(|backend| backend.push_lines(lines))
+    }
+
+    /// Lock the terminal and run `f` on its backend, returning its result.
+    pub fn with_backend<R>(
+        &self,
+        f: impl FnOnce(&mut BevyBackend) -> R,
+    ) -> Result<R, TerminalPoisoned> {
+        let mut terminal = self.terminal.lock().map_err(|_| TerminalPoisoned)?;
+        Ok(f(terminal.backend_mut()))
+    }
 }
 
 /// System sets for ordering terminal systems.
 ///
 /// Usage: add custom systems to `TerminalSet::AppTick` for your ratatui draw logic,
-/// or to `TerminalSet::Effects` for custom visual effects.
+/// to `TerminalSet::GeometryEffects` for effects that move cells, or to
+/// `TerminalSet::ColorEffects` for effects that recolor them.
+///
+/// Each frame runs AppTick → Sync → ResetTransforms → Effects. Sync finishes
+/// writing every `CellStyle` and sprite color before any effect runs, and
+/// `ResetColors` repaints from those styles, so effects always work on the
+/// frame's new content: a redraw during an animation shows up with the effect
+/// applied on that same frame, never as one frame of raw or stale color.
+/// Draws made outside `AppTick` still land, but a frame late.
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum TerminalSet {
     /// User's ratatui draw + input handling runs here.
     AppTick,
-    /// Buffer → entity sync.
+    /// Buffer → entity sync. Writes cell styles, glyphs and sprite colors
+    /// for the cells that changed.
     Sync,
     /// Reset transforms to base positions.
     ResetTransforms,
-    /// All effects run here. Add custom effect systems to this set.
+    /// Contains `GeometryEffects`, `ResetColors` and `ColorEffects`, in that
+    /// order. Prefer adding custom effects to one of those sub-sets; systems
+    /// added to `Effects` directly have no order relative to them.
+    ///
+    /// Built-in effects run one at a time in a fixed order, so overlapping
+    /// effects always combine the same way. To run a custom effect at a
+    /// specific point, order it against a built-in system, e.g.
+    /// `.after(effects::wave::wave_system::<T>)`.
     Effects,
+    /// Effects that move, rotate or scale cells (`Wave`, `Ripple`, ...).
+    /// Runs after `ResetTransforms`.
+    GeometryEffects,
+    /// Reset sprite colors to their cell styles. Runs after geometry so color
+    /// effects see final cell positions.
+    ResetColors,
+    /// Effects that change sprite colors (`Rainbow`, `Shiny`, `Fade`, ...).
+    /// Runs after `ResetColors`. On frames `ColorFrameCache` finds idle,
+    /// neither the reset nor the built-in effects run and sprites keep last
+    /// frame's colors. Custom systems here that recolor cells without an
+    /// effect entity should set colors outright or call
+    /// `ColorFrameCache::invalidate`.
+    ColorEffects,
 }
 
 /// The main plugin that sets up the terminal emulator.
@@ -214,19 +892,314 @@ impl<T: 'static + Send + Sync> Default for TerminalEmuPlugin<T> {
     }
 }
 
+impl<T: 'static + Send + Sync> TerminalEmuPlugin<T> {
+    /// Plugin with the default config, to be customized with the setters below:
+    /// `TerminalEmuPlugin::<T>::new().columns(120).rows(40).font_size(18.0)`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of columns.
+    pub fn columns(mut self, columns: u16) -> Self {
+        self.config.columns = columns;
+        self
+    }
+
+    /// Number of rows.
+    pub fn rows(mut self, rows: u16) -> Self {
+        self.config.rows = rows;
+        self
+    }
+
+    /// Font size for glyph rasterization.
+    pub fn font_size(mut self, font_size: f32) -> Self {
+        self.config.font_size = font_size;
+        self
+    }
+
+    /// Font to rasterize glyphs from.
+    pub fn font(mut self, font: FontSource) -> Self {
+        self.config.font = font;
+        self
+    }
+
+    /// Default foreground color.
+    pub fn default_fg(mut self, default_fg: Color) -> Self {
+        self.config.default_fg = default_fg;
+        self
+    }
+
+    /// Default background color.
+    pub fn default_bg(mut self, default_bg: Color) -> Self {
+        self.config.default_bg = default_bg;
+        self
+    }
+
+    /// Top-left corner of the grid in world space, instead of centering it.
+    pub fn origin(mut self, origin: Vec2) -> Self {
+        self.config.origin_override = Some(origin);
+        self
+    }
+
+    /// Z depth for cell entities.
+    pub fn z_layer(mut self, z_layer: f32) -> Self {
+        self.config.z_layer = z_layer;
+        self
+    }
+
+    /// Depth of glyphs above their cell background.
+    pub fn fg_z_offset(mut self, fg_z_offset: f32) -> Self {
+        self.config.fg_z_offset = fg_z_offset;
+        self
+    }
+
+    /// Shift of each glyph within its cell, in pixels.
+    pub fn glyph_offset(mut self, glyph_offset: Vec2) -> Self {
+        self.config.glyph_offset = glyph_offset;
+        self
+    }
+
+    /// Vertical placement of glyphs in their cell.
+    pub fn glyph_align(mut self, glyph_align: GlyphAlign) -> Self {
+        self.config.glyph_align = glyph_align;
+        self
+    }
+
+    /// Whether this terminal receives keyboard input.
+    pub fn receive_input(mut self, receive_input: bool) -> Self {
+        self.config.receive_input = receive_input;
+        self
+    }
+
+    /// Also queue key releases.
+    pub fn forward_releases(mut self, forward_releases: bool) -> Self {
+        self.config.forward_releases = forward_releases;
+        self
+    }
+
+    /// What to do with key auto-repeat.
+    pub fn repeat_mode(mut self, repeat_mode: RepeatMode) -> Self {
+        self.config.repeat_mode = repeat_mode;
+        self
+    }
+
+    /// Cap on queued input events.
+    pub fn max_queued_events(mut self, max_queued_events: usize) -> Self {
+        self.config.max_queued_events = max_queued_events;
+        self
+    }
+
+    /// What to do with input events past `max_queued_events`.
+    pub fn input_overflow(mut self, input_overflow: InputOverflow) -> Self {
+        self.config.input_overflow = input_overflow;
+        self
+    }
+
+    /// Cell size in pixels, instead of deriving it from the font.
+    pub fn cell_size(mut self, cell_size: Vec2) -> Self {
+        self.config.cell_size_override = Some(cell_size);
+        self
+    }
+
+    /// Texture filtering for the glyph atlas.
+    pub fn atlas_filter(mut self, atlas_filter: AtlasFilter) -> Self {
+        self.config.atlas_filter = atlas_filter;
+        self
+    }
+
+    /// Space between glyph tiles in the atlas.
+    pub fn atlas_padding(mut self, atlas_padding: AtlasPadding) -> Self {
+        self.config.atlas_padding = atlas_padding;
+        self
+    }
+
+    /// Stretch box-drawing strokes to the cell edges.
+    pub fn snap_box_drawing(mut self, snap_box_drawing: bool) -> Self {
+        self.config.snap_box_drawing = snap_box_drawing;
+        self
+    }
+
+    /// Draw block elements as exact rectangles.
+    pub fn solid_block_elements(mut self, solid_block_elements: bool) -> Self {
+        self.config.solid_block_elements = solid_block_elements;
+        self
+    }
+
+    /// Keep the colors of color emoji.
+    pub fn color_glyphs(mut self, color_glyphs: bool) -> Self {
+        self.config.color_glyphs = color_glyphs;
+        self
+    }
+
+    /// How proportional glyphs are fit into cells.
+    pub fn monospace_policy(mut self, monospace_policy: MonospacePolicy) -> Self {
+        self.config.monospace_policy = monospace_policy;
+        self
+    }
+
+    /// Skip sync and effects while off screen.
+    pub fn cull_offscreen(mut self, cull_offscreen: bool) -> Self {
+        self.config.cull_offscreen = cull_offscreen;
+        self
+    }
+
+    /// Skip drawing blank cells.
+    pub fn hide_blank_cells(mut self, hide_blank_cells: bool) -> Self {
+        self.config.hide_blank_cells = hide_blank_cells;
+        self
+    }
+
+    /// Cap the atlas at this many glyphs, evicting the least recently used.
+    pub fn max_glyphs(mut self, max_glyphs: usize) -> Self {
+        self.config.max_glyphs = Some(max_glyphs);
+        self
+    }
+
+    /// Largest atlas texture width or height, in texels.
+    pub fn max_atlas_dimension(mut self, max_atlas_dimension: u32) -> Self {
+        self.config.max_atlas_dimension = max_atlas_dimension;
+        self
+    }
+
+    /// Set the character set rasterized at startup.
+    pub fn initial_charset(mut self, initial_charset: CharSet) -> Self {
+        self.config.initial_charset = initial_charset;
+        self
+    }
+
+    /// Characters to rasterize at startup, e.g. `'А'..='я'` for Cyrillic.
+    pub fn preload_chars(mut self, chars: impl IntoIterator<Item = char>) -> Self {
+        self.config.preload_chars = chars.into_iter().collect();
+        self
+    }
+
+    /// Cache the startup atlas in this directory.
+    pub fn atlas_cache_dir(mut self, dir: impl Into<std::path::PathBuf>) -> Self {
+        self.config.atlas_cache_dir = Some(dir.into());
+        self
+    }
+
+    /// Share the atlas texture with matching terminals.
+    pub fn share_atlas(mut self, share_atlas: bool) -> Self {
+        self.config.share_atlas = share_atlas;
+        self
+    }
+
+    /// Render offscreen on this layer into a `TerminalRenderTarget<T>`.
+    pub fn render_layer(mut self, render_layer: u8) -> Self {
+        self.config.render_layer = Some(render_layer);
+        self
+    }
+
+    /// Rows kept after they scroll off the top.
+    pub fn scrollback_lines(mut self, scrollback_lines: usize) -> Self {
+        self.config.scrollback_lines = scrollback_lines;
+        self
+    }
+
+    /// Columns between tab stops for `BevyBackend::print`.
+    pub fn tab_width(mut self, tab_width: u8) -> Self {
+        self.config.tab_width = tab_width;
+        self
+    }
+
+    /// Sync dirty cells as per-row spans.
+    pub fn coalesce_dirty(mut self, coalesce_dirty: bool) -> Self {
+        self.config.coalesce_dirty = coalesce_dirty;
+        self
+    }
+
+    /// Make `Reset` backgrounds transparent.
+    pub fn transparent_background(mut self, transparent_background: bool) -> Self {
+        self.config.transparent_background = transparent_background;
+        self
+    }
+
+    /// Advance effects in fixed steps of this many seconds.
+    pub fn effect_fixed_step(mut self, effect_fixed_step: f32) -> Self {
+        self.config.effect_fixed_step = Some(effect_fixed_step);
+        self
+    }
+
+    /// Sync the terminal at most once per `update_interval`.
+    pub fn update_interval(mut self, update_interval: Duration) -> Self {
+        self.config.update_interval = Some(update_interval);
+        self
+    }
+
+    /// Set the shape of the cursor.
+    pub fn cursor_style(mut self, cursor_style: CursorStyle) -> Self {
+        self.config.cursor_style = cursor_style;
+        self
+    }
+
+    /// Set the cursor color.
+    pub fn cursor_color(mut self, cursor_color: Color) -> Self {
+        self.config.cursor_color = Some(cursor_color);
+        self
+    }
+
+    /// Blink the cursor this many times per second.
+    pub fn cursor_blink_hz(mut self, cursor_blink_hz: f32) -> Self {
+        self.config.cursor_blink_hz = Some(cursor_blink_hz);
+        self
+    }
+
+    /// Set how dim glyphs are drawn.
+    pub fn dim_mode(mut self, dim_mode: DimMode) -> Self {
+        self.config.dim_mode = dim_mode;
+        self
+    }
+
+    /// Set how much of a dim glyph's alpha or brightness is kept.
+    pub fn dim_factor(mut self, dim_factor: f32) -> Self {
+        self.config.dim_factor = dim_factor;
+        self
+    }
+
+    /// Draw bold glyphs in a brighter color.
+    pub fn bold_brightens(mut self, bold_brightens: bool) -> Self {
+        self.config.bold_brightens = bold_brightens;
+        self
+    }
+
+    /// Draw a `color` panel behind the grid, `margin` pixels past each edge.
+    pub fn frame_background(mut self, color: Color, margin: f32) -> Self {
+        self.config.frame_background = Some((color, margin));
+        self
+    }
+
+    /// Slant italic glyphs by `angle` radians, as the font has no italic face.
+    pub fn faux_italic(mut self, angle: f32) -> Self {
+        self.config.faux_italic = Some(angle);
+        self
+    }
+}
+
 impl<T: 'static + Send + Sync> Plugin for TerminalEmuPlugin<T> {
     fn build(&self, app: &mut App) {
-        let config = clone_config(&self.config);
+        let config = clone_config(&self.config).repaired();
         let layout = TerminalLayout::from_config(&config);
-        let backend = BevyBackend::new(config.columns, config.rows);
-        let terminal = ratatui::Terminal::new(backend).expect("Failed to create ratatui terminal");
+        let backend = BevyBackend::with_cell_size(
+            config.columns,
+            config.rows,
+            layout.cell_width.round() as u16,
+            layout.cell_height.round() as u16,
+        )
+        .with_scrollback(config.scrollback_lines)
+        .with_tab_width(config.tab_width);
+        // BevyBackend's error type is Infallible
+        let Ok(terminal) = ratatui::Terminal::new(backend);
         let terminal_resource = TerminalResource::<T>::new(terminal);
 
         app.insert_resource(config)
             .insert_resource(layout)
             .insert_resource(terminal_resource)
             .insert_resource(TerminalInputQueue::<T>::default())
-            .insert_resource(SyncGeneration::<T>::default());
+            .insert_resource(ScrollOffset::<T>::default())
+            .insert_resource(UpdateThrottle::<T>::default())
+            .init_resource::<input::FocusedTerminal>()
+            .add_systems(PreUpdate, throttle::tick_update_throttle::<T>);
 
         // Only configure system set ordering once (first plugin instance)
         if !app.world().contains_resource::<TerminalSetConfigured>() {
@@ -241,40 +1214,235 @@ impl<T: 'static + Send + Sync> Plugin for TerminalEmuPlugin<T> {
                 )
                     .chain(),
             );
+            app.configure_sets(
+                Update,
+                (
+                    TerminalSet::GeometryEffects,
+                    TerminalSet::ResetColors,
+                    TerminalSet::ColorEffects,
+                )
+                    .chain()
+                    .in_set(TerminalSet::Effects),
+            );
+        }
+
+        // Update systems in their respective sets
+        if self.config.receive_input {
+            // Headless apps may run without `InputPlugin`
+            app.add_systems(
+                Update,
+                input::forward_input::<T>
+                    .in_set(TerminalSet::AppTick)
+                    .run_if(resource_exists::<ButtonInput<KeyCode>>),
+            );
+
+            #[cfg(feature = "gamepad")]
+            app.init_resource::<gamepad::GamepadMap>().add_systems(
+                Update,
+                gamepad::forward_gamepad_input::<T>.in_set(TerminalSet::AppTick),
+            );
+        }
+
+        #[cfg(feature = "render")]
+        self.build_render(app);
+        #[cfg(not(feature = "render"))]
+        app.add_systems(
+            Update,
+            scroll::apply_scroll_offset::<T>.in_set(TerminalSet::Sync),
+        );
+    }
+}
+
+#[cfg(feature = "render")]
+impl<T: 'static + Send + Sync> TerminalEmuPlugin<T> {
+    /// Atlas, cell entities, sync and effects: everything that draws.
+    fn build_render(&self, app: &mut App) {
+        let effect_clock = EffectClock::<T> {
+            fixed_step: self.config.effect_fixed_step,
+            ..default()
+        };
+
+        app.insert_resource(SyncGeneration::<T>::default())
+            .insert_resource(SyncStats::<T>::default())
+            .insert_resource(CursorState::<T>::default())
+            .insert_resource(TerminalVisible::<T>::default())
+            .insert_resource(Selection::<T>::default())
+            .insert_resource(selection::LineHighlight::<T>::default())
+            .insert_resource(effect_clock)
+            .init_resource::<atlas::SharedAtlases>()
+            .insert_resource(effects::flash::FlashTimers::<T>::default())
+            .insert_resource(effects::ColorFrameCache::<T>::default())
+            .insert_resource(grid_lines::ShowGridLines::<T>::default())
+            .insert_resource(atlas::MissingGlyphLog::<T>::default())
+            .add_message::<effects::EffectFinished>();
+
+        // The first terminal also runs effects spawned without a target
+        if !app.world().contains_resource::<DefaultEffectTerminal>() {
+            app.insert_resource(DefaultEffectTerminal)
+                .add_observer(effects::target_untargeted_effects::<T>);
         }
 
         // Startup: generate atlas, then spawn grid (chained because grid needs atlas)
         app.add_systems(
             Startup,
             (atlas::generate_font_atlas::<T>, grid::spawn_grid::<T>).chain(),
+        )
+        .add_systems(Startup, cursor::spawn_cursor::<T>);
+
+        // Offscreen render target needs the atlas-aligned layout
+        if self.config.render_layer.is_some() {
+            app.add_systems(
+                Startup,
+                render_target::spawn_render_target::<T>.after(atlas::generate_font_atlas::<T>),
+            )
+            .add_systems(
+                Update,
+                render_target::resize_render_target::<T>
+                    .in_set(TerminalSet::Sync)
+                    .after(grid::resize_grid::<T>),
+            );
+
+            #[cfg(feature = "crt")]
+            {
+                // Shared by every terminal; a second add would panic
+                type CrtMaterialPlugin = bevy::sprite_render::Material2dPlugin<crt::CrtMaterial>;
+                if !app.is_plugin_added::<CrtMaterialPlugin>() {
+                    bevy::asset::embedded_asset!(app, "crt.wgsl");
+                    app.add_plugins(CrtMaterialPlugin::default());
+                }
+                app.insert_resource(crt::CrtPostProcess::<T>::default())
+                    .add_systems(
+                        Update,
+                        crt::update_crt::<T>
+                            .in_set(TerminalSet::Sync)
+                            .after(render_target::resize_render_target::<T>),
+                    );
+            }
+
+            #[cfg(feature = "ui")]
+            app.add_systems(
+                Update,
+                (
+                    ui::attach_ui_node_image::<T>,
+                    ui::resize_from_ui_node::<T>,
+                    ui::update_ui_hovered_cell::<T>,
+                )
+                    .in_set(TerminalSet::AppTick),
+            );
+        }
+
+        app.add_systems(
+            Update,
+            grid::update_frame_background::<T>
+                .in_set(TerminalSet::Sync)
+                .after(atlas::rebuild_font_atlas::<T>),
+        )
+        .add_systems(
+            Update,
+            grid_lines::draw_grid_lines::<T>
+                .in_set(TerminalSet::Sync)
+                .after(atlas::rebuild_font_atlas::<T>),
+        )
+        .add_systems(
+            PostUpdate,
+            grid::shear_italic_glyphs::<T>.after(TransformSystems::Propagate),
         );
 
-        // Update systems in their respective sets
-        if self.config.receive_input {
+        #[cfg(feature = "debug")]
+        app.insert_resource(debug::AtlasDebugOverlay::<T>::default())
+            .add_systems(
+                Update,
+                debug::debug_draw_atlas::<T>
+                    .in_set(TerminalSet::Sync)
+                    .after(atlas::rebuild_font_atlas::<T>),
+            );
+
+        if self.config.cull_offscreen {
+            app.add_systems(
+                Update,
+                culling::update_terminal_visibility::<T>
+                    .in_set(TerminalSet::Sync)
+                    .before(grid::resize_grid::<T>),
+            );
+        }
+
+        if self.config.hide_blank_cells {
             app.add_systems(
                 Update,
-                input::forward_input::<T>.in_set(TerminalSet::AppTick),
+                culling::hide_blank_cells::<T>
+                    .in_set(TerminalSet::ColorEffects)
+                    .after(effects::apply_cell_opacity::<T>)
+                    .run_if(culling::terminal_visible::<T>),
             );
         }
 
         app.add_systems(
+            Update,
+            atlas::finish_font_loading::<T>
+                .in_set(TerminalSet::Sync)
+                .before(grid::resize_grid::<T>),
+        )
+        .add_systems(
+            Update,
+            grid::apply_glyph_offset::<T>
+                .in_set(TerminalSet::Sync)
+                .after(grid::resize_grid::<T>),
+        )
+        .add_systems(
+            Update,
+            scroll::apply_scroll_offset::<T>
+                .in_set(TerminalSet::Sync)
+                .after(grid::resize_grid::<T>)
+                .before(sync::sync_buffer_to_entities::<T>),
+        )
+        .add_systems(
             Update,
             (
+                grid::resize_grid::<T>,
                 atlas::expand_font_atlas::<T>,
                 atlas::rebuild_font_atlas::<T>,
                 sync::sync_buffer_to_entities::<T>,
             )
                 .chain()
-                .in_set(TerminalSet::Sync),
+                .in_set(TerminalSet::Sync)
+                .run_if(culling::terminal_visible::<T>)
+                .run_if(atlas::font_ready::<T>)
+                .run_if(throttle::terminal_update_due::<T>),
+        )
+        .add_systems(
+            Update,
+            effects::tick_effect_clock::<T>.in_set(TerminalSet::ResetTransforms),
+        )
+        .add_systems(
+            Update,
+            effects::advance_effect_envelopes::<T>
+                .in_set(TerminalSet::ResetTransforms)
+                .after(effects::tick_effect_clock::<T>),
+        )
+        .add_systems(
+            Update,
+            effects::follow_effect_origin::<T>
+                .in_set(TerminalSet::ResetTransforms)
+                .run_if(culling::terminal_visible::<T>),
+        )
+        .add_systems(
+            Update,
+            effects::reset_transforms::<T>
+                .in_set(TerminalSet::ResetTransforms)
+                .run_if(culling::terminal_visible::<T>),
         )
         .add_systems(
             Update,
             (
-                effects::reset_transforms::<T>,
-                effects::reset_colors::<T>,
+                effects::update_color_frame_cache::<T>,
+                effects::reset_colors::<T>.run_if(effects::color_frame_dirty::<T>),
             )
-                .in_set(TerminalSet::ResetTransforms),
+                .chain()
+                .in_set(TerminalSet::ResetColors)
+                .run_if(culling::terminal_visible::<T>),
         )
+        // Effects all write the same cells, so they can't run in parallel
+        // anyway; chaining pins down how they combine.
         .add_systems(
             Update,
             (
@@ -282,19 +1450,52 @@ impl<T: 'static + Send + Sync> Plugin for TerminalEmuPlugin<T> {
                 effects::bubbly::bubbly_system::<T>,
                 effects::collapse::collapse_system::<T>,
                 effects::explode::explode_system::<T>,
+                effects::flip::flip_system::<T>,
                 effects::glitch::glitch_system::<T>,
-                effects::glow::glow_system::<T>,
                 effects::gravity::gravity_system::<T>,
                 effects::jitter::jitter_system::<T>,
                 effects::knock::knock_system::<T>,
-                effects::rainbow::rainbow_system::<T>,
+                effects::magnet::magnet_system::<T>,
                 effects::ripple::ripple_system::<T>,
                 effects::scatter::scatter_system::<T>,
-                effects::shiny::shiny_system::<T>,
                 effects::slash::slash_system::<T>,
+                effects::swirl::swirl_system::<T>,
                 effects::wave::wave_system::<T>,
             )
-                .in_set(TerminalSet::Effects),
+                .chain()
+                .in_set(TerminalSet::GeometryEffects)
+                .run_if(culling::terminal_visible::<T>),
+        )
+        .add_systems(
+            Update,
+            (
+                (
+                    effects::fade::fade_system::<T>,
+                    effects::flash::flash_on_change_system::<T>,
+                    effects::flip::flip_color_system::<T>,
+                    effects::glitch::glitch_aberration_system::<T>,
+                    effects::glow::glow_system::<T>,
+                    effects::gradient_tint::gradient_tint_system::<T>,
+                    effects::line_reveal::line_reveal_system::<T>,
+                    effects::rainbow::rainbow_system::<T>,
+                    effects::shiny::shiny_system::<T>,
+                    selection::apply_line_highlight::<T>,
+                    selection::apply_selection_highlight::<T>,
+                )
+                    .chain()
+                    .run_if(effects::color_frame_dirty::<T>),
+                // The cursor blinks through idle frames
+                cursor::update_cursor_sprite::<T>,
+                (
+                    cursor::invert_cursor_cell::<T>,
+                    effects::apply_cell_opacity::<T>,
+                )
+                    .chain()
+                    .run_if(effects::color_frame_dirty::<T>),
+            )
+                .chain()
+                .in_set(TerminalSet::ColorEffects)
+                .run_if(culling::terminal_visible::<T>),
         );
     }
 }
@@ -303,6 +1504,11 @@ impl<T: 'static + Send + Sync> Plugin for TerminalEmuPlugin<T> {
 #[derive(Resource)]
 struct TerminalSetConfigured;
 
+/// Marker resource for the terminal that claims untargeted effects.
+#[cfg(feature = "render")]
+#[derive(Resource)]
+struct DefaultEffectTerminal;
+
 /// Clone a TerminalConfig without requiring T: Clone (T is only PhantomData).
 fn clone_config<T: 'static + Send + Sync>(c: &TerminalConfig<T>) -> TerminalConfig<T> {
     TerminalConfig {
@@ -314,8 +1520,161 @@ fn clone_config<T: 'static + Send + Sync>(c: &TerminalConfig<T>) -> TerminalConf
         default_bg: c.default_bg,
         origin_override: c.origin_override,
         z_layer: c.z_layer,
+        fg_z_offset: c.fg_z_offset,
+        glyph_offset: c.glyph_offset,
+        glyph_align: c.glyph_align,
         receive_input: c.receive_input,
+        forward_releases: c.forward_releases,
+        repeat_mode: c.repeat_mode,
+        max_queued_events: c.max_queued_events,
+        input_overflow: c.input_overflow,
         cell_size_override: c.cell_size_override,
+        atlas_filter: c.atlas_filter,
+        atlas_padding: c.atlas_padding,
+        snap_box_drawing: c.snap_box_drawing,
+        solid_block_elements: c.solid_block_elements,
+        color_glyphs: c.color_glyphs,
+        monospace_policy: c.monospace_policy,
+        cull_offscreen: c.cull_offscreen,
+        hide_blank_cells: c.hide_blank_cells,
+        max_glyphs: c.max_glyphs,
+        max_atlas_dimension: c.max_atlas_dimension,
+        initial_charset: c.initial_charset.clone(),
+        preload_chars: c.preload_chars.clone(),
+        atlas_cache_dir: c.atlas_cache_dir.clone(),
+        share_atlas: c.share_atlas,
+        render_layer: c.render_layer,
+        scrollback_lines: c.scrollback_lines,
+        tab_width: c.tab_width,
+        coalesce_dirty: c.coalesce_dirty,
+        transparent_background: c.transparent_background,
+        effect_fixed_step: c.effect_fixed_step,
+        update_interval: c.update_interval,
+        cursor_style: c.cursor_style,
+        cursor_color: c.cursor_color,
+        cursor_blink_hz: c.cursor_blink_hz,
+        dim_mode: c.dim_mode,
+        dim_factor: c.dim_factor,
+        bold_brightens: c.bold_brightens,
+        frame_background: c.frame_background,
+        faux_italic: c.faux_italic,
         _marker: PhantomData,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Term;
+
+    fn layout() -> TerminalLayout<Term> {
+        TerminalLayout {
+            cell_width: 10.0,
+            cell_height: 20.0,
+            origin: Vec2::new(-40.0, 60.0),
+            columns: 8,
+            rows: 6,
+            _marker: PhantomData,
+        }
+    }
+
+    #[test]
+    fn test_world_to_grid_edges() {
+        let layout = layout();
+        let at = |x, y| layout.world_to_grid(Vec2::new(x, y));
+        assert_eq!(at(-40.0, 60.0), Some(GridPosition { col: 0, row: 0 }));
+        assert_eq!(at(-30.1, 40.1), Some(GridPosition { col: 0, row: 0 }));
+        assert_eq!(at(-30.0, 40.0), Some(GridPosition { col: 1, row: 1 }));
+        assert_eq!(at(39.9, -59.9), Some(GridPosition { col: 7, row: 5 }));
+        // Right and bottom edges belong to the next (nonexistent) cell
+        assert_eq!(at(40.0, 0.0), None);
+        assert_eq!(at(0.0, -60.0), None);
+        assert_eq!(at(-40.1, 0.0), None);
+        assert_eq!(at(0.0, 60.1), None);
+    }
+
+    #[test]
+    fn test_poisoned_lock_is_an_error() {
+        let Ok(terminal) = ratatui::Terminal::new(BevyBackend::new(4, 2));
+        let terminal = TerminalResource::<Term>::new(terminal);
+        let shared = terminal.0.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = shared.lock();
+            panic!("poison the terminal lock");
+        })
+        .join();
+
+        assert!(terminal.lock().is_err());
+        assert_eq!(terminal.draw(|_| {}), Err(TerminalPoisoned));
+        assert_eq!(terminal.with_backend(|_| ()), Err(TerminalPoisoned));
+    }
+
+    #[test]
+    fn test_validate_and_repair() {
+        assert_eq!(TerminalConfig::<Term>::default().validate(), Ok(()));
+
+        let config = TerminalConfig::<Term> {
+            columns: 0,
+            font_size: f32::NAN,
+            font: FontSource::Custom(vec![1, 2, 3]),
+            ..default()
+        };
+        assert_eq!(config.validate(), Err(ConfigError::ZeroSize { columns: 0, rows: 24 }));
+
+        let repaired = config.repaired();
+        assert_eq!(repaired.validate(), Ok(()));
+        assert_eq!((repaired.columns, repaired.rows), (80, 24));
+        assert_eq!(repaired.font_size, 20.0);
+        assert!(matches!(repaired.font, FontSource::Default));
+    }
+
+    #[test]
+    fn test_grid_to_world_is_cell_center() {
+        let layout = layout();
+        assert_eq!(layout.grid_to_world(0, 0), Vec2::new(-35.0, 50.0));
+        assert_eq!(layout.grid_to_world(7, 5), Vec2::new(35.0, -50.0));
+        // Clamped to the last cell
+        assert_eq!(layout.grid_to_world(100, 100), layout.grid_to_world(7, 5));
+
+        for (col, row) in [(0, 0), (3, 2), (7, 5)] {
+            let center = layout.grid_to_world(col, row);
+            assert_eq!(layout.world_to_grid(center), Some(GridPosition { col, row }));
+        }
+    }
+
+    #[test]
+    fn test_grid_position_neighbors_stay_in_bounds() {
+        let at = GridPosition::new;
+        let corner: Vec<_> = at(0, 0).neighbors4(3, 2).collect();
+        assert_eq!(corner, [at(1, 0), at(0, 1)]);
+        let middle: Vec<_> = at(1, 0).neighbors8(3, 2).collect();
+        assert_eq!(middle, [at(0, 0), at(2, 0), at(0, 1), at(1, 1), at(2, 1)]);
+        assert_eq!(at(1, 1).neighbors8(3, 3).count(), 8);
+
+        assert_eq!(at(2, 1).offset(-2, -1, 3, 2), Some(at(0, 0)));
+        assert_eq!(at(2, 1).offset(1, 0, 3, 2), None);
+    }
+
+    #[test]
+    fn test_world_to_grid_coords_round_trip() {
+        let layout = layout();
+        assert_eq!(layout.world_to_grid_coords(layout.grid_to_world(3, 2)), Vec2::new(3.0, 2.0));
+        // Top-left corner of the grid is half a cell before cell (0, 0)
+        assert_eq!(layout.world_to_grid_coords(layout.origin), Vec2::new(-0.5, -0.5));
+        // Not clamped
+        assert_eq!(layout.world_to_grid_coords(Vec2::new(-60.0, 60.0)).x, -2.5);
+    }
+
+    #[test]
+    fn test_pixel_size_and_bounds() {
+        let layout = layout();
+        assert_eq!((layout.pixel_width(), layout.pixel_height()), (80.0, 120.0));
+        let bounds = layout.bounds();
+        assert_eq!(bounds, Rect::new(-40.0, -60.0, 40.0, 60.0));
+        assert_eq!(bounds.center(), Vec2::ZERO);
+        // Every cell center lies inside
+        assert!(bounds.contains(layout.grid_to_world(0, 0)));
+        assert!(bounds.contains(layout.grid_to_world(7, 5)));
+    }
+}
@@ -0,0 +1,111 @@
+use std::marker::PhantomData;
+
+use bevy::camera::visibility::RenderLayers;
+use bevy::camera::RenderTarget;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureFormat};
+
+use crate::{TerminalConfig, TerminalLayout};
+
+/// Offscreen render target for a terminal drawn on its own render layer.
+///
+/// Created automatically when `TerminalConfig::render_layer` is set. Use
+/// `image` as a texture on a 3D quad, a UI node, or anywhere else an
+/// `Image` handle is accepted.
+#[derive(Resource, Clone, Debug)]
+pub struct TerminalRenderTarget<T: 'static + Send + Sync> {
+    /// The image the terminal is rendered into.
+    pub image: Handle<Image>,
+    /// The camera that renders the terminal's layer into `image`.
+    pub camera: Entity,
+    /// Size of `image` in pixels.
+    pub size: UVec2,
+    _marker: PhantomData<T>,
+}
+
+impl<T: 'static + Send + Sync> TerminalRenderTarget<T> {
+    /// Pixel size of the render target needed to hold the whole grid.
+    pub fn size_for(config: &TerminalConfig<T>, layout: &TerminalLayout<T>) -> UVec2 {
+        UVec2::new(
+            (config.columns as f32 * layout.cell_width).ceil() as u32,
+            (config.rows as f32 * layout.cell_height).ceil() as u32,
+        )
+        .max(UVec2::ONE)
+    }
+}
+
+/// Startup system that creates the render-target image and a 2D camera
+/// looking at the terminal's render layer. Runs after the atlas has aligned
+/// the layout so the image matches the grid exactly.
+pub fn spawn_render_target<T: 'static + Send + Sync>(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    config: Res<TerminalConfig<T>>,
+    layout: Res<TerminalLayout<T>>,
+) {
+    let Some(layer) = config.render_layer else {
+        return;
+    };
+
+    let size = TerminalRenderTarget::size_for(&config, &layout);
+    let image = Image::new_target_texture(size.x, size.y, TextureFormat::Rgba8UnormSrgb, None);
+    let image_handle = images.add(image);
+
+    // Center the camera on the grid so the image frames it exactly
+    let center = layout.bounds().center();
+
+    let camera = commands
+        .spawn((
+            Camera2d,
+            Camera {
+                // Render before any camera that samples the image
+                order: -1,
+                clear_color: ClearColorConfig::Custom(config.reset_bg()),
+                ..default()
+            },
+            RenderTarget::Image(image_handle.clone().into()),
+            RenderLayers::layer(layer as usize),
+            Transform::from_translation(center.extend(0.0)),
+        ))
+        .id();
+
+    commands.insert_resource(TerminalRenderTarget::<T> {
+        image: image_handle,
+        camera,
+        size,
+        _marker: PhantomData,
+    });
+}
+
+/// Keeps the render-target image and camera matched to the grid after the
+/// terminal is resized or its cell size changes.
+pub fn resize_render_target<T: 'static + Send + Sync>(
+    config: Res<TerminalConfig<T>>,
+    layout: Res<TerminalLayout<T>>,
+    target: Option<ResMut<TerminalRenderTarget<T>>>,
+    mut images: ResMut<Assets<Image>>,
+    mut cameras: Query<&mut Transform, With<Camera>>,
+) {
+    let Some(mut target) = target else {
+        return;
+    };
+    let size = TerminalRenderTarget::size_for(&config, &layout);
+    if size == target.size {
+        return;
+    }
+
+    if let Some(image) = images.get_mut(&target.image) {
+        image.resize(Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        });
+    }
+
+    if let Ok(mut transform) = cameras.get_mut(target.camera) {
+        let center = layout.bounds().center();
+        transform.translation = center.extend(transform.translation.z);
+    }
+
+    target.size = size;
+}
@@ -0,0 +1,178 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+
+use crate::TerminalResource;
+
+/// How far the visible window is scrolled back into the scrollback buffer.
+///
+/// Requires `TerminalConfig::scrollback_lines > 0`. The offset is clamped to
+/// the available scrollback each frame.
+#[derive(Resource)]
+pub struct ScrollOffset<T: 'static + Send + Sync> {
+    /// Rows scrolled up from the live screen (0 = following the bottom).
+    pub lines: usize,
+    /// Jump back to the live screen whenever the app draws
+    /// (default: false = stay put while new output arrives).
+    pub snap_on_write: bool,
+    _marker: PhantomData<T>,
+}
+
+impl<T: 'static + Send + Sync> Default for ScrollOffset<T> {
+    fn default() -> Self {
+        Self {
+            lines: 0,
+            snap_on_write: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: 'static + Send + Sync> ScrollOffset<T> {
+    /// Scroll back `lines` rows into history.
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.lines = self.lines.saturating_add(lines);
+    }
+
+    /// Scroll forward `lines` rows toward the live screen.
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.lines = self.lines.saturating_sub(lines);
+    }
+
+    /// Return to the live screen.
+    pub fn scroll_to_bottom(&mut self) {
+        self.lines = 0;
+    }
+
+    /// Whether the window is showing history rather than the live screen.
+    pub fn is_scrolled(&self) -> bool {
+        self.lines > 0
+    }
+}
+
+/// Applies `ScrollOffset<T>` to the backend's view before sync. While
+/// scrolled back, rows pushed into scrollback raise the offset so the window
+/// stays on the same content (unless `snap_on_write` is set). Skips the
+/// frame while another thread holds the terminal.
+pub fn apply_scroll_offset<T: 'static + Send + Sync>(
+    terminal_res: Res<TerminalResource<T>>,
+    mut offset: ResMut<ScrollOffset<T>>,
+) -> Result {
+    let Some(mut terminal) = terminal_res.try_lock()? else {
+        return Ok(());
+    };
+    let backend = terminal.backend_mut();
+    let scrolled = backend.take_scrolled_lines();
+    let written = backend.take_written();
+
+    let mut lines = offset.lines;
+    if lines > 0 {
+        if offset.snap_on_write && written {
+            lines = 0;
+        } else {
+            lines += scrolled;
+        }
+    }
+    let lines = lines.min(backend.scrollback_len());
+
+    if offset.lines != lines {
+        offset.lines = lines;
+    }
+    backend.set_view_offset(lines);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::backend::Backend;
+    use ratatui::style::Style;
+    use ratatui::Terminal;
+
+    use super::*;
+    use crate::testing::TerminalTestHarness;
+    use crate::{BevyBackend, TerminalEmuPlugin};
+
+    struct Term;
+
+    #[test]
+    fn test_snap_on_write_ignores_unchanged_redraws() {
+        let Ok(terminal) = Terminal::new(BevyBackend::new(4, 2).with_scrollback(10));
+        let terminal = TerminalResource::<Term>::new(terminal);
+        let mut app = App::new();
+        app.insert_resource(terminal.clone())
+            .insert_resource(ScrollOffset::<Term> {
+                snap_on_write: true,
+                ..default()
+            })
+            .add_systems(Update, apply_scroll_offset::<Term>);
+        let draw = |text: &str| {
+            let mut terminal = terminal.lock().unwrap();
+            let style = Style::default();
+            terminal
+                .draw(|frame| frame.buffer_mut().set_string(0, 0, text, style))
+                .unwrap();
+        };
+        let lines = |app: &App| app.world().resource::<ScrollOffset<Term>>().lines;
+
+        draw("ab");
+        let mut locked = terminal.lock().unwrap();
+        locked.backend_mut().append_lines(4).unwrap();
+        drop(locked);
+        app.update();
+        let mut offset = app.world_mut().resource_mut::<ScrollOffset<Term>>();
+        offset.scroll_up(2);
+
+        // ratatui sends an empty diff for the same frame
+        draw("ab");
+        app.update();
+        assert_eq!(lines(&app), 2);
+
+        draw("cd");
+        app.update();
+        assert_eq!(lines(&app), 0);
+    }
+
+    #[test]
+    fn test_pushed_lines_fill_history() {
+        use ratatui::text::Line;
+
+        let plugin = TerminalEmuPlugin::<Term>::new()
+            .columns(4)
+            .rows(2)
+            .scrollback_lines(10);
+        let mut harness = TerminalTestHarness::new(plugin);
+        let style = Style::default();
+        harness.draw(|frame| frame.buffer_mut().set_string(0, 0, "live", style));
+        let push = |harness: &TerminalTestHarness<Term>, lines: &[&'static str]| {
+            let lines = lines.iter().map(|&text| Line::from(text));
+            harness.terminal().push_lines(lines).unwrap();
+        };
+        // The rows the window shows, through the scroll offset
+        let view = |harness: &TerminalTestHarness<Term>| {
+            let rows = harness.terminal().with_backend(|backend| {
+                let cells = (0..8).map(|idx| backend.view_cell(idx).symbol().to_string());
+                cells.collect::<String>()
+            });
+            rows.unwrap()
+        };
+
+        push(&harness, &["one", "two", "three"]);
+        harness.update();
+        assert_eq!(harness.row_text(0), "live");
+        assert_eq!(view(&harness), "live    ");
+
+        let world = harness.app.world_mut();
+        world.resource_mut::<ScrollOffset<Term>>().scroll_up(2);
+        harness.update();
+        assert_eq!(view(&harness), "two thre");
+
+        // New history keeps the window on the same rows
+        push(&harness, &["four"]);
+        harness.update();
+        let offset = harness.app.world().resource::<ScrollOffset<Term>>();
+        assert_eq!(offset.lines, 3);
+        assert_eq!(view(&harness), "two thre");
+        #[cfg(feature = "render")]
+        assert_eq!(harness.style(0, 0).unwrap().symbol, "t");
+    }
+}
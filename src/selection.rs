@@ -0,0 +1,326 @@
+use std::marker::PhantomData;
+
+use bevy::color::Mix;
+use bevy::prelude::*;
+use ratatui::backend::Backend;
+
+use crate::backend::BevyBackend;
+use crate::grid::{CellEntityIndex, GridPosition, TerminalCell};
+
+/// How a selection range maps to cells.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SelectionMode {
+    /// Reading-order range, wrapping across rows like a text editor.
+    #[default]
+    Linear,
+    /// Rectangular block between the two corners.
+    Block,
+}
+
+/// The current text selection for a terminal instance.
+///
+/// Selected cells get their background tinted toward `highlight` each frame.
+/// The ratatui buffer is never touched, and because `reset_colors` restores
+/// base colors every frame, clearing the selection restores them too.
+#[derive(Resource, Clone, Debug)]
+pub struct Selection<T: 'static + Send + Sync> {
+    /// Anchor and active end of the selection, in the order they were set.
+    pub range: Option<(GridPosition, GridPosition)>,
+    pub mode: SelectionMode,
+    /// Color selected backgrounds are tinted toward.
+    pub highlight: Color,
+    /// How far to blend toward `highlight` (0.0 = none, 1.0 = solid).
+    pub strength: f32,
+    _marker: PhantomData<T>,
+}
+
+impl<T: 'static + Send + Sync> Default for Selection<T> {
+    fn default() -> Self {
+        Self {
+            range: None,
+            mode: SelectionMode::Linear,
+            highlight: Color::srgb(0.25, 0.4, 0.7),
+            strength: 0.6,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: 'static + Send + Sync> Selection<T> {
+    /// Start a drag selection at `pos` (e.g. on mouse press).
+    pub fn begin_drag(&mut self, pos: GridPosition) {
+        self.range = Some((pos, pos));
+    }
+
+    /// Move the active end of a drag selection (e.g. on mouse move).
+    /// Does nothing if no drag has begun.
+    pub fn update_drag(&mut self, pos: GridPosition) {
+        if let Some((_, end)) = self.range.as_mut() {
+            *end = pos;
+        }
+    }
+
+    /// Remove the selection.
+    pub fn clear(&mut self) {
+        self.range = None;
+    }
+
+    /// Whether anything is selected.
+    pub fn is_active(&self) -> bool {
+        self.range.is_some()
+    }
+
+    /// Anchor and end ordered so the first comes first in reading order.
+    pub fn ordered(&self) -> Option<(GridPosition, GridPosition)> {
+        let (a, b) = self.range?;
+        if (a.row, a.col) <= (b.row, b.col) {
+            Some((a, b))
+        } else {
+            Some((b, a))
+        }
+    }
+
+    /// Check if the cell at (col, row) is selected.
+    pub fn contains(&self, col: u16, row: u16) -> bool {
+        let Some((start, end)) = self.ordered() else {
+            return false;
+        };
+        match self.mode {
+            SelectionMode::Linear => {
+                (row, col) >= (start.row, start.col) && (row, col) <= (end.row, end.col)
+            }
+            SelectionMode::Block => {
+                let (min_col, max_col) = (start.col.min(end.col), start.col.max(end.col));
+                row >= start.row && row <= end.row && col >= min_col && col <= max_col
+            }
+        }
+    }
+
+    /// Read the selected cells' symbols from the backend, row by row.
+    ///
+    /// Trailing spaces are trimmed from each line and lines are joined with
+    /// `\n`. Linear selections take partial first/last rows; block selections
+    /// take the same column span from every row.
+    pub fn selected_text(&self, backend: &BevyBackend) -> String {
+        let Some((start, end)) = self.ordered() else {
+            return String::new();
+        };
+        let columns = backend.size().map(|s| s.width).unwrap_or(0);
+        let last_col = columns.saturating_sub(1);
+
+        let mut lines = Vec::new();
+        for row in start.row..=end.row {
+            let (from, to) = match self.mode {
+                SelectionMode::Linear => (
+                    if row == start.row { start.col } else { 0 },
+                    if row == end.row { end.col } else { last_col },
+                ),
+                SelectionMode::Block => (start.col.min(end.col), start.col.max(end.col)),
+            };
+            let line: String = (from..=to.min(last_col))
+                .filter_map(|col| backend.cell(col, row))
+                .map(|cell| cell.symbol())
+                .collect();
+            lines.push(line.trim_end_matches(' ').to_string());
+        }
+        lines.join("\n")
+    }
+}
+
+/// Copy the selected text to the OS clipboard and return it.
+#[cfg(feature = "clipboard")]
+pub fn copy_selection<T: 'static + Send + Sync>(
+    terminal_res: &crate::TerminalResource<T>,
+    selection: &Selection<T>,
+) -> Result<String, arboard::Error> {
+    let text = terminal_res
+        .with_backend(|backend| selection.selected_text(backend))
+        .map_err(|err| arboard::Error::Unknown {
+            description: err.to_string(),
+        })?;
+    arboard::Clipboard::new()?.set_text(text.clone())?;
+    Ok(text)
+}
+
+/// Highlights one whole row of a terminal instance, e.g. the line an
+/// editor's cursor is on.
+///
+/// Like `Selection`, only the background sprites are tinted and the tint is
+/// reapplied every frame, so moving `row` or setting it to None restores the
+/// old row's colors on the next frame. A selection is tinted on top of it,
+/// and `CursorStyle::Invert` inverts whatever the cell shows.
+#[derive(Resource, Clone, Debug)]
+pub struct LineHighlight<T: 'static + Send + Sync> {
+    /// Row to highlight (default: None).
+    pub row: Option<u16>,
+    /// Color the row's backgrounds are tinted toward.
+    pub color: Color,
+    /// How far to blend toward `color` (0.0 = none, 1.0 = solid).
+    pub strength: f32,
+    _marker: PhantomData<T>,
+}
+
+impl<T: 'static + Send + Sync> Default for LineHighlight<T> {
+    fn default() -> Self {
+        Self {
+            row: None,
+            color: Color::srgb(0.3, 0.3, 0.35),
+            strength: 0.3,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Tints the background sprites of the `LineHighlight` row. Runs after
+/// `reset_colors` and before the selection highlight, and touches only the
+/// one row.
+pub fn apply_line_highlight<T: 'static + Send + Sync>(
+    highlight: Res<LineHighlight<T>>,
+    cell_index: Res<CellEntityIndex<T>>,
+    mut cells: Query<&mut Sprite, With<TerminalCell<T>>>,
+) {
+    let Some(row) = highlight.row else {
+        return;
+    };
+
+    for entity in cell_index.row(row) {
+        if let Ok(mut sprite) = cells.get_mut(entity) {
+            sprite.color = sprite.color.mix(&highlight.color, highlight.strength);
+        }
+    }
+}
+
+/// Tints the background sprites of selected cells. Runs after `reset_colors`
+/// so the tint lasts exactly one frame.
+pub fn apply_selection_highlight<T: 'static + Send + Sync>(
+    selection: Res<Selection<T>>,
+    cell_index: Res<CellEntityIndex<T>>,
+    mut cells: Query<(&GridPosition, &mut Sprite), With<TerminalCell<T>>>,
+) {
+    if !selection.is_active() {
+        return;
+    }
+
+    for &entity in &cell_index.entities {
+        let Ok((pos, mut sprite)) = cells.get_mut(entity) else {
+            continue;
+        };
+        if selection.contains(pos.col, pos.row) {
+            sprite.color = sprite.color.mix(&selection.highlight, selection.strength);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Term;
+
+    fn selection(mode: SelectionMode, a: (u16, u16), b: (u16, u16)) -> Selection<Term> {
+        Selection {
+            range: Some((
+                GridPosition { col: a.0, row: a.1 },
+                GridPosition { col: b.0, row: b.1 },
+            )),
+            mode,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn test_line_highlight_tints_one_row_under_selection() {
+        use crate::testing::TerminalTestHarness;
+        use crate::TerminalEmuPlugin;
+
+        let plugin = TerminalEmuPlugin::<Term>::new().columns(3).rows(2);
+        let mut harness = TerminalTestHarness::new(plugin);
+        let bg = |harness: &TerminalTestHarness<Term>, col, row| {
+            let world = harness.app.world();
+            let entity = world.resource::<CellEntityIndex<Term>>().get(col, row);
+            world.get::<Sprite>(entity.unwrap()).unwrap().color
+        };
+        let base = bg(&harness, 0, 1);
+        let set_row = |harness: &mut TerminalTestHarness<Term>, row| {
+            let world = harness.app.world_mut();
+            world.resource_mut::<LineHighlight<Term>>().row = row;
+            harness.update();
+        };
+
+        set_row(&mut harness, Some(1));
+        let line = LineHighlight::<Term>::default();
+        let lit = base.mix(&line.color, line.strength);
+        assert_eq!((bg(&harness, 0, 0), bg(&harness, 2, 1)), (base, lit));
+
+        // The selection tints on top of the highlighted row
+        let world = harness.app.world_mut();
+        let pos = GridPosition { col: 1, row: 1 };
+        world.resource_mut::<Selection<Term>>().range = Some((pos, pos));
+        harness.update();
+        let sel = Selection::<Term>::default();
+        assert_eq!(bg(&harness, 1, 1), lit.mix(&sel.highlight, sel.strength));
+
+        let world = harness.app.world_mut();
+        world.resource_mut::<Selection<Term>>().clear();
+        set_row(&mut harness, None);
+        assert_eq!(bg(&harness, 2, 1), base);
+    }
+
+    #[test]
+    fn test_linear_selection_wraps_rows() {
+        // Dragged backwards: end before start
+        let sel = selection(SelectionMode::Linear, (2, 2), (5, 0));
+        assert!(sel.contains(5, 0));
+        assert!(sel.contains(79, 0));
+        assert!(sel.contains(0, 1));
+        assert!(sel.contains(2, 2));
+        assert!(!sel.contains(4, 0));
+        assert!(!sel.contains(3, 2));
+    }
+
+    #[test]
+    fn test_block_selection() {
+        let sel = selection(SelectionMode::Block, (5, 0), (2, 2));
+        assert!(sel.contains(2, 1));
+        assert!(sel.contains(5, 2));
+        assert!(!sel.contains(6, 1));
+        assert!(!sel.contains(0, 1));
+    }
+
+    fn backend_with_lines(lines: &[&str]) -> BevyBackend {
+        use ratatui::buffer::Cell;
+
+        let mut backend = BevyBackend::new(8, lines.len() as u16);
+        for (row, line) in lines.iter().enumerate() {
+            for (col, ch) in line.chars().enumerate() {
+                let mut cell = Cell::default();
+                cell.set_char(ch);
+                backend
+                    .draw(std::iter::once((col as u16, row as u16, &cell)))
+                    .unwrap();
+            }
+        }
+        backend
+    }
+
+    #[test]
+    fn test_selected_text_linear() {
+        let backend = backend_with_lines(&["hello", "big", "world"]);
+        let sel = selection(SelectionMode::Linear, (1, 0), (2, 2));
+        assert_eq!(sel.selected_text(&backend), "ello\nbig\nwor");
+    }
+
+    #[test]
+    fn test_selected_text_block() {
+        let backend = backend_with_lines(&["hello", "big", "world"]);
+        let sel = selection(SelectionMode::Block, (3, 2), (1, 0));
+        assert_eq!(sel.selected_text(&backend), "ell\nig\norl");
+    }
+
+    #[test]
+    fn test_cleared_selection_contains_nothing() {
+        let mut sel = selection(SelectionMode::Linear, (0, 0), (3, 3));
+        sel.clear();
+        assert!(!sel.contains(0, 0));
+    }
+}
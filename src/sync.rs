@@ -1,12 +1,16 @@
 use std::marker::PhantomData;
+use std::ops::Range;
+use std::time::Duration;
 
+use bevy::platform::time::Instant;
 use bevy::prelude::*;
 use ratatui::style::Modifier;
 
 use crate::atlas::FontAtlasResource;
-use crate::color::{ratatui_bg_to_bevy, ratatui_fg_to_bevy};
-use crate::grid::{BackgroundSprite, CellEntityIndex, CellStyle, ForegroundSprite};
-use crate::{TerminalResource, TerminalConfig};
+use crate::color::{bright_bold_fg, ratatui_bg_to_bevy, ratatui_fg_to_bevy};
+use crate::cursor::CursorState;
+use crate::grid::{BackgroundSprite, CellEntityIndex, CellStyle, ForegroundSprite, GridPosition};
+use crate::{TerminalResource, TerminalConfig, TerminalLayout};
 
 /// Resource tracking the last synced generation to skip redundant updates.
 #[derive(Resource)]
@@ -24,6 +28,59 @@ impl<T: 'static + Send + Sync> Default for SyncGeneration<T> {
     }
 }
 
+/// Per-frame metrics from the last `sync_buffer_to_entities` pass.
+///
+/// Reset to zero on frames where the buffer didn't change.
+#[derive(Resource, Clone, Debug)]
+pub struct SyncStats<T: 'static + Send + Sync> {
+    /// Dirty cells visited.
+    pub cells_processed: usize,
+    /// Index ranges the dirty cells were processed as. Equals
+    /// `cells_processed` unless `TerminalConfig::coalesce_dirty` is set.
+    pub spans: usize,
+    /// Wall time spent in the pass.
+    pub elapsed: Duration,
+    _marker: PhantomData<T>,
+}
+
+impl<T: 'static + Send + Sync> Default for SyncStats<T> {
+    fn default() -> Self {
+        Self {
+            cells_processed: 0,
+            spans: 0,
+            elapsed: Duration::ZERO,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Group dirty flags into contiguous index ranges that never cross a row
+/// boundary, in ascending order.
+pub(crate) fn dirty_spans(dirty: &[bool], columns: usize) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    if columns == 0 {
+        return spans;
+    }
+    for (row, flags) in dirty.chunks(columns).enumerate() {
+        let base = row * columns;
+        let mut start = None;
+        for (col, &d) in flags.iter().enumerate() {
+            match (d, start) {
+                (true, None) => start = Some(col),
+                (false, Some(s)) => {
+                    spans.push(base + s..base + col);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(s) = start {
+            spans.push(base + s..base + flags.len());
+        }
+    }
+    spans
+}
+
 /// Sync the backend buffer contents to cell entity sprites each frame.
 ///
 /// Only processes cells marked dirty by the backend, and uses compare-before-write
@@ -31,51 +88,116 @@ impl<T: 'static + Send + Sync> Default for SyncGeneration<T> {
 pub fn sync_buffer_to_entities<T: 'static + Send + Sync>(
     terminal_res: Res<TerminalResource<T>>,
     config: Res<TerminalConfig<T>>,
+    layout: Res<TerminalLayout<T>>,
     mut atlas: ResMut<FontAtlasResource<T>>,
     cell_index: Res<CellEntityIndex<T>>,
     mut sync_gen: ResMut<SyncGeneration<T>>,
+    mut stats: ResMut<SyncStats<T>>,
+    mut cursor: ResMut<CursorState<T>>,
     mut cell_query: Query<(&mut CellStyle, &mut Sprite), With<BackgroundSprite<T>>>,
     mut fg_query: Query<&mut Sprite, (With<ForegroundSprite<T>>, Without<BackgroundSprite<T>>)>,
-) {
-    let mut terminal = terminal_res.0.lock().unwrap();
+) -> Result {
+    // A `TerminalDriver` drawing on another thread holds the lock; its
+    // flush bumps the generation, so the next frame picks it up
+    let Some(mut terminal) = terminal_res.try_lock()? else {
+        return Ok(());
+    };
+
+    // Keep window_size().pixels in step with the atlas-aligned layout
+    let cell_size = (
+        layout.cell_width.round() as u16,
+        layout.cell_height.round() as u16,
+    );
+    if terminal.backend().cell_size() != cell_size {
+        terminal.backend_mut().set_cell_size(cell_size.0, cell_size.1);
+    }
+
+    // Cursor moves don't bump the generation, so mirror it every frame
+    let backend = terminal.backend();
+    let position = backend.cursor_position();
+    let position = GridPosition {
+        col: position.x,
+        row: position.y,
+    };
+    if cursor.position != position {
+        cursor.position = position;
+    }
+    if cursor.visible != backend.cursor_visible() {
+        cursor.visible = backend.cursor_visible();
+    }
+
     let generation = terminal.backend().generation();
 
     // Skip if nothing has changed
     if generation == sync_gen.generation {
-        return;
+        if stats.cells_processed != 0 {
+            stats.cells_processed = 0;
+            stats.spans = 0;
+            stats.elapsed = Duration::ZERO;
+        }
+        return Ok(());
     }
     sync_gen.generation = generation;
+    let started = Instant::now();
 
-    // Collect dirty cell indices while holding immutable borrow
+    // Collect dirty cell ranges while holding immutable borrow
     let columns = config.columns as usize;
-    let dirty_indices: Vec<usize> = terminal
-        .backend()
-        .dirty_cells()
-        .iter()
-        .enumerate()
-        .filter_map(|(i, &d)| if d { Some(i) } else { None })
-        .collect();
+    let backend = terminal.backend();
+    let dirty_cells = backend.dirty_cells();
+    let dirty: Vec<Range<usize>> = if backend.view_offset() > 0 && dirty_cells.contains(&true) {
+        // While scrolled back, live rows are shifted in the window, so any
+        // write repaints the whole visible grid.
+        let all = vec![true; dirty_cells.len()];
+        if config.coalesce_dirty {
+            dirty_spans(&all, columns)
+        } else {
+            (0..all.len()).map(|i| i..i + 1).collect()
+        }
+    } else if config.coalesce_dirty {
+        dirty_spans(dirty_cells, columns)
+    } else {
+        dirty_cells
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &d)| if d { Some(i..i + 1) } else { None })
+            .collect()
+    };
+    let span_count = dirty.len();
+    let mut cells_processed = 0;
 
     // Clear dirty flags (needs mutable borrow, but dirty_indices is owned)
     terminal.backend_mut().clear_dirty();
 
-    let buffer = terminal.backend().buffer();
+    let backend = terminal.backend();
+    let cell_count = backend.buffer().len();
+    atlas.use_tick += 1;
     let space_index = atlas.glyph_map.get(&' ').copied().unwrap_or(0);
     let mut new_glyphs: Vec<char> = Vec::new();
 
-    for idx in dirty_indices {
-        if idx >= buffer.len() {
+    for idx in dirty.into_iter().flatten() {
+        if idx >= cell_count {
             continue;
         }
+        cells_processed += 1;
         let col = (idx % columns) as u16;
         let row = (idx / columns) as u16;
 
-        let cell = &buffer[idx];
+        let cell = backend.view_cell(idx);
         let symbol = cell.symbol();
-        let fg = ratatui_fg_to_bevy(cell.fg, config.default_fg);
-        let bg = ratatui_bg_to_bevy(cell.bg, config.default_bg);
         let modifier = cell.modifier;
         let bold = modifier.contains(Modifier::BOLD);
+        let cell_fg = if bold && config.bold_brightens {
+            bright_bold_fg(cell.fg, config.default_fg)
+        } else {
+            ratatui_fg_to_bevy(cell.fg, config.default_fg)
+        };
+        // Reverse video swaps the resolved colors; a reset background turns
+        // into an opaque default_bg glyph even with transparent_background
+        let (fg, bg) = if modifier.contains(Modifier::REVERSED) {
+            (ratatui_bg_to_bevy(cell.bg, config.default_bg), cell_fg)
+        } else {
+            (cell_fg, ratatui_bg_to_bevy(cell.bg, config.reset_bg()))
+        };
         let italic = modifier.contains(Modifier::ITALIC);
         let underlined = modifier.contains(Modifier::UNDERLINED);
         let dim = modifier.contains(Modifier::DIM);
@@ -111,15 +233,18 @@ pub fn sync_buffer_to_entities<T: 'static + Send + Sync>(
         // Update foreground sprite via direct entity lookup
         let fg_entity = cell_index.fg_entities[idx];
         if let Ok(mut fg_sprite) = fg_query.get_mut(fg_entity) {
-            let target_fg = if dim { fg.with_alpha(0.5) } else { fg };
+            let ch = symbol.chars().next().unwrap_or(' ');
+            let target_fg = config.glyph_tint(fg, dim, atlas.is_color_glyph(ch));
             if fg_sprite.color != target_fg {
                 fg_sprite.color = target_fg;
             }
 
             // Look up glyph in atlas; queue unknown chars for next-frame expansion
-            let ch = symbol.chars().next().unwrap_or(' ');
-            let glyph_index = match atlas.glyph_map.get(&ch) {
-                Some(&glyph_idx) => glyph_idx,
+            let glyph_index = match atlas.glyph_map.get(&ch).copied() {
+                Some(glyph_idx) => {
+                    atlas.touch_glyph(ch);
+                    glyph_idx
+                }
                 None => {
                     if ch != ' ' {
                         new_glyphs.push(ch);
@@ -142,4 +267,96 @@ pub fn sync_buffer_to_entities<T: 'static + Send + Sync>(
     if !new_glyphs.is_empty() {
         atlas.pending_glyphs.extend(new_glyphs);
     }
+
+    stats.cells_processed = cells_processed;
+    stats.spans = span_count;
+    stats.elapsed = started.elapsed();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dirty_spans_split_at_row_boundaries() {
+        // 4 columns x 3 rows
+        let dirty = [
+            true, true, false, true, //
+            true, true, true, true, //
+            false, false, false, false,
+        ];
+        assert_eq!(dirty_spans(&dirty, 4), vec![0..2, 3..4, 4..8]);
+    }
+
+    #[test]
+    fn test_dirty_spans_empty() {
+        assert!(dirty_spans(&[false; 8], 4).is_empty());
+        assert!(dirty_spans(&[], 4).is_empty());
+    }
+
+    #[test]
+    fn test_driver_draw_from_thread_syncs_without_blocking() {
+        use ratatui::style::Style;
+
+        use crate::testing::TerminalTestHarness;
+        use crate::TerminalEmuPlugin;
+
+        struct Term;
+
+        let plugin = TerminalEmuPlugin::<Term>::new().columns(6).rows(1);
+        let mut harness = TerminalTestHarness::new(plugin);
+        let driver = harness.terminal().driver();
+        std::thread::spawn(move || {
+            let text = "tail -f".to_string();
+            driver
+                .draw(|frame| frame.buffer_mut().set_string(0, 0, &text, Style::default()))
+                .unwrap();
+        })
+        .join()
+        .unwrap();
+
+        // While someone else holds the lock, sync skips the frame
+        let shared = harness.terminal().0.clone();
+        let guard = shared.lock().unwrap();
+        harness.update();
+        drop(guard);
+        assert_eq!(harness.style(0, 0).map(|s| s.symbol).as_deref(), Some(" "));
+
+        harness.update();
+        assert_eq!(harness.style(0, 0).map(|s| s.symbol).as_deref(), Some("t"));
+        assert_eq!(harness.style(5, 0).map(|s| s.symbol).as_deref(), Some("-"));
+    }
+
+    #[test]
+    fn test_transparent_background_only_clears_reset_backgrounds() {
+        use ratatui::style::{Color as RatColor, Style};
+
+        use crate::testing::TerminalTestHarness;
+        use crate::TerminalEmuPlugin;
+
+        struct Term;
+
+        let plugin = TerminalEmuPlugin::<Term>::new()
+            .columns(2)
+            .rows(1)
+            .transparent_background(true);
+        let mut harness = TerminalTestHarness::new(plugin);
+        harness.draw(|frame| {
+            let buffer = frame.buffer_mut();
+            buffer.set_string(0, 0, "a", Style::default());
+            buffer.set_string(1, 0, "b", Style::default().bg(RatColor::Blue));
+        });
+
+        // The synced style and the background sprite drawn from it
+        let bg_alpha = |col: u16| {
+            let world = harness.app.world();
+            let cell = world.resource::<CellEntityIndex<Term>>().get(col, 0);
+            let sprite = world.get::<Sprite>(cell.unwrap()).unwrap();
+            let style = harness.style(col, 0).unwrap();
+            (style.bg.alpha(), sprite.color.alpha())
+        };
+        assert_eq!(bg_alpha(0), (0.0, 0.0));
+        assert_eq!(bg_alpha(1), (1.0, 1.0));
+    }
 }
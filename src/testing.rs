@@ -0,0 +1,126 @@
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use ratatui::backend::Backend as _;
+use ratatui::buffer::Cell;
+
+#[cfg(feature = "render")]
+use crate::grid::{CellEntityIndex, CellStyle};
+use crate::{TerminalEmuPlugin, TerminalResource};
+
+/// Time each harness update advances the clock by (one 60 Hz frame), so
+/// effects animate identically on every run.
+pub const FRAME_TIME: Duration = Duration::from_nanos(16_666_667);
+
+/// A windowless `App` running a single terminal, for asserting on what a
+/// ratatui draw produces without a GPU.
+///
+/// `draw` renders a frame and ticks the app once, so buffer contents and
+/// (with the `render` feature) synced `CellStyle`s can be checked right
+/// after. The clock advances by exactly `FRAME_TIME` per update.
+pub struct TerminalTestHarness<T: 'static + Send + Sync> {
+    pub app: App,
+    _marker: PhantomData<T>,
+}
+
+impl<T: 'static + Send + Sync> TerminalTestHarness<T> {
+    /// Build the app around `plugin` and run its startup systems.
+    pub fn new(plugin: TerminalEmuPlugin<T>) -> Self {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME_TIME));
+
+        // The atlas and cell sprites only need CPU-side assets
+        #[cfg(feature = "render")]
+        app.add_plugins(bevy::asset::AssetPlugin::default())
+            .init_asset::<Image>()
+            .init_asset::<TextureAtlasLayout>()
+            .init_asset::<bevy::text::Font>();
+
+        app.add_plugins(plugin);
+        app.update();
+        Self {
+            app,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Draw a frame with `f`, then tick the app once.
+    pub fn draw(&mut self, f: impl FnOnce(&mut ratatui::Frame)) -> &mut Self {
+        self.terminal()
+            .draw(f)
+            .expect("terminal lock poisoned by an earlier panic");
+        self.update()
+    }
+
+    /// Tick the app once without drawing, e.g. to advance effects.
+    pub fn update(&mut self) -> &mut Self {
+        self.app.update();
+        self
+    }
+
+    pub fn terminal(&self) -> &TerminalResource<T> {
+        self.app.world().resource::<TerminalResource<T>>()
+    }
+
+    /// The buffer cell at (col, row).
+    pub fn cell(&self, col: u16, row: u16) -> Option<Cell> {
+        self.terminal()
+            .with_backend(|backend| backend.cell(col, row).cloned())
+            .ok()
+            .flatten()
+    }
+
+    /// The symbols of row `row` joined into a string.
+    pub fn row_text(&self, row: u16) -> String {
+        self.terminal()
+            .with_backend(|backend| {
+                // BevyBackend's error type is Infallible
+                let Ok(size) = backend.size();
+                (0..size.width)
+                    .filter_map(|col| backend.cell(col, row))
+                    .map(|cell| cell.symbol())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The synced style of the cell entity at (col, row).
+    #[cfg(feature = "render")]
+    pub fn style(&self, col: u16, row: u16) -> Option<CellStyle> {
+        let world = self.app.world();
+        let entity = world.resource::<CellEntityIndex<T>>().get(col, row)?;
+        world.get::<CellStyle>(entity).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::widgets::{Block, Borders};
+
+    use super::*;
+
+    struct Term;
+
+    #[test]
+    fn test_block_borders_land_in_cells() {
+        let plugin = TerminalEmuPlugin::<Term>::new().columns(10).rows(4);
+        let mut harness = TerminalTestHarness::new(plugin);
+        harness.draw(|frame| {
+            frame.render_widget(Block::default().borders(Borders::ALL), frame.area());
+        });
+
+        assert_eq!(harness.row_text(0), "┌────────┐");
+        assert_eq!(harness.row_text(1), "│        │");
+        assert_eq!(harness.row_text(3), "└────────┘");
+        assert_eq!(harness.cell(9, 2).map(|c| c.symbol().to_string()).as_deref(), Some("│"));
+
+        #[cfg(feature = "render")]
+        {
+            assert_eq!(harness.style(0, 0).map(|s| s.symbol).as_deref(), Some("┌"));
+            assert_eq!(harness.style(5, 1).map(|s| s.symbol).as_deref(), Some(" "));
+        }
+    }
+}
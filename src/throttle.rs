@@ -0,0 +1,95 @@
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::TerminalConfig;
+
+/// Whether terminal `T` syncs this frame, under
+/// `TerminalConfig::update_interval`. Always due when no interval is set.
+#[derive(Resource, Clone, Debug)]
+pub struct UpdateThrottle<T: 'static + Send + Sync> {
+    /// True on frames where the terminal updates.
+    pub due: bool,
+    /// Time since the last due frame.
+    since_update: Duration,
+    _marker: PhantomData<T>,
+}
+
+impl<T: 'static + Send + Sync> Default for UpdateThrottle<T> {
+    fn default() -> Self {
+        Self {
+            due: true,
+            // The first frame is always due
+            since_update: Duration::MAX,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Decides at the start of each frame whether terminal `T` is due to update.
+/// Time that passes while it waits isn't carried over, so a slow frame
+/// doesn't cause a burst of updates after it.
+pub fn tick_update_throttle<T: 'static + Send + Sync>(
+    time: Res<Time>,
+    config: Res<TerminalConfig<T>>,
+    mut throttle: ResMut<UpdateThrottle<T>>,
+) {
+    let Some(interval) = config.update_interval else {
+        if !throttle.due {
+            throttle.due = true;
+        }
+        return;
+    };
+
+    throttle.since_update = throttle.since_update.saturating_add(time.delta());
+    let due = throttle.since_update >= interval;
+    if due {
+        throttle.since_update = Duration::ZERO;
+    }
+    if throttle.due != due {
+        throttle.due = due;
+    }
+}
+
+/// Run condition: true on frames terminal `T` updates. Add it to draw
+/// systems in `TerminalSet::AppTick` so they only redraw when the result
+/// will be synced.
+pub fn terminal_update_due<T: 'static + Send + Sync>(throttle: Res<UpdateThrottle<T>>) -> bool {
+    throttle.due
+}
+
+#[cfg(all(test, feature = "render"))]
+mod tests {
+    use ratatui::style::Style;
+
+    use crate::testing::TerminalTestHarness;
+    use crate::TerminalEmuPlugin;
+
+    use super::*;
+
+    struct Term;
+
+    #[test]
+    fn test_sync_waits_for_update_interval() {
+        let plugin = TerminalEmuPlugin::<Term>::new()
+            .columns(4)
+            .rows(1)
+            .update_interval(Duration::from_millis(50));
+        let mut harness = TerminalTestHarness::new(plugin);
+        let symbol = |harness: &TerminalTestHarness<Term>| harness.style(0, 0).unwrap().symbol;
+
+        harness.draw(|frame| {
+            frame.buffer_mut().set_string(0, 0, "a", Style::default());
+        });
+        assert_eq!(symbol(&harness), " ");
+
+        // Three 60 Hz frames make up the interval
+        harness.update().update();
+        assert_eq!(symbol(&harness), "a");
+        assert!(harness.app.world().resource::<UpdateThrottle<Term>>().due);
+
+        harness.update();
+        assert!(!harness.app.world().resource::<UpdateThrottle<Term>>().due);
+    }
+}
@@ -0,0 +1,161 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+use bevy::ui::{ComputedNode, UiGlobalTransform};
+use bevy::window::PrimaryWindow;
+
+use crate::grid::GridPosition;
+use crate::render_target::TerminalRenderTarget;
+use crate::{TerminalConfig, TerminalLayout};
+
+/// Displays a terminal's render-target image inside the Bevy UI layout.
+///
+/// Requires `TerminalConfig::render_layer` so the terminal renders offscreen.
+/// Spawn it with a `Node` describing its size; the image is filled in
+/// automatically once the render target exists.
+#[derive(Component)]
+#[require(Node, ImageNode)]
+pub struct TerminalUiNode<T: 'static + Send + Sync> {
+    /// Derive columns/rows from the node's computed size
+    /// (`cols = node_width / cell_width`), resizing the terminal as the
+    /// layout changes.
+    pub auto_size: bool,
+    /// Grid cell currently under the mouse cursor, if any.
+    pub hovered_cell: Option<GridPosition>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: 'static + Send + Sync> Default for TerminalUiNode<T> {
+    fn default() -> Self {
+        Self {
+            auto_size: false,
+            hovered_cell: None,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: 'static + Send + Sync> TerminalUiNode<T> {
+    /// A node whose grid dimensions follow its computed UI size.
+    pub fn auto_sized() -> Self {
+        Self {
+            auto_size: true,
+            ..default()
+        }
+    }
+}
+
+/// Map a physical-pixel point in UI space to the grid cell under it.
+pub fn ui_point_to_cell(
+    node: &ComputedNode,
+    transform: UiGlobalTransform,
+    point: Vec2,
+    columns: u16,
+    rows: u16,
+) -> Option<GridPosition> {
+    // normalize_point puts the node center at the origin, corners at ±0.5
+    let uv = node.normalize_point(transform, point)? + Vec2::splat(0.5);
+    if uv.x < 0.0 || uv.y < 0.0 || uv.x >= 1.0 || uv.y >= 1.0 {
+        return None;
+    }
+    Some(GridPosition {
+        col: (uv.x * columns as f32) as u16,
+        row: (uv.y * rows as f32) as u16,
+    })
+}
+
+/// Points every `TerminalUiNode<T>` at the terminal's render-target image.
+pub fn attach_ui_node_image<T: 'static + Send + Sync>(
+    target: Option<Res<TerminalRenderTarget<T>>>,
+    mut nodes: Query<&mut ImageNode, With<TerminalUiNode<T>>>,
+) {
+    let Some(target) = target else {
+        return;
+    };
+    for mut image_node in nodes.iter_mut() {
+        if image_node.image != target.image {
+            image_node.image = target.image.clone();
+        }
+    }
+}
+
+/// Resizes the terminal to fit auto-sized UI nodes when their layout changes.
+/// The grid itself is rebuilt by `resize_grid` during sync.
+pub fn resize_from_ui_node<T: 'static + Send + Sync>(
+    mut config: ResMut<TerminalConfig<T>>,
+    layout: Res<TerminalLayout<T>>,
+    nodes: Query<(&TerminalUiNode<T>, &ComputedNode), Changed<ComputedNode>>,
+) {
+    for (node, computed) in nodes.iter() {
+        if !node.auto_size {
+            continue;
+        }
+        let logical = computed.size * computed.inverse_scale_factor;
+        let columns = (logical.x / layout.cell_width).floor().max(1.0) as u16;
+        let rows = (logical.y / layout.cell_height).floor().max(1.0) as u16;
+        if config.columns != columns || config.rows != rows {
+            config.columns = columns;
+            config.rows = rows;
+        }
+    }
+}
+
+/// Tracks which grid cell the mouse cursor is over for each UI node.
+pub fn update_ui_hovered_cell<T: 'static + Send + Sync>(
+    config: Res<TerminalConfig<T>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut nodes: Query<(&mut TerminalUiNode<T>, &ComputedNode, &UiGlobalTransform)>,
+) {
+    let cursor = window_query
+        .single()
+        .ok()
+        .and_then(|w| w.physical_cursor_position());
+
+    for (mut node, computed, transform) in nodes.iter_mut() {
+        let hovered = cursor.and_then(|point| {
+            ui_point_to_cell(computed, *transform, point, config.columns, config.rows)
+        });
+        if node.hovered_cell != hovered {
+            node.hovered_cell = hovered;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::CellEntityIndex;
+    use crate::testing::TerminalTestHarness;
+    use crate::TerminalEmuPlugin;
+
+    struct Term;
+
+    #[test]
+    fn test_ui_node_shows_target_and_sizes_grid() {
+        let plugin = TerminalEmuPlugin::<Term>::new()
+            .columns(4)
+            .rows(2)
+            .render_layer(1);
+        let mut harness = TerminalTestHarness::new(plugin);
+        let layout = harness.app.world().resource::<TerminalLayout<Term>>();
+        let cell = Vec2::new(layout.cell_width, layout.cell_height);
+
+        // 12.5 x 3.5 cells of logical space, at a 2x scale factor
+        let computed = ComputedNode {
+            size: cell * Vec2::new(12.5, 3.5) * 2.0,
+            inverse_scale_factor: 0.5,
+            ..default()
+        };
+        let node = TerminalUiNode::<Term>::auto_sized();
+        let node = harness.app.world_mut().spawn((node, computed)).id();
+        harness.update().update();
+
+        let world = harness.app.world();
+        let target = world.resource::<TerminalRenderTarget<Term>>();
+        assert_eq!(world.get::<ImageNode>(node).unwrap().image, target.image);
+        let config = world.resource::<TerminalConfig<Term>>();
+        assert_eq!((config.columns, config.rows), (12, 3));
+        let index = world.resource::<CellEntityIndex<Term>>();
+        assert_eq!((index.columns, index.rows), (12, 3));
+    }
+}